@@ -21,6 +21,15 @@ cfg_if! {if #[cfg(feature = "with-dom")] {
     pub use dom_attr::{DomAttr, DomAttrValue, GroupedDomAttrValues};
     pub use http::Http;
     pub use program::{MountAction, MountTarget, Program, MountProcedure};
+    pub use hydrate::HydrationMismatch;
+    #[cfg(feature = "with-serde")]
+    pub use embedded_state::{embedded_state, EmbeddedStateError};
+    #[cfg(feature = "with-serde")]
+    pub use server_program::{
+        ChannelTransport, ClientMessage, RemoteClient, ServerMessage, ServerProgram, Transport,
+    };
+    pub use backend::{Listener, MemoryDom, MemoryNode, RenderBackend, Renderer, WebBackend};
+    pub use test_app::{TestApp, TestAppError};
     pub use util::{
         document, history, now, performance,
         spawn_local, window, inject_style,
@@ -30,19 +39,27 @@ cfg_if! {if #[cfg(feature = "with-dom")] {
     pub use timeout::{delay, request_timeout_callback, TimeoutCallbackHandle};
     pub use dispatch::Dispatch;
     use crate::dom::events::MountEvent;
+    use crate::dom::events::RemoteEvent;
     pub use window::Window;
     pub use dom_node::DomNode;
     pub use document::Document;
     pub use time::Time;
 
     mod application;
+    pub mod backend;
     pub mod dispatch;
     mod dom_node;
     mod dom_patch;
     mod dom_attr;
     pub mod events;
+    mod hydrate;
+    #[cfg(feature = "with-serde")]
+    mod embedded_state;
+    #[cfg(feature = "with-serde")]
+    mod server_program;
     mod http;
     mod program;
+    mod test_app;
     pub mod util;
     mod raf;
     mod ric;
@@ -59,6 +76,8 @@ cfg_if! {if #[cfg(feature = "with-dom")] {
         WebEvent(web_sys::Event),
         /// custom event here follows
         MountEvent(MountEvent),
+        /// an event which was sent from a remote client
+        RemoteEvent(RemoteEvent),
     }
 
 }}
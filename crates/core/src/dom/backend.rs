@@ -0,0 +1,204 @@
+//! The operations which are needed to create and update the nodes of a document,
+//! so the views can be rendered into a document other than the browser DOM.
+//!
+//! [`WebBackend`] is the browser DOM with `web_sys`, which is the default backend that
+//! [`Program`](crate::dom::Program) renders into. [`MemoryDom`] is an in-memory document which
+//! works natively, the [`Renderer`] renders a view into it or into any other backend.
+//! Both of them create and patch the nodes with the same [`DomNode`] and [`DomPatch`](crate::dom::DomPatch).
+use crate::dom::dom_node::{CreateDomNode, DomInner};
+use crate::dom::{DomNode, DomPatch, Event, StatefulModel};
+use crate::vdom::{diff, ApplyPatchError, EventCallback, ListenerOptions, Node, Style};
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+pub use memory::{MemoryDom, MemoryNode};
+pub use web::WebBackend;
+
+mod memory;
+mod web;
+
+/// The event listener of an element in a [`RenderBackend`]
+pub type Listener = Rc<dyn Fn(Event)>;
+
+/// The operations needed to create and update the nodes of a document.
+///
+/// The operations are associated functions, since the nodes refer to the document they are in.
+/// The nodes are referred to with `Self::Node`, which must be cheap to clone,
+/// such as a reference counted handle.
+pub trait RenderBackend {
+    /// a reference to a node in the document
+    type Node: Clone + PartialEq + fmt::Debug;
+    /// an attached event listener, which is passed back when the listener is detached
+    type ListenerHandle;
+
+    /// create an element with this tag, in the `namespace` if there is any
+    fn create_element(tag: &str, namespace: Option<&str>) -> Self::Node;
+    /// create a text node
+    fn create_text(text: &str) -> Self::Node;
+    /// create a comment node
+    fn create_comment(comment: &str) -> Self::Node;
+    /// create a fragment, a container of nodes which is not rendered itself
+    fn create_fragment() -> Self::Node;
+    /// the lowercased tag of the node, if it is an element
+    fn tag(node: &Self::Node) -> Option<String>;
+    /// set the attribute of the element, in the `namespace` if there is any
+    fn set_attribute(element: &Self::Node, name: &str, namespace: Option<&str>, value: &str);
+    /// remove the attribute of the element
+    fn remove_attribute(element: &Self::Node, name: &str);
+    /// set these style properties of the element, leaving its other style properties as is
+    fn set_style_properties(element: &Self::Node, styles: &[Style]);
+    /// remove the style properties with these names from the element
+    fn remove_style_properties(element: &Self::Node, names: &[String]);
+    /// add the class names to the element, leaving its other class names as is
+    fn add_classes(element: &Self::Node, classes: &[String]);
+    /// remove the class names from the element
+    fn remove_classes(element: &Self::Node, classes: &[String]);
+    /// set the content of a text or comment node
+    fn set_text(node: &Self::Node, text: &str);
+    /// attach the `listener` of the `event` to the element, next to the other listeners of the event.
+    /// The `options` tell how the listener is attached and how the event is handled before the listener is called.
+    fn add_event_listener(
+        element: &Self::Node,
+        event: &str,
+        listener: Listener,
+        options: ListenerOptions,
+    ) -> Self::ListenerHandle;
+    /// detach the listener of the `event` from the element, it was attached with the `options`
+    fn remove_event_listener(
+        element: &Self::Node,
+        event: &str,
+        listener: Self::ListenerHandle,
+        options: ListenerOptions,
+    );
+    /// append the `child` as the last child of the `parent`
+    fn append_child(parent: &Self::Node, child: &Self::Node);
+    /// append the nodes of the `html` as the last children of the `parent`,
+    /// the html is used as is, such as the html entities of a symbol
+    fn append_html(parent: &Self::Node, html: &str);
+    /// insert the `node` before the `target`, the node is moved if it is already in the document
+    fn insert_before(target: &Self::Node, node: &Self::Node);
+    /// insert the `node` after the `target`, the node is moved if it is already in the document
+    fn insert_after(target: &Self::Node, node: &Self::Node);
+    /// put the `replacement` in place of the `target`, the target is removed
+    fn replace_node(target: &Self::Node, replacement: &Self::Node);
+    /// remove the node from its parent, along with its descendants.
+    /// The listeners of the removed nodes are no longer called.
+    fn remove_node(node: &Self::Node);
+    /// remove all the child nodes of the element
+    fn clear_children(element: &Self::Node);
+    /// called when the node is inserted into its parent,
+    /// the browser DOM dispatches the `mount` event to it
+    fn dispatch_mount_event(_node: &Self::Node) {}
+}
+
+/// Renders the view into a document of the backend and keeps it updated with the next views.
+///
+/// The nodes are created and patched the same way as the [`Program`](crate::dom::Program) does it
+/// in the browser DOM.
+///
+/// # Examples
+/// ```rust
+/// use sauron::{dom::{MemoryDom, RenderBackend, Renderer}, *};
+///
+/// let body = MemoryDom::create_element("body", None);
+/// let mut renderer = Renderer::<MemoryDom, ()>::mount(&body, div([], [text("hello")]), |_| {});
+/// assert_eq!(body.inner_html(), "<div>hello</div>");
+///
+/// renderer.update(div([], [text("world")])).unwrap();
+/// assert_eq!(body.inner_html(), "<div>world</div>");
+/// ```
+pub struct Renderer<B, MSG>
+where
+    B: RenderBackend,
+{
+    dispatcher: Dispatcher<MSG>,
+    current_vdom: Node<MSG>,
+    /// the node where the view is mounted into
+    mount_node: DomNode<B>,
+    root_node: DomNode<B>,
+}
+
+/// creates the nodes of the views, the msgs of their listeners are passed to the dispatch
+struct Dispatcher<MSG>(Rc<dyn Fn(MSG)>);
+
+impl<B, MSG> Renderer<B, MSG>
+where
+    B: RenderBackend,
+    MSG: 'static,
+{
+    /// create the nodes of the `vdom` and append them into the `mount_node`.
+    /// The msgs from the event listeners are passed to `dispatch`
+    pub fn mount(mount_node: &B::Node, vdom: Node<MSG>, dispatch: impl Fn(MSG) + 'static) -> Self {
+        let dispatcher = Dispatcher(Rc::new(dispatch));
+        let mount_node = DomNode {
+            inner: DomInner::Element {
+                element: mount_node.clone(),
+                listeners: Rc::new(RefCell::new(None)),
+                children: Rc::new(RefCell::new(vec![])),
+            },
+            parent: Rc::new(None),
+        };
+        let root_node = dispatcher.create_dom_node(Rc::new(None), &vdom);
+        mount_node.append_children(vec![root_node.clone()]);
+        Self {
+            dispatcher,
+            current_vdom: vdom,
+            mount_node,
+            root_node,
+        }
+    }
+
+    /// the node of the root of the view
+    pub fn root_node(&self) -> B::Node {
+        self.root_node.as_node()
+    }
+
+    /// the current view
+    pub fn vdom(&self) -> &Node<MSG> {
+        &self.current_vdom
+    }
+
+    /// apply the patches of the difference of the current view and the `new_vdom` into the document,
+    /// then the `new_vdom` becomes the current view
+    ///
+    /// All the nodes of the patches are looked up first, so nothing is changed when a node is not found.
+    pub fn update(&mut self, new_vdom: Node<MSG>) -> Result<(), ApplyPatchError> {
+        let patches = diff(&self.current_vdom, &new_vdom);
+        let dom_patches = DomPatch::convert_all(&self.dispatcher, &self.root_node, &patches)?;
+        drop(patches);
+        for dom_patch in dom_patches {
+            if let Some(root_node) = dom_patch.apply(Some(&self.mount_node)) {
+                self.root_node = root_node;
+            }
+        }
+        self.current_vdom = new_vdom;
+        Ok(())
+    }
+}
+
+impl<B, MSG> CreateDomNode<B> for Dispatcher<MSG>
+where
+    B: RenderBackend,
+    MSG: 'static,
+{
+    type MSG = MSG;
+
+    fn create_listener(&self, event_listener: &EventCallback<MSG>) -> Listener {
+        let dispatch = Rc::clone(&self.0);
+        let event_listener = event_listener.clone();
+        Rc::new(move |event: Event| dispatch(event_listener.emit(event)))
+    }
+
+    /// the stateful components need the browser DOM, a comment is put in their place
+    fn create_stateful_component(
+        &self,
+        parent_node: Rc<Option<DomNode<B>>>,
+        _comp: &StatefulModel<MSG>,
+    ) -> DomNode<B> {
+        DomNode {
+            inner: DomInner::Comment(B::create_comment("stateful component")),
+            parent: parent_node,
+        }
+    }
+}
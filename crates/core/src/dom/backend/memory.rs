@@ -0,0 +1,433 @@
+//! An in-memory document, which can be rendered into without a browser
+use crate::dom::backend::{Listener, RenderBackend};
+use crate::dom::Event;
+use crate::vdom::{escape, ListenerOptions, Style};
+use indexmap::IndexMap;
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::{Rc, Weak};
+
+/// Elements which has no content and no closing tag
+///
+/// <https://html.spec.whatwg.org/multipage/syntax.html#void-elements>
+const VOID_ELEMENTS: [&str; 13] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Attributes which are left out when they are false, the same as in rendering
+const BOOLEAN_ATTRIBUTES: [&str; 3] = ["open", "checked", "disabled"];
+
+/// An in-memory document as a [`RenderBackend`].
+///
+/// The nodes are kept alive for as long as they are referred to, the same as in the browser DOM.
+/// Unlike a `DocumentFragment` of the browser DOM, a fragment keeps its children when it is inserted.
+#[derive(Debug, Clone, Copy)]
+pub struct MemoryDom;
+
+/// A node in the [`MemoryDom`], two nodes are equal if they refer to the same node
+#[derive(Clone)]
+pub struct MemoryNode(Rc<RefCell<Entry>>);
+
+struct Entry {
+    content: Content,
+    children: Vec<MemoryNode>,
+    parent: Weak<RefCell<Entry>>,
+}
+
+enum Content {
+    Element {
+        tag: String,
+        namespace: Option<String>,
+        attrs: IndexMap<String, String>,
+        listeners: Vec<(String, Listener, ListenerOptions)>,
+    },
+    Text(String),
+    Comment(String),
+    Symbol(String),
+    Fragment,
+}
+
+impl MemoryNode {
+    fn new(content: Content) -> Self {
+        Self(Rc::new(RefCell::new(Entry {
+            content,
+            children: vec![],
+            parent: Weak::new(),
+        })))
+    }
+
+    /// the lowercased tag of the element
+    pub fn tag(&self) -> Option<String> {
+        match &self.0.borrow().content {
+            Content::Element { tag, .. } => Some(tag.to_lowercase()),
+            _ => None,
+        }
+    }
+
+    /// the namespace of the element
+    pub fn namespace(&self) -> Option<String> {
+        match &self.0.borrow().content {
+            Content::Element { namespace, .. } => namespace.clone(),
+            _ => None,
+        }
+    }
+
+    /// the value of the attribute of the element
+    pub fn attribute(&self, name: &str) -> Option<String> {
+        match &self.0.borrow().content {
+            Content::Element { attrs, .. } => attrs.get(name).cloned(),
+            _ => None,
+        }
+    }
+
+    /// the content of a text or comment node
+    pub fn text(&self) -> Option<String> {
+        match &self.0.borrow().content {
+            Content::Text(text) | Content::Comment(text) => Some(text.clone()),
+            _ => None,
+        }
+    }
+
+    /// the child nodes of the element or fragment
+    pub fn children(&self) -> Vec<MemoryNode> {
+        self.0.borrow().children.clone()
+    }
+
+    /// the parent node
+    pub fn parent(&self) -> Option<MemoryNode> {
+        self.0.borrow().parent.upgrade().map(MemoryNode)
+    }
+
+    /// call the listeners of the `event` of the element, returns false if there is no listener.
+    ///
+    /// The listeners with the `once` option are removed before they are called.
+    pub fn dispatch_event(&self, event: &str, dom_event: Event) -> bool {
+        let called: Vec<Listener> = match &mut self.0.borrow_mut().content {
+            Content::Element { listeners, .. } => {
+                let called = listeners
+                    .iter()
+                    .filter(|(name, ..)| name == event)
+                    .map(|(_, listener, _)| Rc::clone(listener))
+                    .collect();
+                listeners.retain(|(name, _, options)| name != event || !options.once);
+                called
+            }
+            _ => vec![],
+        };
+        // the listeners are called after the node is released, since they may change the document
+        for listener in called.iter() {
+            listener(dom_event.clone());
+        }
+        !called.is_empty()
+    }
+
+    /// the options of the first listener of the `event` of the element
+    pub fn listener_options(&self, event: &str) -> Option<ListenerOptions> {
+        match &self.0.borrow().content {
+            Content::Element { listeners, .. } => listeners
+                .iter()
+                .find(|(name, ..)| name == event)
+                .map(|(_, _, options)| *options),
+            _ => None,
+        }
+    }
+
+    /// serialize this node and its descendants into html, the same as `outerHTML` in the browser
+    pub fn outer_html(&self) -> String {
+        let mut buffer = String::new();
+        self.write_html(&mut buffer).expect("must write");
+        buffer
+    }
+
+    /// serialize the descendants of this node into html, the same as `innerHTML` in the browser
+    pub fn inner_html(&self) -> String {
+        let mut buffer = String::new();
+        for child in self.0.borrow().children.iter() {
+            child.write_html(&mut buffer).expect("must write");
+        }
+        buffer
+    }
+
+    fn write_html(&self, buffer: &mut dyn fmt::Write) -> fmt::Result {
+        let entry = self.0.borrow();
+        match &entry.content {
+            Content::Element { tag, attrs, .. } => {
+                write!(buffer, "<{tag}")?;
+                for (name, value) in attrs.iter() {
+                    write!(buffer, " {name}=\"")?;
+                    escape::write_attribute_value(buffer, value)?;
+                    write!(buffer, "\"")?;
+                }
+                write!(buffer, ">")?;
+                if VOID_ELEMENTS
+                    .iter()
+                    .any(|void| void.eq_ignore_ascii_case(tag))
+                {
+                    return Ok(());
+                }
+                for child in entry.children.iter() {
+                    match &child.0.borrow().content {
+                        Content::Text(text) if escape::is_raw_text_element(tag) => {
+                            escape::write_raw_text(buffer, text)?
+                        }
+                        _ => child.write_html(buffer)?,
+                    }
+                }
+                write!(buffer, "</{tag}>")
+            }
+            Content::Text(text) => escape::write_text(buffer, text),
+            Content::Comment(comment) => {
+                write!(buffer, "<!--")?;
+                escape::write_comment(buffer, comment)?;
+                write!(buffer, "-->")
+            }
+            Content::Symbol(symbol) => write!(buffer, "{symbol}"),
+            Content::Fragment => {
+                for child in entry.children.iter() {
+                    child.write_html(buffer)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn with_attrs(&self, f: impl FnOnce(&mut IndexMap<String, String>)) {
+        if let Content::Element { attrs, .. } = &mut self.0.borrow_mut().content {
+            f(attrs)
+        }
+    }
+
+    /// drop the listeners of this node and its descendants, since they are removed for good
+    fn drop_listeners(&self) {
+        let entry = &mut *self.0.borrow_mut();
+        if let Content::Element { listeners, .. } = &mut entry.content {
+            listeners.clear();
+        }
+        for child in entry.children.iter() {
+            child.drop_listeners();
+        }
+    }
+
+    fn detach(&self) {
+        let Some(parent) = self.parent() else {
+            return;
+        };
+        parent.0.borrow_mut().children.retain(|child| child != self);
+        self.0.borrow_mut().parent = Weak::new();
+    }
+
+    /// insert the `node` into the parent of this node, at `offset` from the position of this node
+    fn insert_beside(&self, node: &MemoryNode, offset: usize) {
+        let parent = self.parent().expect("must have a parent");
+        node.detach();
+        let index = parent
+            .0
+            .borrow()
+            .children
+            .iter()
+            .position(|child| child == self)
+            .expect("must be in the parent");
+        node.0.borrow_mut().parent = Rc::downgrade(&parent.0);
+        parent
+            .0
+            .borrow_mut()
+            .children
+            .insert(index + offset, node.clone());
+    }
+}
+
+impl PartialEq for MemoryNode {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for MemoryNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MemoryNode({})", self.outer_html())
+    }
+}
+
+/// the style properties of the style attribute, ie: `display:none;color:red;`
+fn style_properties(style: &str) -> Vec<(String, String)> {
+    style
+        .split(';')
+        .filter_map(|property| property.split_once(':'))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+fn set_style_properties(attrs: &mut IndexMap<String, String>, properties: Vec<(String, String)>) {
+    let style = properties
+        .iter()
+        .map(|(name, value)| format!("{name}:{value};"))
+        .collect::<String>();
+    attrs.insert("style".to_string(), style);
+}
+
+fn class_list(attrs: &IndexMap<String, String>) -> Vec<String> {
+    attrs
+        .get("class")
+        .map(|class| class.split_whitespace().map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+impl RenderBackend for MemoryDom {
+    type Node = MemoryNode;
+    type ListenerHandle = Listener;
+
+    fn create_element(tag: &str, namespace: Option<&str>) -> MemoryNode {
+        MemoryNode::new(Content::Element {
+            tag: tag.to_string(),
+            namespace: namespace.map(str::to_string),
+            attrs: IndexMap::new(),
+            listeners: vec![],
+        })
+    }
+
+    fn create_text(text: &str) -> MemoryNode {
+        MemoryNode::new(Content::Text(text.to_string()))
+    }
+
+    fn create_comment(comment: &str) -> MemoryNode {
+        MemoryNode::new(Content::Comment(comment.to_string()))
+    }
+
+    fn create_fragment() -> MemoryNode {
+        MemoryNode::new(Content::Fragment)
+    }
+
+    fn tag(node: &MemoryNode) -> Option<String> {
+        node.tag()
+    }
+
+    fn set_attribute(element: &MemoryNode, name: &str, _namespace: Option<&str>, value: &str) {
+        element.with_attrs(|attrs| {
+            if BOOLEAN_ATTRIBUTES.contains(&name) && value != "true" {
+                attrs.shift_remove(name);
+            } else {
+                attrs.insert(name.to_string(), value.to_string());
+            }
+        })
+    }
+
+    fn remove_attribute(element: &MemoryNode, name: &str) {
+        element.with_attrs(|attrs| {
+            attrs.shift_remove(name);
+        })
+    }
+
+    fn set_style_properties(element: &MemoryNode, styles: &[Style]) {
+        element.with_attrs(|attrs| {
+            let mut properties = style_properties(attrs.get("style").map_or("", |s| s));
+            for style in styles {
+                let value = style.value.to_string();
+                match properties.iter_mut().find(|(name, _)| *name == style.name) {
+                    Some(property) => property.1 = value,
+                    None => properties.push((style.name.to_string(), value)),
+                }
+            }
+            set_style_properties(attrs, properties);
+        })
+    }
+
+    fn remove_style_properties(element: &MemoryNode, names: &[String]) {
+        element.with_attrs(|attrs| {
+            if let Some(style) = attrs.get("style") {
+                let mut properties = style_properties(style);
+                properties.retain(|(name, _)| !names.contains(name));
+                set_style_properties(attrs, properties);
+            }
+        })
+    }
+
+    fn add_classes(element: &MemoryNode, classes: &[String]) {
+        element.with_attrs(|attrs| {
+            let mut class_names = class_list(attrs);
+            for class in classes {
+                if !class_names.contains(class) {
+                    class_names.push(class.to_string());
+                }
+            }
+            attrs.insert("class".to_string(), class_names.join(" "));
+        })
+    }
+
+    fn remove_classes(element: &MemoryNode, classes: &[String]) {
+        element.with_attrs(|attrs| {
+            if attrs.contains_key("class") {
+                let mut class_names = class_list(attrs);
+                class_names.retain(|class| !classes.contains(class));
+                attrs.insert("class".to_string(), class_names.join(" "));
+            }
+        })
+    }
+
+    fn set_text(node: &MemoryNode, text: &str) {
+        if let Content::Text(content) | Content::Comment(content) = &mut node.0.borrow_mut().content
+        {
+            *content = text.to_string();
+        }
+    }
+
+    fn add_event_listener(
+        element: &MemoryNode,
+        event: &str,
+        listener: Listener,
+        options: ListenerOptions,
+    ) -> Listener {
+        if let Content::Element { listeners, .. } = &mut element.0.borrow_mut().content {
+            listeners.push((event.to_string(), Rc::clone(&listener), options));
+        }
+        listener
+    }
+
+    fn remove_event_listener(
+        element: &MemoryNode,
+        event: &str,
+        listener: Listener,
+        _options: ListenerOptions,
+    ) {
+        if let Content::Element { listeners, .. } = &mut element.0.borrow_mut().content {
+            listeners
+                .retain(|(name, attached, _)| name != event || !Rc::ptr_eq(attached, &listener));
+        }
+    }
+
+    fn append_child(parent: &MemoryNode, child: &MemoryNode) {
+        child.detach();
+        child.0.borrow_mut().parent = Rc::downgrade(&parent.0);
+        parent.0.borrow_mut().children.push(child.clone());
+    }
+
+    fn append_html(parent: &MemoryNode, html: &str) {
+        Self::append_child(parent, &MemoryNode::new(Content::Symbol(html.to_string())));
+    }
+
+    fn insert_before(target: &MemoryNode, node: &MemoryNode) {
+        target.insert_beside(node, 0);
+    }
+
+    fn insert_after(target: &MemoryNode, node: &MemoryNode) {
+        target.insert_beside(node, 1);
+    }
+
+    fn replace_node(target: &MemoryNode, replacement: &MemoryNode) {
+        target.insert_beside(replacement, 0);
+        Self::remove_node(target);
+    }
+
+    fn remove_node(node: &MemoryNode) {
+        node.detach();
+        node.drop_listeners();
+    }
+
+    fn clear_children(element: &MemoryNode) {
+        let children = std::mem::take(&mut element.0.borrow_mut().children);
+        for child in children {
+            child.0.borrow_mut().parent = Weak::new();
+            child.drop_listeners();
+        }
+    }
+}
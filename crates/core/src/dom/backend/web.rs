@@ -0,0 +1,181 @@
+//! The browser DOM as a render backend
+use crate::dom::backend::{Listener, RenderBackend};
+use crate::dom::dom_node::{intern, symbol_nodes};
+use crate::dom::events::MountEvent;
+use crate::dom::{document, DomAttr, Event};
+use crate::vdom::{ListenerOptions, Style};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{Element, Node};
+
+pub(crate) type EventClosure = Closure<dyn FnMut(web_sys::Event)>;
+
+/// The browser DOM as a [`RenderBackend`], using `web_sys`.
+/// This is the backend of the [`DomNode`](crate::dom::DomNode)s of the [`Program`](crate::dom::Program).
+///
+/// Note: the children of a fragment are moved into the parent when the fragment is inserted,
+/// so the fragment itself is not in the document after that.
+#[derive(Debug, Clone, Copy)]
+pub struct WebBackend;
+
+fn as_element(node: &Node) -> &Element {
+    node.unchecked_ref()
+}
+
+impl RenderBackend for WebBackend {
+    type Node = Node;
+    type ListenerHandle = EventClosure;
+
+    fn create_element(tag: &str, namespace: Option<&str>) -> Node {
+        let element = match namespace {
+            Some(namespace) => document().create_element_ns(Some(intern(namespace)), intern(tag)),
+            None => document().create_element(intern(tag)),
+        };
+        element.expect("create element").into()
+    }
+
+    fn create_text(text: &str) -> Node {
+        document().create_text_node(text).into()
+    }
+
+    fn create_comment(comment: &str) -> Node {
+        document().create_comment(comment).into()
+    }
+
+    fn create_fragment() -> Node {
+        document().create_document_fragment().into()
+    }
+
+    fn tag(node: &Node) -> Option<String> {
+        (node.node_type() == Node::ELEMENT_NODE).then(|| as_element(node).tag_name().to_lowercase())
+    }
+
+    fn set_attribute(element: &Node, name: &str, namespace: Option<&str>, value: &str) {
+        DomAttr::set_element_attribute(as_element(element), intern(name), namespace, value);
+    }
+
+    fn remove_attribute(element: &Node, name: &str) {
+        DomAttr::remove_element_attribute(as_element(element), intern(name));
+    }
+
+    fn set_style_properties(element: &Node, styles: &[Style]) {
+        DomAttr::set_element_style_properties(as_element(element), styles);
+    }
+
+    fn remove_style_properties(element: &Node, names: &[String]) {
+        DomAttr::remove_element_style_properties(as_element(element), names);
+    }
+
+    fn add_classes(element: &Node, classes: &[String]) {
+        DomAttr::add_element_classes(as_element(element), classes);
+    }
+
+    fn remove_classes(element: &Node, classes: &[String]) {
+        DomAttr::remove_element_classes(as_element(element), classes);
+    }
+
+    fn set_text(node: &Node, text: &str) {
+        node.set_node_value(Some(text));
+    }
+
+    fn add_event_listener(
+        element: &Node,
+        event: &str,
+        listener: Listener,
+        options: ListenerOptions,
+    ) -> EventClosure {
+        let closure: EventClosure = Closure::new(move |web_event: web_sys::Event| {
+            if options.prevent_default {
+                web_event.prevent_default();
+            }
+            if options.stop_propagation {
+                web_event.stop_propagation();
+            }
+            listener(Event::from(web_event));
+        });
+        let listener_options = web_sys::AddEventListenerOptions::new();
+        listener_options.set_passive(options.passive);
+        listener_options.set_capture(options.capture);
+        listener_options.set_once(options.once);
+        element
+            .add_event_listener_with_callback_and_add_event_listener_options(
+                intern(event),
+                closure.as_ref().unchecked_ref(),
+                &listener_options,
+            )
+            .unwrap_or_else(|_| panic!("Error adding an event listener for {element:?}"));
+        closure
+    }
+
+    fn remove_event_listener(
+        element: &Node,
+        event: &str,
+        listener: EventClosure,
+        options: ListenerOptions,
+    ) {
+        // a capture listener is only removed with the capture flag
+        element
+            .remove_event_listener_with_callback_and_bool(
+                intern(event),
+                listener.as_ref().unchecked_ref(),
+                options.capture,
+            )
+            .unwrap_or_else(|_| panic!("Error removing an event listener for {element:?}"));
+    }
+
+    fn append_child(parent: &Node, child: &Node) {
+        parent.append_child(child).expect("append child");
+    }
+
+    fn append_html(parent: &Node, html: &str) {
+        if parent.node_type() == Node::ELEMENT_NODE {
+            as_element(parent)
+                .insert_adjacent_html(intern("beforeend"), html)
+                .expect("must not error");
+        } else {
+            parent
+                .append_child(&symbol_nodes(html))
+                .expect("append symbol");
+        }
+    }
+
+    fn insert_before(target: &Node, node: &Node) {
+        let parent = target.parent_node().expect("must have a parent");
+        parent
+            .insert_before(node, Some(target))
+            .expect("must insert before this node");
+    }
+
+    fn insert_after(target: &Node, node: &Node) {
+        let parent = target.parent_node().expect("must have a parent");
+        parent
+            .insert_before(node, target.next_sibling().as_ref())
+            .expect("must insert after this node");
+    }
+
+    fn replace_node(target: &Node, replacement: &Node) {
+        let parent = target.parent_node().expect("must have a parent");
+        parent
+            .replace_child(replacement, target)
+            .expect("must replace child");
+    }
+
+    fn remove_node(node: &Node) {
+        if let Some(parent) = node.parent_node() {
+            parent.remove_child(node).expect("remove child");
+        }
+    }
+
+    fn clear_children(element: &Node) {
+        // NOTE: It is faster to remove from the last
+        while let Some(last_child) = element.last_child() {
+            element
+                .remove_child(&last_child)
+                .expect("must remove child");
+        }
+    }
+
+    fn dispatch_mount_event(node: &Node) {
+        node.dispatch_event(&MountEvent::create_web_event())
+            .expect("must be ok");
+    }
+}
@@ -6,7 +6,6 @@ use crate::dom::Application;
 use crate::dom::DomAttr;
 use crate::dom::DomAttrValue;
 use crate::dom::DomNode;
-use crate::dom::GroupedDomAttrValues;
 use crate::dom::Program;
 use crate::dom::StatelessModel;
 use crate::vdom;
@@ -17,7 +16,6 @@ use std::any::TypeId;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
-use wasm_bindgen::JsCast;
 
 thread_local! {
     static TEMPLATE_LOOKUP: RefCell<HashMap<TypeId, DomNode>> = RefCell::new(HashMap::new());
@@ -157,7 +155,7 @@ fn create_fragment_node_no_listeners<MSG>(
     let fragment = document().create_document_fragment();
     let dom_node = DomNode {
         inner: DomInner::Fragment {
-            fragment,
+            fragment: fragment.into(),
             children: Rc::new(RefCell::new(vec![])),
         },
         parent: parent_node,
@@ -174,7 +172,7 @@ fn create_fragment_node_no_listeners<MSG>(
 fn create_leaf_node_no_listeners<MSG>(parent_node: Rc<Option<DomNode>>, leaf: &Leaf<MSG>) -> DomNode {
     match leaf {
         Leaf::Text(txt) => DomNode {
-            inner: DomInner::Text(document().create_text_node(txt)),
+            inner: DomInner::Text(document().create_text_node(txt).into()),
             parent: parent_node,
         },
         Leaf::Symbol(symbol) => DomNode {
@@ -182,7 +180,7 @@ fn create_leaf_node_no_listeners<MSG>(parent_node: Rc<Option<DomNode>>, leaf: &L
             parent: parent_node,
         },
         Leaf::Comment(comment) => DomNode {
-            inner: DomInner::Comment(document().create_comment(comment)),
+            inner: DomInner::Comment(document().create_comment(comment).into()),
             parent: parent_node,
         },
         Leaf::DocType(_doctype) => {
@@ -203,6 +201,7 @@ fn create_leaf_node_no_listeners<MSG>(parent_node: Rc<Option<DomNode>>, leaf: &L
             unreachable!("stateless component should not be here")
         }
         Leaf::TemplatedView(_) => todo!(),
+        Leaf::Lazy(lazy) => create_dom_node_no_listeners(parent_node, lazy.view()),
     }
 }
 
@@ -223,23 +222,15 @@ fn create_element_node_no_listeners<MSG>(
     // TODO: dispatch the mount event recursively after the dom node is mounted into
     // the root node
     let attrs = Attribute::merge_attributes_of_same_name(elm.attributes().iter());
-    let attrs = attrs
-        .iter()
-        .map(|a| convert_attr_except_listener(a))
-        .collect::<Vec<_>>();
-
-    for att in attrs {
-        set_element_dom_attr_except_listeners(&element, att);
-    }
-
     let dom_node = DomNode {
         inner: DomInner::Element {
-            element,
+            element: element.into(),
             listeners: Rc::new(RefCell::new(None)),
             children: Rc::new(RefCell::new(vec![])),
         },
         parent: parent_node,
     };
+    dom_node.set_dom_attrs(attrs.iter().map(|a| convert_attr_except_listener(a)));
     let dom_node_rc = Rc::new(Some(dom_node.clone()));
     let children = elm
         .children()
@@ -262,21 +253,6 @@ pub(crate) fn convert_attr_except_listener<MSG>(attr: &Attribute<MSG>) -> DomAtt
     }
 }
 
-/// Note: Used only templates
-/// set the lement with dom attr except for the event listeners
-pub(crate) fn set_element_dom_attr_except_listeners(element: &web_sys::Element, attr: DomAttr) {
-    let attr_name = intern(attr.name);
-    let attr_namespace = attr.namespace;
-
-    let GroupedDomAttrValues {
-        listeners: _,
-        plain_values,
-        styles,
-    } = attr.group_values();
-    DomAttr::set_element_style(element, attr_name, styles);
-    DomAttr::set_element_simple_values(element, attr_name, attr_namespace, plain_values);
-}
-
 fn convert_attr_value_except_listener<MSG>(
     attr_value: &AttributeValue<MSG>,
 ) -> Option<DomAttrValue> {
@@ -376,8 +352,7 @@ impl DomInner {
     fn deep_clone(&self) -> Self {
         match self {
             Self::Element { element, .. } => {
-                let node = element.clone_node_with_deep(true).expect("deep_clone");
-                let element: web_sys::Element = node.unchecked_into();
+                let element = element.clone_node_with_deep(true).expect("deep_clone");
                 let child_nodes = element.child_nodes();
                 let children_count = child_nodes.length();
                 let children = (0..children_count)
@@ -1,15 +1,14 @@
-use crate::vdom::AttributeName;
-use crate::vdom::Namespace;
+use crate::dom::Listener;
+use crate::vdom::ListenerOptions;
 use crate::vdom::Style;
 use crate::vdom::Value;
 #[cfg(feature = "ensure-attr-set")]
 use crate::vdom::{CHECKED, DISABLED, OPEN, VALUE};
+use std::fmt;
 use wasm_bindgen::intern;
-#[cfg(feature = "ensure-attr-set")]
 use wasm_bindgen::JsCast;
-use wasm_bindgen::{closure::Closure, JsValue};
 use web_sys;
-use web_sys::Element;
+use web_sys::{Element, HtmlElement, SvgElement};
 #[cfg(feature = "ensure-attr-set")]
 use web_sys::{
     HtmlButtonElement, HtmlDataElement, HtmlDetailsElement, HtmlFieldSetElement, HtmlInputElement,
@@ -30,14 +29,13 @@ pub struct DomAttr {
 }
 
 /// a dom version of the Attribute value, thereby removing the MSG generic
-#[derive(Debug)]
 pub enum DomAttrValue {
     /// simple value
     Simple(Value),
     /// a style
     Style(Vec<Style>),
-    /// event listeners
-    EventListener(Closure<dyn FnMut(web_sys::Event)>),
+    /// event listeners, with the options of how they are attached
+    EventListener(Listener, ListenerOptions),
     /// an empty value, can also represents null values from JsValue
     Empty,
 }
@@ -45,8 +43,8 @@ pub enum DomAttrValue {
 /// a struct where the listeners, plain values, styles and function call values are grouped
 /// separately
 pub struct GroupedDomAttrValues {
-    /// the listeners of the event listeners
-    pub listeners: Vec<Closure<dyn FnMut(web_sys::Event)>>,
+    /// the listeners of the event listeners, with the options of how they are attached
+    pub listeners: Vec<(Listener, ListenerOptions)>,
     /// plain attribute values
     pub plain_values: Vec<Value>,
     /// style attribute values
@@ -67,8 +65,8 @@ impl DomAttr {
                 DomAttrValue::Style(s) => {
                     styles.extend(s);
                 }
-                DomAttrValue::EventListener(cb) => {
-                    listeners.push(cb);
+                DomAttrValue::EventListener(cb, options) => {
+                    listeners.push((cb, options));
                 }
                 DomAttrValue::Empty => (),
             }
@@ -80,114 +78,132 @@ impl DomAttr {
         }
     }
 
-    /// set the style of this element
-    pub(crate) fn set_element_style(
+    /// set the attribute of the element, in the `namespace` if there is any
+    pub(crate) fn set_element_attribute(
         element: &Element,
-        attr_name: AttributeName,
-        styles: Vec<Style>,
+        attr_name: &str,
+        attr_namespace: Option<&str>,
+        value: &str,
     ) {
-        if let Some(merged_styles) = Style::merge_to_string(&styles) {
-            // set the styles
+        if let Some(namespace) = attr_namespace {
+            // Warning NOTE: set_attribute_ns should only be called
+            // when you meant to use a namespace
+            // using this with None will error in the browser with:
+            // NamespaceError: An attempt was made to create or change an object in a way which is incorrect with regard to namespaces
             element
-                .set_attribute(attr_name, &merged_styles)
+                .set_attribute_ns(Some(namespace), attr_name, value)
                 .unwrap_or_else(|_| panic!("Error setting an attribute_ns for {element:?}"));
         } else {
-            //if the merged attribute is blank of empty when string is trimmed
-            //remove the attribute
-            element
-                .remove_attribute(attr_name)
-                .expect("must remove attribute");
-        }
-    }
-
-    /// set simple values
-    pub(crate) fn set_element_simple_values(
-        element: &Element,
-        attr_name: AttributeName,
-        attr_namespace: Option<Namespace>,
-        plain_values: Vec<Value>,
-    ) {
-        if let Some(merged_plain_values) = Value::merge_to_string(plain_values.iter()) {
-            if let Some(namespace) = attr_namespace {
-                // Warning NOTE: set_attribute_ns should only be called
-                // when you meant to use a namespace
-                // using this with None will error in the browser with:
-                // NamespaceError: An attempt was made to create or change an object in a way which is incorrect with regard to namespaces
+            #[cfg(feature = "ensure-attr-set")]
+            if *VALUE == attr_name {
                 element
-                    .set_attribute_ns(Some(namespace), attr_name, &merged_plain_values)
-                    .unwrap_or_else(|_| panic!("Error setting an attribute_ns for {element:?}"));
-            } else {
-                #[cfg(feature = "ensure-attr-set")]
-                if *VALUE == attr_name {
-                    element
-                        .set_attribute(attr_name, &merged_plain_values)
-                        .unwrap_or_else(|_| panic!("Error setting an attribute for {element:?}"));
-                    Self::set_value_str(element, &merged_plain_values);
-                    Self::set_numeric_values(element, &plain_values);
-                } else if *OPEN == attr_name {
-                    let is_open: bool = plain_values
-                        .first()
-                        .and_then(|v| v.as_bool())
-                        .unwrap_or(false);
+                    .set_attribute(attr_name, value)
+                    .unwrap_or_else(|_| panic!("Error setting an attribute for {element:?}"));
+                Self::set_value_str(element, value);
+                Self::set_numeric_value(element, value);
+            } else if *OPEN == attr_name {
+                let is_open = value == "true";
 
-                    element
-                        .set_attribute(attr_name, &is_open.to_string())
-                        .unwrap_or_else(|_| panic!("Error setting an attribute for {element:?}"));
-                    Self::set_open(element, is_open);
-                } else if *CHECKED == attr_name {
-                    let is_checked: bool = plain_values
-                        .first()
-                        .and_then(|v| v.as_bool())
-                        .unwrap_or(false);
+                element
+                    .set_attribute(attr_name, &is_open.to_string())
+                    .unwrap_or_else(|_| panic!("Error setting an attribute for {element:?}"));
+                Self::set_open(element, is_open);
+            } else if *CHECKED == attr_name {
+                let is_checked = value == "true";
 
-                    element
-                        .set_attribute(attr_name, &is_checked.to_string())
-                        .unwrap_or_else(|_| panic!("Error setting an attribute for {element:?}"));
-                    Self::set_checked(element, is_checked)
-                } else if *DISABLED == attr_name {
-                    let is_disabled: bool = plain_values
-                        .first()
-                        .and_then(|v| v.as_bool())
-                        .unwrap_or(false);
+                element
+                    .set_attribute(attr_name, &is_checked.to_string())
+                    .unwrap_or_else(|_| panic!("Error setting an attribute for {element:?}"));
+                Self::set_checked(element, is_checked)
+            } else if *DISABLED == attr_name {
+                let is_disabled = value == "true";
 
-                    element
-                        .set_attribute(attr_name, &is_disabled.to_string())
-                        .unwrap_or_else(|_| panic!("Error setting an attribute for {element:?}"));
-                    Self::set_disabled(element, is_disabled);
-                } else if "inner_html" == attr_name {
-                    panic!("Setting inner_html is not allowed, as it breaks the tracking of the DomTree, use html-parse instead")
-                } else {
-                    element
-                        .set_attribute(attr_name, &merged_plain_values)
-                        .unwrap_or_else(|_| panic!("Error setting an attribute for {element:?}"));
-                }
-                #[cfg(not(feature = "ensure-attr-set"))]
                 element
-                    .set_attribute(attr_name, &merged_plain_values)
+                    .set_attribute(attr_name, &is_disabled.to_string())
+                    .unwrap_or_else(|_| panic!("Error setting an attribute for {element:?}"));
+                Self::set_disabled(element, is_disabled);
+            } else if "inner_html" == attr_name {
+                panic!("Setting inner_html is not allowed, as it breaks the tracking of the DomTree, use html-parse instead")
+            } else {
+                element
+                    .set_attribute(attr_name, value)
                     .unwrap_or_else(|_| panic!("Error setting an attribute for {element:?}"));
             }
+            #[cfg(not(feature = "ensure-attr-set"))]
+            element
+                .set_attribute(attr_name, value)
+                .unwrap_or_else(|_| panic!("Error setting an attribute for {element:?}"));
         }
     }
 
-    /// remove the elemnt dom attr
-    pub(crate) fn remove_element_dom_attr(
-        element: &Element,
-        attr: &DomAttr,
-    ) -> Result<(), JsValue> {
+    /// the inline style of the html or svg element
+    fn element_inline_style(element: &Element) -> web_sys::CssStyleDeclaration {
+        if let Some(html_element) = element.dyn_ref::<HtmlElement>() {
+            html_element.style()
+        } else if let Some(svg_element) = element.dyn_ref::<SvgElement>() {
+            svg_element.style()
+        } else {
+            unreachable!("only html and svg elements have inline style: {element:?}")
+        }
+    }
+
+    /// set each of the style properties of the element, leaving the rest of its style as is
+    pub(crate) fn set_element_style_properties(element: &Element, styles: &[Style]) {
+        let inline_style = Self::element_inline_style(element);
+        for style in styles {
+            inline_style
+                .set_property(intern(&style.name), &style.value.to_string())
+                .unwrap_or_else(|_| panic!("Error setting style {style} for {element:?}"));
+        }
+    }
+
+    /// remove each of the style properties with these names from the element
+    pub(crate) fn remove_element_style_properties(element: &Element, names: &[String]) {
+        let inline_style = Self::element_inline_style(element);
+        for name in names {
+            inline_style
+                .remove_property(intern(name))
+                .unwrap_or_else(|_| panic!("Error removing style {name} for {element:?}"));
+        }
+    }
+
+    /// add each of the class names into the class list of the element,
+    /// the classes added by other scripts are left as is
+    pub(crate) fn add_element_classes(element: &Element, classes: &[String]) {
+        let class_list = element.class_list();
+        for class in classes {
+            class_list
+                .add_1(intern(class))
+                .unwrap_or_else(|_| panic!("Error adding class {class} for {element:?}"));
+        }
+    }
+
+    /// remove each of the class names from the class list of the element
+    pub(crate) fn remove_element_classes(element: &Element, classes: &[String]) {
+        let class_list = element.class_list();
+        for class in classes {
+            class_list
+                .remove_1(intern(class))
+                .unwrap_or_else(|_| panic!("Error removing class {class} for {element:?}"));
+        }
+    }
+
+    /// remove the attribute of the element
+    pub(crate) fn remove_element_attribute(element: &Element, attr_name: &str) {
         #[cfg(feature = "ensure-attr-set")]
-        if *VALUE == attr.name {
+        if *VALUE == attr_name {
             DomAttr::set_value_str(element, "");
-        } else if *OPEN == attr.name {
+        } else if *OPEN == attr_name {
             DomAttr::set_open(element, false);
-        } else if *CHECKED == attr.name {
+        } else if *CHECKED == attr_name {
             DomAttr::set_checked(element, false);
-        } else if *DISABLED == attr.name {
+        } else if *DISABLED == attr_name {
             DomAttr::set_disabled(element, false);
         }
         //actually remove the element
-        element.remove_attribute(intern(attr.name))?;
-
-        Ok(())
+        element
+            .remove_attribute(attr_name)
+            .unwrap_or_else(|_| panic!("Error removing an attribute for {element:?}"));
     }
 
     /// explicitly call `set_checked` function on the html element
@@ -299,12 +315,12 @@ impl DomAttr {
         }
     }
 
-    /// set the element attribute value with the first numerical value found in values
+    /// set the element attribute value with the value if it is numerical
     #[cfg(feature = "ensure-attr-set")]
-    pub(crate) fn set_numeric_values(element: &Element, values: &[Value]) {
-        let value_i32 = values.first().and_then(|v| v.as_i32());
+    pub(crate) fn set_numeric_value(element: &Element, value: &str) {
+        let value_i32 = value.parse::<i32>().ok();
 
-        let value_f64 = values.first().and_then(|v| v.as_f64());
+        let value_f64 = value.parse::<f64>().ok();
 
         if let Some(value_i32) = value_i32 {
             Self::set_value_i32(element, value_i32);
@@ -315,6 +331,19 @@ impl DomAttr {
     }
 }
 
+impl fmt::Debug for DomAttrValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Simple(v) => f.debug_tuple("Simple").field(v).finish(),
+            Self::Style(v) => f.debug_tuple("Style").field(v).finish(),
+            Self::EventListener(_, options) => {
+                f.debug_tuple("EventListener").field(options).finish()
+            }
+            Self::Empty => write!(f, "Empty"),
+        }
+    }
+}
+
 impl DomAttrValue {
     /// return the value if it is a Simple variant
     pub fn get_simple(&self) -> Option<&Value> {
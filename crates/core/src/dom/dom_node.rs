@@ -1,16 +1,17 @@
 use crate::dom::component::StatelessModel;
 use crate::dom::DomAttr;
+use crate::dom::DomAttrValue;
 use crate::dom::GroupedDomAttrValues;
 use crate::dom::StatefulComponent;
 use crate::dom::StatefulModel;
+use crate::dom::{Listener, RenderBackend, WebBackend};
 use crate::html::lookup;
 use crate::vdom::TreePath;
 use crate::{
     dom::document,
-    dom::events::MountEvent,
     dom::{Application, Program},
     vdom,
-    vdom::{Attribute, Leaf},
+    vdom::{Attribute, AttributeValue, EventCallback, Leaf, ListenerOptions, Style, Value},
 };
 use indexmap::IndexMap;
 use std::borrow::Cow;
@@ -18,75 +19,115 @@ use std::cell::Ref;
 use std::cell::RefCell;
 use std::fmt;
 use std::rc::Rc;
-use wasm_bindgen::{closure::Closure, JsCast, JsValue};
-use web_sys::{self, Element, Node};
+use wasm_bindgen::JsCast;
+use web_sys::{self, Node};
 
-pub(crate) type EventClosure = Closure<dyn FnMut(web_sys::Event)>;
-pub type NamedEventClosures = IndexMap<&'static str, EventClosure>;
+/// the attached event listeners of an element by event name, with the options they are attached with
+pub type NamedListeners<B> =
+    IndexMap<&'static str, Vec<(<B as RenderBackend>::ListenerHandle, ListenerOptions)>>;
 
 /// A counter part of the vdom Node
 /// This is needed, so that we can
 /// 1. Keep track of event closure and drop them when nodes has been removed
 /// 2. Custom removal of children nodes on a stateful component
 ///
-#[derive(Clone, Debug)]
-pub struct DomNode {
-    pub(crate) inner: DomInner,
+/// The nodes are in the document of the backend `B`, which is the browser DOM by default.
+pub struct DomNode<B: RenderBackend = WebBackend> {
+    pub(crate) inner: DomInner<B>,
     //TODO: parent needs to be a weak reference
-    pub(crate) parent: Rc<Option<DomNode>>,
+    pub(crate) parent: Rc<Option<DomNode<B>>>,
 }
-#[derive(Clone)]
-pub enum DomInner {
+
+pub enum DomInner<B: RenderBackend = WebBackend> {
     /// a reference to an element node
     Element {
         /// the reference to the actual element
-        element: web_sys::Element,
+        element: B::Node,
         /// the listeners of this element, which we will drop when this element is removed
-        listeners: Rc<RefCell<Option<NamedEventClosures>>>,
+        listeners: Rc<RefCell<Option<NamedListeners<B>>>>,
         /// keeps track of the children nodes
         /// this needs to be synced with the actual element children
-        children: Rc<RefCell<Vec<DomNode>>>,
+        children: Rc<RefCell<Vec<DomNode<B>>>>,
     },
     /// text node
-    Text(web_sys::Text),
+    Text(B::Node),
     Symbol(Cow<'static, str>),
     /// comment node
-    Comment(web_sys::Comment),
+    Comment(B::Node),
     /// Fragment node
     Fragment {
         ///
-        fragment: web_sys::DocumentFragment,
+        fragment: B::Node,
         ///
-        children: Rc<RefCell<Vec<DomNode>>>,
+        children: Rc<RefCell<Vec<DomNode<B>>>>,
     },
     /// StatefulComponent
     #[allow(unused)]
     StatefulComponent(Rc<RefCell<dyn StatefulComponent>>),
 }
 
-impl fmt::Debug for DomInner {
+impl<B: RenderBackend> Clone for DomNode<B> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            parent: Rc::clone(&self.parent),
+        }
+    }
+}
+
+impl<B: RenderBackend> Clone for DomInner<B> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Element {
+                element,
+                listeners,
+                children,
+            } => Self::Element {
+                element: element.clone(),
+                listeners: Rc::clone(listeners),
+                children: Rc::clone(children),
+            },
+            Self::Text(text_node) => Self::Text(text_node.clone()),
+            Self::Symbol(symbol) => Self::Symbol(symbol.clone()),
+            Self::Comment(comment) => Self::Comment(comment.clone()),
+            Self::Fragment { fragment, children } => Self::Fragment {
+                fragment: fragment.clone(),
+                children: Rc::clone(children),
+            },
+            Self::StatefulComponent(comp) => Self::StatefulComponent(Rc::clone(comp)),
+        }
+    }
+}
+
+impl<B: RenderBackend> fmt::Debug for DomNode<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DomNode")
+            .field("inner", &self.inner)
+            .field("parent", &self.parent)
+            .finish()
+    }
+}
+
+impl<B: RenderBackend> fmt::Debug for DomInner<B> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::Element {
                 element, children, ..
             } => {
                 f.debug_struct("Element")
-                    .field("tag", &element.tag_name().to_lowercase())
+                    .field("tag", &B::tag(element))
                     .field(
                         "children",
                         &children
                             .borrow()
                             .iter()
-                            .map(|c| c.as_element().tag_name().to_lowercase())
+                            .map(|c| c.tag())
                             .collect::<Vec<_>>(),
                     )
                     .finish()?;
                 Ok(())
             }
-            Self::Text(text_node) => f
-                .debug_tuple("Text")
-                .field(&text_node.whole_text().expect("whole text"))
-                .finish(),
+            Self::Text(text_node) => f.debug_tuple("Text").field(text_node).finish(),
             Self::Symbol(symbol) => f.debug_tuple("Symbol").field(&symbol).finish(),
             Self::Comment(_) => write!(f, "Comment"),
             Self::Fragment { .. } => write!(f, "Fragment"),
@@ -99,8 +140,7 @@ impl From<web_sys::Node> for DomNode {
     fn from(node: web_sys::Node) -> Self {
         match node.node_type() {
             Node::ELEMENT_NODE => {
-                let element: web_sys::Element = node.unchecked_into();
-                let child_nodes = element.child_nodes();
+                let child_nodes = node.child_nodes();
                 let children_count = child_nodes.length();
                 let children = (0..children_count)
                     .map(|i| {
@@ -110,33 +150,27 @@ impl From<web_sys::Node> for DomNode {
                     .collect();
                 DomNode {
                     inner: DomInner::Element {
-                        element,
+                        element: node,
                         listeners: Rc::new(RefCell::new(None)),
                         children: Rc::new(RefCell::new(children)),
                     },
                     parent: Rc::new(None),
                 }
             }
-            Node::TEXT_NODE => {
-                let text_node: web_sys::Text = node.unchecked_into();
-                DomNode {
-                    inner: DomInner::Text(text_node),
-                    parent: Rc::new(None),
-                }
-            }
-            Node::COMMENT_NODE => {
-                let comment: web_sys::Comment = node.unchecked_into();
-                DomNode {
-                    inner: DomInner::Comment(comment),
-                    parent: Rc::new(None),
-                }
-            }
+            Node::TEXT_NODE => DomNode {
+                inner: DomInner::Text(node),
+                parent: Rc::new(None),
+            },
+            Node::COMMENT_NODE => DomNode {
+                inner: DomInner::Comment(node),
+                parent: Rc::new(None),
+            },
             _node_type => todo!("for: {_node_type:?}"),
         }
     }
 }
 
-impl PartialEq for DomNode {
+impl<B: RenderBackend> PartialEq for DomNode<B> {
     fn eq(&self, other: &Self) -> bool {
         match (&self.inner, &other.inner) {
             (DomInner::Element { element: v, .. }, DomInner::Element { element: o, .. }) => v == o,
@@ -152,11 +186,16 @@ impl PartialEq for DomNode {
     }
 }
 
-impl DomNode {
-    fn children(&self) -> Option<Ref<'_, Vec<DomNode>>> {
+impl<B: RenderBackend> DomNode<B> {
+    fn children(&self) -> Option<Ref<'_, Vec<DomNode<B>>>> {
+        self.child_list().map(|children| children.borrow())
+    }
+
+    /// the tracked children of an element or fragment
+    fn child_list(&self) -> Option<&Rc<RefCell<Vec<DomNode<B>>>>> {
         match &self.inner {
-            DomInner::Element { children, .. } => Some(children.borrow()),
-            DomInner::Fragment { children, .. } => Some(children.borrow()),
+            DomInner::Element { children, .. } => Some(children),
+            DomInner::Fragment { children, .. } => Some(children),
             _ => None,
         }
     }
@@ -167,35 +206,19 @@ impl DomNode {
 
     pub(crate) fn tag(&self) -> Option<String> {
         match &self.inner {
-            DomInner::Element { element, .. } => Some(element.tag_name().to_lowercase()),
+            DomInner::Element { element, .. } => B::tag(element),
             _ => None,
         }
     }
 
-    /// exposed the underlying wrapped node as `web_sys::Node`
-    pub fn as_node(&self) -> web_sys::Node {
-        match &self.inner {
-            DomInner::Element { element, .. } => element.clone().unchecked_into(),
-            DomInner::Fragment { fragment, .. } => fragment.clone().unchecked_into(),
-            DomInner::Text(text_node) => text_node.clone().unchecked_into(),
-            DomInner::Symbol(_) => panic!("don't know how to deal with symbol"),
-            DomInner::Comment(comment_node) => comment_node.clone().unchecked_into(),
-            DomInner::StatefulComponent(_) => todo!("for stateful component.."),
-        }
-    }
-
-    /// exposed the underlying wrapped node as `web_sys::Element`
-    pub fn as_element(&self) -> web_sys::Element {
+    /// exposed the underlying wrapped node of the backend
+    pub fn as_node(&self) -> B::Node {
         match &self.inner {
             DomInner::Element { element, .. } => element.clone(),
-            DomInner::Fragment { fragment, .. } => {
-                let fragment: web_sys::Element = fragment.clone().unchecked_into();
-                assert!(fragment.is_object());
-                fragment
-            }
-            DomInner::Text(text_node) => text_node.clone().unchecked_into(),
+            DomInner::Fragment { fragment, .. } => fragment.clone(),
+            DomInner::Text(text_node) => text_node.clone(),
             DomInner::Symbol(_) => panic!("don't know how to deal with symbol"),
-            DomInner::Comment(comment_node) => comment_node.clone().unchecked_into(),
+            DomInner::Comment(comment_node) => comment_node.clone(),
             DomInner::StatefulComponent(_) => todo!("for stateful component.."),
         }
     }
@@ -208,133 +231,88 @@ impl DomNode {
         }
     }
 
-    /// return the outer html string of an element
-    pub fn outer_html(&self) -> String {
-        let DomInner::Element { element, .. } = &self.inner else {
-            unreachable!("should only be called to element");
-        };
-        element.outer_html()
-    }
-
     /// append the DomNode `child` into this DomNode `self`
-    pub fn append_children(&self, for_append: Vec<DomNode>) {
-        match &self.inner {
-            DomInner::Element {
-                element, children, ..
-            } => {
-                for mut child in for_append.into_iter() {
-                    if let Some(symbol) = child.as_symbol() {
-                        element
-                            .insert_adjacent_html(intern("beforeend"), &symbol)
-                            .expect("must not error");
-                    } else {
-                        element
-                            .append_child(&child.as_node())
-                            .expect("append child");
-                        child.dispatch_mount_event();
-                    }
-                    child.parent = Rc::new(Some(self.clone()));
-                    children.borrow_mut().push(child);
-                }
-            }
-            DomInner::Fragment {
-                fragment, children, ..
-            } => {
-                for mut child in for_append.into_iter() {
-                    fragment
-                        .append_child(&child.as_node())
-                        .expect("append child");
-                    child.dispatch_mount_event();
-                    child.parent = Rc::new(Some(self.clone()));
-                    children.borrow_mut().push(child);
-                }
-            }
-            _ => unreachable!(
+    pub fn append_children(&self, for_append: Vec<DomNode<B>>) {
+        let (DomInner::Element {
+            element: node,
+            children,
+            ..
+        }
+        | DomInner::Fragment {
+            fragment: node,
+            children,
+        }) = &self.inner
+        else {
+            unreachable!(
                 "appending should only be called to Element and Fragment, found: {:#?}",
                 self
-            ),
+            )
+        };
+        for mut child in for_append.into_iter() {
+            if let Some(symbol) = child.as_symbol() {
+                B::append_html(node, &symbol);
+            } else {
+                B::append_child(node, &child.as_node());
+                child.dispatch_mount_event();
+            }
+            child.parent = Rc::new(Some(self.clone()));
+            children.borrow_mut().push(child);
         }
     }
 
-    /// Insert the DomNode `for_insert` before `self` DomNode
-    pub(crate) fn insert_before(&self, mut for_insert: Vec<DomNode>) {
+    /// the tracked children of the parent of this node, along with the index of this node in them
+    fn parent_children(&self) -> (&Rc<RefCell<Vec<DomNode<B>>>>, usize) {
         let parent_target = self.parent.as_ref().as_ref().expect("must have a parent");
-        let DomInner::Element {
-            children: parent_children,
-            ..
-        } = &parent_target.inner
-        else {
-            unreachable!("parent must be an element");
-        };
+        let parent_children = parent_target
+            .child_list()
+            .expect("parent must be an element or a fragment");
+        let self_index = parent_children
+            .borrow()
+            .iter()
+            .position(|child| self == child)
+            .expect("should have a self index");
+        (parent_children, self_index)
+    }
 
-        let mut self_index = None;
-        for (i, child) in parent_children.borrow().iter().enumerate() {
-            if self == child {
-                self_index = Some(i);
-                break;
-            }
-        }
+    /// Insert the DomNode `for_insert` before `self` DomNode
+    pub(crate) fn insert_before(&self, mut for_insert: Vec<DomNode<B>>) {
+        let (parent_children, self_index) = self.parent_children();
         for insert_node in for_insert.iter_mut() {
             insert_node.parent = Rc::clone(&self.parent);
         }
         // NOTE: This is not reverse since inserting the last insert_node will always be next
         // before the target element
+        let target = self.as_node();
         for insert_node in for_insert.iter() {
-            self.as_element()
-                .insert_adjacent_element(intern("beforebegin"), &insert_node.as_element())
-                .expect("must insert before this element");
+            B::insert_before(&target, &insert_node.as_node());
             insert_node.dispatch_mount_event();
         }
 
         // NOTE: It is important that we reverse the insertion to the wrapper DomNode since it is
         // just a Vec where inserting from the last will preserve the index to insert into
         for insert_node in for_insert.into_iter().rev() {
-            if let Some(self_index) = self_index {
-                parent_children.borrow_mut().insert(self_index, insert_node);
-            } else {
-                unreachable!("should have a self index");
-            }
+            parent_children.borrow_mut().insert(self_index, insert_node);
         }
     }
 
     /// Insert the DomNode `for_insert` after `self` DomNode
-    pub(crate) fn insert_after(&self, mut for_insert: Vec<DomNode>) {
-        let parent_target = self.parent.as_ref().as_ref().expect("must have a parent");
-        let DomInner::Element {
-            children: parent_children,
-            ..
-        } = &parent_target.inner
-        else {
-            unreachable!("parent must be an element");
-        };
-        let mut self_index = None;
-        for (i, child) in parent_children.borrow().iter().enumerate() {
-            if self == child {
-                self_index = Some(i);
-                break;
-            }
-        }
+    pub(crate) fn insert_after(&self, mut for_insert: Vec<DomNode<B>>) {
+        let (parent_children, self_index) = self.parent_children();
         for insert_node in for_insert.iter_mut() {
             insert_node.parent = Rc::clone(&self.parent);
         }
+        let target = self.as_node();
         for insert_node in for_insert.into_iter().rev() {
-            self.as_element()
-                .insert_adjacent_element(intern("afterend"), &insert_node.as_element())
-                .expect("must insert after this element");
+            B::insert_after(&target, &insert_node.as_node());
             insert_node.dispatch_mount_event();
-
-            if let Some(self_index) = self_index {
-                parent_children
-                    .borrow_mut()
-                    .insert(self_index + 1, insert_node);
-            } else {
-                unreachable!("should have a self index");
-            }
+            parent_children
+                .borrow_mut()
+                .insert(self_index + 1, insert_node);
         }
     }
 
     /// Replace the child `child` DomNode with a replacement DomNode `replacement`
-    pub(crate) fn replace_child(&self, target_child: &DomNode, mut replacement: DomNode) {
+    pub(crate) fn replace_child(&self, target_child: &DomNode<B>, mut replacement: DomNode<B>) {
         match &self.inner {
             DomInner::Element { children, .. } => {
                 let mut child_index = None;
@@ -347,10 +325,7 @@ impl DomNode {
                 replacement.parent = Rc::new(Some(self.clone()));
                 if let Some(child_index) = child_index {
                     children.borrow_mut().remove(child_index);
-                    target_child
-                        .as_element()
-                        .replace_with_with_node_1(&replacement.as_node())
-                        .expect("must replace child");
+                    B::replace_node(&target_child.as_node(), &replacement.as_node());
                     replacement.dispatch_mount_event();
                     children.borrow_mut().insert(child_index, replacement);
                 } else {
@@ -362,35 +337,41 @@ impl DomNode {
         }
     }
 
-    /// Remove the DomNode `child` from the children of `self`
-    pub(crate) fn remove_children(&self, for_remove: &[&DomNode]) {
-        match &self.inner {
-            DomInner::Element {
-                element, children, ..
-            } => {
-                let mut child_indexes = vec![];
-                for (i, ch) in children.borrow().iter().enumerate() {
-                    for remove_node in for_remove.iter() {
-                        if ch == *remove_node {
-                            child_indexes.push(i);
-                            break;
-                        }
-                    }
-                }
-                assert_eq!(child_indexes.len(), for_remove.len(), "must find all");
-
-                // NOTE: It is important to remove from the last, since
-                // vec shifts to the left, while removing from the last
-                // with the rev child index, we remove the correct child_index
-                for child_index in child_indexes.into_iter().rev() {
-                    let child = children.borrow_mut().remove(child_index);
-                    element
-                        .remove_child(&child.as_node())
-                        .expect("remove child");
+    /// Remove the DomNode `child` from the children of `self`,
+    /// the listeners of the removed nodes are dropped
+    pub(crate) fn remove_children(&self, for_remove: &[&DomNode<B>]) {
+        for child in self.take_children(for_remove) {
+            B::remove_node(&child.as_node());
+        }
+    }
+
+    /// take the DomNodes `for_take` out of the tracked children of `self`,
+    /// the nodes are left in the document, such as when they are moved
+    pub(crate) fn take_children(&self, for_take: &[&DomNode<B>]) -> Vec<DomNode<B>> {
+        let children = self
+            .child_list()
+            .expect("expecting an element or a fragment");
+        let mut child_indexes = vec![];
+        for (i, ch) in children.borrow().iter().enumerate() {
+            for take_node in for_take.iter() {
+                if ch == *take_node {
+                    child_indexes.push(i);
+                    break;
                 }
             }
-            _ => todo!(),
         }
+        assert_eq!(child_indexes.len(), for_take.len(), "must find all");
+
+        // NOTE: It is important to remove from the last, since
+        // vec shifts to the left, while removing from the last
+        // with the rev child index, we remove the correct child_index
+        let mut taken: Vec<DomNode<B>> = child_indexes
+            .into_iter()
+            .rev()
+            .map(|child_index| children.borrow_mut().remove(child_index))
+            .collect();
+        taken.reverse();
+        taken
     }
 
     /// remove all the children of this element
@@ -400,14 +381,9 @@ impl DomNode {
                 element, children, ..
             } => {
                 children.borrow_mut().clear();
-                // NOTE: It is faster to remove from the last
                 // This is removing the children of the actual node
                 // regardless if it is mapped with the DomNode wrapper
-                while let Some(last_child) = element.last_child() {
-                    element
-                        .remove_child(&last_child)
-                        .expect("must remove child");
-                }
+                B::clear_children(element);
             }
             _ => todo!(),
         }
@@ -421,105 +397,162 @@ impl DomNode {
         }
     }
 
-    pub(crate) fn replace_node(&self, replacement: DomNode) {
+    pub(crate) fn replace_node(&self, replacement: DomNode<B>) {
         if let Some(parent) = self.parent.as_ref() {
             parent.replace_child(self, replacement);
         } else {
             //NOTE: This must be replacing a mount node
-            self
-                .as_element()
-                .replace_with_with_node_1(&replacement.as_node())
-                .expect("must replace child");
+            B::replace_node(&self.as_node(), &replacement.as_node());
         }
     }
 
-    pub(crate) fn set_dom_attrs(
-        &self,
-        attrs: impl IntoIterator<Item = DomAttr>,
-    ) -> Result<(), JsValue> {
+    pub(crate) fn set_dom_attrs(&self, attrs: impl IntoIterator<Item = DomAttr>) {
         for attr in attrs.into_iter() {
-            self.set_dom_attr(attr)?;
+            self.set_dom_attr(attr);
         }
-        Ok(())
     }
 
-    fn set_dom_attr(&self, attr: DomAttr) -> Result<(), JsValue> {
-        match &self.inner {
-            DomInner::Element {
-                element, listeners, ..
-            } => {
-                let attr_name = intern(attr.name);
-                let attr_namespace = attr.namespace;
-
-                let GroupedDomAttrValues {
-                    listeners: event_callbacks,
-                    plain_values,
-                    styles,
-                } = attr.group_values();
-
-                Self::add_event_dom_listeners(&element, attr_name, &event_callbacks)
-                    .expect("event listeners");
-                let is_none = listeners.borrow().is_none();
-                if is_none {
-                    let listener_closures: IndexMap<
-                        &'static str,
-                        Closure<dyn FnMut(web_sys::Event)>,
-                    > = IndexMap::from_iter(event_callbacks.into_iter().map(|c| (attr_name, c)));
-                    *listeners.borrow_mut() = Some(listener_closures);
-                } else if let Some(listeners) = listeners.borrow_mut().as_mut() {
-                    for event_cb in event_callbacks.into_iter() {
-                        listeners.insert(attr_name, event_cb);
-                    }
-                }
+    fn set_dom_attr(&self, attr: DomAttr) {
+        let DomInner::Element { element, .. } = &self.inner else {
+            unreachable!("should only be called for element");
+        };
+        let attr_name = attr.name;
+        let attr_namespace = attr.namespace;
 
-                DomAttr::set_element_style(&element, attr_name, styles);
-                DomAttr::set_element_simple_values(
-                    &element,
-                    attr_name,
-                    attr_namespace,
-                    plain_values,
-                );
-            }
-            _ => unreachable!("should only be called for element"),
+        let GroupedDomAttrValues {
+            listeners,
+            plain_values,
+            styles,
+        } = attr.group_values();
+        let has_listeners = !listeners.is_empty();
+
+        if has_listeners {
+            self.set_event_listeners(attr_name, listeners);
+        }
+
+        if let Some(merged_plain_values) = Value::merge_to_string(plain_values.iter()) {
+            B::set_attribute(element, attr_name, attr_namespace, &merged_plain_values);
+        } else if let Some(merged_styles) = Style::merge_to_string(&styles) {
+            B::set_attribute(element, attr_name, None, &merged_styles);
+        } else if !has_listeners {
+            //if the merged attribute is blank of empty when string is trimmed
+            //remove the attribute
+            B::remove_attribute(element, attr_name);
+        }
+    }
+
+    /// attach the `listeners` of `event_name` to this element, in place of its previous listeners
+    pub(crate) fn set_event_listeners(
+        &self,
+        event_name: &'static str,
+        event_listeners: Vec<(Listener, ListenerOptions)>,
+    ) {
+        let DomInner::Element {
+            element, listeners, ..
+        } = &self.inner
+        else {
+            unreachable!("expecting an element");
+        };
+        // the previous listeners are detached first, otherwise they are still called
+        // after they are replaced
+        self.remove_event_listener(event_name);
+        let attached = event_listeners
+            .into_iter()
+            .map(|(listener, options)| {
+                (
+                    B::add_event_listener(element, event_name, listener, options),
+                    options,
+                )
+            })
+            .collect();
+        listeners
+            .borrow_mut()
+            .get_or_insert_with(IndexMap::new)
+            .insert(event_name, attached);
+    }
+
+    /// detach all the event listeners of `event_name` from this element and drop them
+    pub(crate) fn remove_event_listener(&self, event_name: &str) {
+        let DomInner::Element {
+            element, listeners, ..
+        } = &self.inner
+        else {
+            unreachable!("expecting an element");
+        };
+        let removed = listeners
+            .borrow_mut()
+            .as_mut()
+            .and_then(|listeners| listeners.shift_remove(event_name));
+        for (listener, options) in removed.into_iter().flatten() {
+            B::remove_event_listener(element, event_name, listener, options);
         }
-        Ok(())
     }
 
-    pub(crate) fn remove_dom_attr(&self, attr: &DomAttr) -> Result<(), JsValue> {
+    pub(crate) fn remove_dom_attr(&self, attr: &DomAttr) {
         let DomInner::Element { element, .. } = &self.inner else {
             unreachable!("expecting an element");
         };
-        DomAttr::remove_element_dom_attr(&element, attr)
+        B::remove_attribute(element, attr.name);
     }
 
-    /// attach and event listener to an event target
-    pub(crate) fn add_event_dom_listeners(
-        target: &web_sys::EventTarget,
-        attr_name: &'static str,
-        event_listeners: &[EventClosure],
-    ) -> Result<(), JsValue> {
-        for event_cb in event_listeners.into_iter() {
-            Self::add_event_listener(target, attr_name, &event_cb)?;
+    /// set only these style properties, the other style properties of this element are kept
+    pub(crate) fn set_style_properties(&self, styles: &[Style]) {
+        let DomInner::Element { element, .. } = &self.inner else {
+            unreachable!("expecting an element");
+        };
+        B::set_style_properties(element, styles);
+    }
+
+    /// remove the style properties with these names
+    pub(crate) fn remove_style_properties(&self, names: &[String]) {
+        let DomInner::Element { element, .. } = &self.inner else {
+            unreachable!("expecting an element");
+        };
+        B::remove_style_properties(element, names);
+    }
+
+    /// set the content of this text or comment node, keeping the node in the DOM
+    pub(crate) fn set_text(&self, text: &str) {
+        match &self.inner {
+            DomInner::Text(text_node) => B::set_text(text_node, text),
+            DomInner::Comment(comment) => B::set_text(comment, text),
+            _ => unreachable!("expecting a text or comment node"),
         }
-        Ok(())
     }
 
-    /// add a event listener to a target element
-    pub(crate) fn add_event_listener(
-        event_target: &web_sys::EventTarget,
-        event_name: &str,
-        listener: &EventClosure,
-    ) -> Result<(), JsValue> {
-        event_target.add_event_listener_with_callback(
-            intern(event_name),
-            listener.as_ref().unchecked_ref(),
-        )?;
-        Ok(())
+    /// add these class names, the other class names of this element are kept
+    pub(crate) fn add_classes(&self, classes: &[String]) {
+        let DomInner::Element { element, .. } = &self.inner else {
+            unreachable!("expecting an element");
+        };
+        B::add_classes(element, classes);
+    }
+
+    /// remove these class names from this element
+    pub(crate) fn remove_classes(&self, classes: &[String]) {
+        let DomInner::Element { element, .. } = &self.inner else {
+            unreachable!("expecting an element");
+        };
+        B::remove_classes(element, classes);
+    }
+
+    pub(crate) fn dispatch_mount_event(&self) {
+        B::dispatch_mount_event(&self.as_node());
+    }
+}
+
+impl DomNode {
+    /// exposed the underlying wrapped node as `web_sys::Element`
+    pub fn as_element(&self) -> web_sys::Element {
+        self.as_node().unchecked_into()
     }
 
-    fn dispatch_mount_event(&self){
-        let event_target:web_sys::EventTarget = self.as_element().unchecked_into();
-        event_target.dispatch_event(&MountEvent::create_web_event()).expect("must be ok");
+    /// return the outer html string of an element
+    pub fn outer_html(&self) -> String {
+        let DomInner::Element { element, .. } = &self.inner else {
+            unreachable!("should only be called to element");
+        };
+        element.unchecked_ref::<web_sys::Element>().outer_html()
     }
 
     /// render this DomNode into an html string represenation
@@ -532,16 +565,24 @@ impl DomNode {
     fn render(&self, buffer: &mut dyn fmt::Write) -> fmt::Result {
         match &self.inner {
             DomInner::Text(text_node) => {
-                let text = text_node.whole_text().expect("whole text");
+                let text = text_node
+                    .unchecked_ref::<web_sys::Text>()
+                    .whole_text()
+                    .expect("whole text");
                 write!(buffer, "{text}")?;
                 Ok(())
             }
             DomInner::Comment(comment) => {
-                write!(buffer, "<!--{}-->", comment.data())
+                write!(
+                    buffer,
+                    "<!--{}-->",
+                    comment.unchecked_ref::<web_sys::Comment>().data()
+                )
             }
             DomInner::Element {
                 element, children, ..
             } => {
+                let element: &web_sys::Element = element.unchecked_ref();
                 let tag = element.tag_name().to_lowercase();
                 let is_self_closing = lookup::is_self_closing(&tag);
 
@@ -589,16 +630,39 @@ pub fn intern(s: &str) -> &str {
     s
 }
 
-impl<APP> Program<APP>
-where
-    APP: Application + 'static,
-{
+/// Creates the DomNodes of the vdom nodes in the document of the backend `B`,
+/// the event listeners of the created nodes are made with `create_listener`
+pub(crate) trait CreateDomNode<B: RenderBackend> {
+    /// the msg of the vdom nodes
+    type MSG;
+
+    /// make the listener which is attached to the created element
+    fn create_listener(&self, event_listener: &EventCallback<Self::MSG>) -> Listener;
+
+    /// create the node of a stateful component
+    fn create_stateful_component(
+        &self,
+        parent_node: Rc<Option<DomNode<B>>>,
+        comp: &StatefulModel<Self::MSG>,
+    ) -> DomNode<B>;
+
+    /// create the node of a stateless component, which is its view
+    fn create_stateless_component(
+        &self,
+        parent_node: Rc<Option<DomNode<B>>>,
+        comp: &StatelessModel<Self::MSG>,
+    ) -> DomNode<B> {
+        let comp_view = &comp.view;
+        let real_comp_view = comp_view.unwrap_template_ref();
+        self.create_dom_node(parent_node, real_comp_view)
+    }
+
     /// Create a dom node
-    pub fn create_dom_node(
+    fn create_dom_node(
         &self,
-        parent_node: Rc<Option<DomNode>>,
-        node: &vdom::Node<APP::MSG>,
-    ) -> DomNode {
+        parent_node: Rc<Option<DomNode<B>>>,
+        node: &vdom::Node<Self::MSG>,
+    ) -> DomNode<B> {
         match node {
             vdom::Node::Element(elm) => self.create_element_node(parent_node, elm),
             vdom::Node::Leaf(leaf) => self.create_leaf_node(parent_node, leaf),
@@ -607,40 +671,25 @@ where
 
     fn create_element_node(
         &self,
-        parent_node: Rc<Option<DomNode>>,
-        elm: &vdom::Element<APP::MSG>,
-    ) -> DomNode {
-        let document = document();
-        let element = if let Some(namespace) = elm.namespace() {
-            document
-                .create_element_ns(Some(intern(namespace)), intern(elm.tag()))
-                .expect("Unable to create element")
-        } else {
-            document
-                .create_element(intern(elm.tag()))
-                .expect("create element")
-        };
-        // TODO: dispatch the mount event recursively after the dom node is mounted into
-        // the root node
-        let attrs = Attribute::merge_attributes_of_same_name(elm.attributes().iter());
-
-        let listeners = self.set_element_dom_attrs(
-            &element,
-            attrs
-                .iter()
-                .map(|a| self.convert_attr(a))
-                .collect::<Vec<_>>(),
-        );
+        parent_node: Rc<Option<DomNode<B>>>,
+        elm: &vdom::Element<Self::MSG>,
+    ) -> DomNode<B> {
+        let element = B::create_element(elm.tag(), elm.namespace().copied());
         let dom_node = DomNode {
             inner: DomInner::Element {
                 element,
-                listeners: Rc::new(RefCell::new(listeners)),
+                listeners: Rc::new(RefCell::new(None)),
                 children: Rc::new(RefCell::new(vec![])),
             },
             parent: parent_node,
         };
+        // TODO: dispatch the mount event recursively after the dom node is mounted into
+        // the root node
+        let attrs = Attribute::merge_attributes_of_same_name(elm.attributes().iter());
+        dom_node.set_dom_attrs(attrs.iter().map(|a| self.convert_attr(a)));
+
         let dom_node_rc = Rc::new(Some(dom_node.clone()));
-        let children: Vec<DomNode> = elm
+        let children: Vec<DomNode<B>> = elm
             .children()
             .iter()
             .map(|child| self.create_dom_node(Rc::clone(&dom_node_rc), child))
@@ -651,12 +700,12 @@ where
 
     fn create_leaf_node(
         &self,
-        parent_node: Rc<Option<DomNode>>,
-        leaf: &vdom::Leaf<APP::MSG>,
-    ) -> DomNode {
+        parent_node: Rc<Option<DomNode<B>>>,
+        leaf: &vdom::Leaf<Self::MSG>,
+    ) -> DomNode<B> {
         match leaf {
             Leaf::Text(txt) => DomNode {
-                inner: DomInner::Text(document().create_text_node(txt)),
+                inner: DomInner::Text(B::create_text(txt)),
                 parent: parent_node,
             },
             Leaf::Symbol(symbol) => DomNode {
@@ -664,7 +713,7 @@ where
                 parent: parent_node,
             },
             Leaf::Comment(comment) => DomNode {
-                inner: DomInner::Comment(document().create_comment(comment)),
+                inner: DomInner::Comment(B::create_comment(comment)),
                 parent: parent_node,
             },
             Leaf::Fragment(nodes) => self.create_fragment_node(parent_node, nodes),
@@ -673,52 +722,93 @@ where
             // We need to wrap this node_list into doc_fragment since root_node is only 1 element
             Leaf::NodeList(nodes) => self.create_fragment_node(parent_node, nodes),
             Leaf::StatefulComponent(comp) => self.create_stateful_component(parent_node, comp),
-            Leaf::StatelessComponent(comp) => {
-                #[cfg(feature = "use-template")]
-                {
-                    self.create_stateless_component_with_template(parent_node, comp)
-                }
-                #[cfg(not(feature = "use-template"))]
-                {
-                    self.create_stateless_component(parent_node, comp)
-                }
-            }
+            Leaf::StatelessComponent(comp) => self.create_stateless_component(parent_node, comp),
             Leaf::TemplatedView(view) => {
                 unreachable!("template view should not be created: {:#?}", view)
             }
+            Leaf::Lazy(lazy) => self.create_dom_node(parent_node, lazy.view()),
             Leaf::DocType(_) => unreachable!("doc type is never converted"),
         }
     }
 
-    fn create_fragment_node<'a>(
+    fn create_fragment_node(
         &self,
-        parent_node: Rc<Option<DomNode>>,
-        nodes: impl IntoIterator<Item = &'a vdom::Node<APP::MSG>>,
-    ) -> DomNode {
-        let fragment = document().create_document_fragment();
+        parent_node: Rc<Option<DomNode<B>>>,
+        nodes: &[vdom::Node<Self::MSG>],
+    ) -> DomNode<B> {
         let dom_node = DomNode {
             inner: DomInner::Fragment {
-                fragment,
+                fragment: B::create_fragment(),
                 children: Rc::new(RefCell::new(vec![])),
             },
             parent: parent_node,
         };
         let dom_node_rc = Rc::new(Some(dom_node.clone()));
         let children = nodes
-            .into_iter()
-            .map(|node| self.create_dom_node(Rc::clone(&dom_node_rc), &node))
+            .iter()
+            .map(|node| self.create_dom_node(Rc::clone(&dom_node_rc), node))
             .collect();
         dom_node.append_children(children);
         dom_node
     }
+
+    fn convert_attr(&self, attr: &Attribute<Self::MSG>) -> DomAttr {
+        DomAttr {
+            namespace: attr.namespace,
+            name: attr.name,
+            value: attr
+                .value
+                .iter()
+                .filter_map(|v| self.convert_attr_value(v))
+                .collect(),
+        }
+    }
+
+    fn convert_attr_value(&self, attr_value: &AttributeValue<Self::MSG>) -> Option<DomAttrValue> {
+        match attr_value {
+            AttributeValue::Simple(v) => Some(DomAttrValue::Simple(v.clone())),
+            AttributeValue::Style(v) => Some(DomAttrValue::Style(v.clone())),
+            AttributeValue::EventListener(v) => Some(DomAttrValue::EventListener(
+                self.create_listener(v),
+                v.options(),
+            )),
+            AttributeValue::Empty => None,
+        }
+    }
 }
 
-/// A node along with all of the closures that were created for that
-/// node's events and all of it's child node's events.
 impl<APP> Program<APP>
 where
-    APP: Application,
+    APP: Application + 'static,
+{
+    /// Create a dom node
+    pub fn create_dom_node(
+        &self,
+        parent_node: Rc<Option<DomNode>>,
+        node: &vdom::Node<APP::MSG>,
+    ) -> DomNode {
+        CreateDomNode::create_dom_node(self, parent_node, node)
+    }
+}
+
+/// The program creates its nodes in the browser DOM,
+/// the msgs of the event listeners are dispatched to the program
+impl<APP> CreateDomNode<WebBackend> for Program<APP>
+where
+    APP: Application + 'static,
 {
+    type MSG = APP::MSG;
+
+    fn create_listener(&self, event_listener: &EventCallback<APP::MSG>) -> Listener {
+        let program = self.downgrade();
+        let event_listener = event_listener.clone();
+        Rc::new(move |event| {
+            let msg = event_listener.emit(event);
+            let mut program = program.upgrade().expect("must upgrade");
+            program.dispatch(msg);
+        })
+    }
+
     /// TODO: register the template if not yet
     /// pass a program to leaf component and mount itself and its view to the program
     /// There are 2 types of children components of Stateful Component
@@ -757,87 +847,34 @@ where
         comp_node
     }
 
-    #[allow(unused)]
+    #[cfg(feature = "use-template")]
     fn create_stateless_component(
         &self,
         parent_node: Rc<Option<DomNode>>,
         comp: &StatelessModel<APP::MSG>,
     ) -> DomNode {
-        let comp_view = &comp.view;
-        let real_comp_view = comp_view.unwrap_template_ref();
-        self.create_dom_node(parent_node, &real_comp_view)
-    }
-
-
-    /// set element with the dom attrs
-    pub(crate) fn set_element_dom_attrs(
-        &self,
-        element: &Element,
-        attrs: Vec<DomAttr>,
-    ) -> Option<NamedEventClosures> {
-        attrs
-            .into_iter()
-            .filter_map(|att| self.set_element_dom_attr(element, att))
-            .reduce(|mut acc, e| {
-                e.into_iter().for_each(|(k, v)| {
-                    acc.insert(k, v);
-                });
-                acc
-            })
-    }
-
-    fn set_element_dom_attr(&self, element: &Element, attr: DomAttr) -> Option<NamedEventClosures> {
-        let attr_name = intern(attr.name);
-        let attr_namespace = attr.namespace;
-
-        let GroupedDomAttrValues {
-            listeners,
-            plain_values,
-            styles,
-        } = attr.group_values();
-
-        DomAttr::set_element_style(element, attr_name, styles);
-        DomAttr::set_element_simple_values(element, attr_name, attr_namespace, plain_values);
-        self.add_event_listeners(element, attr_name, &listeners)
-            .unwrap();
-        if !listeners.is_empty() {
-            let event_closures =
-                IndexMap::from_iter(listeners.into_iter().map(|cb| (attr_name, cb)));
-            Some(event_closures)
-        } else {
-            None
-        }
-    }
-
-    pub(crate) fn add_event_listeners(
-        &self,
-        event_target: &web_sys::EventTarget,
-        event_name: &str,
-        listeners: &[EventClosure],
-    ) -> Result<(), JsValue> {
-        for listener in listeners.iter() {
-            self.add_event_listener(event_target, event_name, listener)
-                .unwrap();
-        }
-        Ok(())
+        self.create_stateless_component_with_template(parent_node, comp)
     }
+}
 
-    /// add a event listener to a target element
-    pub(crate) fn add_event_listener(
-        &self,
-        event_target: &web_sys::EventTarget,
-        event_name: &str,
-        listener: &Closure<dyn FnMut(web_sys::Event)>,
-    ) -> Result<(), JsValue> {
-        event_target.add_event_listener_with_callback(
-            intern(event_name),
-            listener.as_ref().unchecked_ref(),
-        )?;
-        Ok(())
-    }
+/// parse the html of a symbol into the nodes it is made of,
+/// for when there is no element to insert the html into
+pub(crate) fn symbol_nodes(symbol: &str) -> web_sys::DocumentFragment {
+    let container = document()
+        .create_element(intern("div"))
+        .expect("create element");
+    container.set_inner_html(symbol);
+    let fragment = document().create_document_fragment();
+    while let Some(child) = container.first_child() {
+        fragment.append_child(&child).expect("append symbol node");
+    }
+    fragment
 }
 
-pub(crate) fn find_node(target_node: &DomNode, path: &mut TreePath) -> Option<DomNode> {
+pub(crate) fn find_node<B: RenderBackend>(
+    target_node: &DomNode<B>,
+    path: &mut TreePath,
+) -> Option<DomNode<B>> {
     if path.is_empty() {
         Some(target_node.clone())
     } else {
@@ -851,11 +888,11 @@ pub(crate) fn find_node(target_node: &DomNode, path: &mut TreePath) -> Option<Do
     }
 }
 
-pub(crate) fn find_all_nodes(
-    target_node: &DomNode,
+pub(crate) fn find_all_nodes<B: RenderBackend>(
+    target_node: &DomNode<B>,
     nodes_to_find: &[(&TreePath, Option<&&'static str>)],
-) -> IndexMap<TreePath, DomNode> {
-    let mut nodes_to_patch: IndexMap<TreePath, DomNode> =
+) -> IndexMap<TreePath, DomNode<B>> {
+    let mut nodes_to_patch: IndexMap<TreePath, DomNode<B>> =
         IndexMap::with_capacity(nodes_to_find.len());
     for (path, tag) in nodes_to_find {
         let mut traverse_path: TreePath = (*path).clone();
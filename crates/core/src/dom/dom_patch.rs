@@ -1,16 +1,15 @@
-use crate::dom;
-use crate::dom::dom_node::find_all_nodes;
-use crate::dom::dom_node::DomInner;
+use crate::dom::dom_node::{find_all_nodes, CreateDomNode};
 use crate::dom::DomAttr;
 use crate::dom::DomAttrValue;
 use crate::dom::DomNode;
 use crate::dom::{Application, Program};
-use crate::vdom::EventCallback;
+use crate::dom::{RenderBackend, WebBackend};
+use crate::vdom::ApplyPatchError;
+use crate::vdom::Style;
 use crate::vdom::TreePath;
-use crate::vdom::{Attribute, AttributeValue, Patch, PatchType};
+use crate::vdom::{Attribute, Patch, PatchType};
 use indexmap::IndexMap;
 use std::rc::Rc;
-use wasm_bindgen::closure::Closure;
 use wasm_bindgen::JsValue;
 
 /// a Patch where the virtual nodes are all created in the document.
@@ -18,32 +17,32 @@ use wasm_bindgen::JsValue;
 /// as opposed to Patch which contains reference to the vdom, which makes it hard
 /// to be included in a struct
 #[derive(Debug)]
-pub struct DomPatch {
+pub struct DomPatch<B: RenderBackend = WebBackend> {
     /// The path to traverse to get to the target_element
     pub patch_path: TreePath,
     /// the target node
-    pub target_element: DomNode,
+    pub target_element: DomNode<B>,
     /// the patch variant
-    pub patch_variant: PatchVariant,
+    pub patch_variant: PatchVariant<B>,
 }
 
 /// patch variant
 #[derive(Debug)]
-pub enum PatchVariant {
+pub enum PatchVariant<B: RenderBackend = WebBackend> {
     /// Insert nodes before the target node
     InsertBeforeNode {
         /// nodes to be inserted before the target node
-        nodes: Vec<DomNode>,
+        nodes: Vec<DomNode<B>>,
     },
     /// Insert nodes after the target node
     InsertAfterNode {
         /// the nodes to be inserted after the target node
-        nodes: Vec<DomNode>,
+        nodes: Vec<DomNode<B>>,
     },
     /// Append nodes into the target node
     AppendChildren {
         /// the children nodes to be appended into the target node
-        children: Vec<DomNode>,
+        children: Vec<DomNode<B>>,
     },
     /// Add attributes to the target node
     AddAttributes {
@@ -55,10 +54,35 @@ pub enum PatchVariant {
         /// the attributes names to be removed
         attrs: Vec<DomAttr>,
     },
+    /// Set the style properties of the target node
+    SetStyles {
+        /// the style properties to be set
+        styles: Vec<Style>,
+    },
+    /// Remove the style properties of the target node
+    RemoveStyles {
+        /// the names of the style properties to be removed
+        names: Vec<String>,
+    },
+    /// Add the class names into the class list of the target node
+    AddClasses {
+        /// the class names to be added
+        classes: Vec<String>,
+    },
+    /// Remove the class names from the class list of the target node
+    RemoveClasses {
+        /// the class names to be removed
+        classes: Vec<String>,
+    },
     /// Replace the target node with the replacement node
     ReplaceNode {
         /// the replacement node
-        replacement: Vec<DomNode>,
+        replacement: Vec<DomNode<B>>,
+    },
+    /// Set the content of the target text or comment node
+    SetText {
+        /// the new content
+        text: String,
     },
     /// Remove the target node
     RemoveNode,
@@ -67,62 +91,26 @@ pub enum PatchVariant {
     /// Move the target node before the node specified in the path location
     MoveBeforeNode {
         /// before the node at this location
-        for_moving: Vec<DomNode>,
+        for_moving: Vec<DomNode<B>>,
     },
     /// Move the target node after the node specified in the path location
     MoveAfterNode {
         /// after the node at this location
-        for_moving: Vec<DomNode>,
+        for_moving: Vec<DomNode<B>>,
     },
 }
 
-impl<APP> Program<APP>
+impl<B> DomPatch<B>
 where
-    APP: Application + 'static,
+    B: RenderBackend,
 {
-    pub(crate) fn convert_attr(&self, attr: &Attribute<APP::MSG>) -> DomAttr {
-        DomAttr {
-            namespace: attr.namespace,
-            name: attr.name,
-            value: attr
-                .value
-                .iter()
-                .filter_map(|v| self.convert_attr_value(v))
-                .collect(),
-        }
-    }
-
-    fn convert_attr_value(&self, attr_value: &AttributeValue<APP::MSG>) -> Option<DomAttrValue> {
-        match attr_value {
-            AttributeValue::Simple(v) => Some(DomAttrValue::Simple(v.clone())),
-            AttributeValue::Style(v) => Some(DomAttrValue::Style(v.clone())),
-            AttributeValue::EventListener(v) => {
-                Some(DomAttrValue::EventListener(self.convert_event_listener(v)))
-            }
-            AttributeValue::Empty => None,
-        }
-    }
-
-    fn convert_event_listener(
-        &self,
-        event_listener: &EventCallback<APP::MSG>,
-    ) -> Closure<dyn FnMut(web_sys::Event)> {
-        let program = self.downgrade();
-        let event_listener = event_listener.clone();
-        let closure: Closure<dyn FnMut(web_sys::Event)> =
-            Closure::new(move |event: web_sys::Event| {
-                let msg = event_listener.emit(dom::Event::from(event));
-                let mut program = program.upgrade().expect("must upgrade");
-                program.dispatch(msg);
-            });
-        closure
-    }
-    /// get the real DOM target node and make a DomPatch object for each of the Patch
-    pub(crate) fn convert_patches(
-        &self,
-        target_node: &DomNode,
-        patches: &[Patch<APP::MSG>],
-    ) -> Result<Vec<DomPatch>, JsValue> {
+    /// get the target node of each of the Patch and make a DomPatch object for it,
+    /// the nodes of the patches are created with the `creator`
+    pub(crate) fn convert_all<C: CreateDomNode<B>>(
+        creator: &C,
+        target_node: &DomNode<B>,
+        patches: &[Patch<C::MSG>],
+    ) -> Result<Vec<Self>, ApplyPatchError> {
         let nodes_to_find: Vec<(&TreePath, Option<&&'static str>)> = patches
             .iter()
             .map(|patch| (patch.path(), patch.tag()))
@@ -136,36 +124,37 @@ where
 
         let nodes_lookup = find_all_nodes(target_node, &nodes_to_find);
 
-        let dom_patches:Vec<DomPatch> = patches.iter().map(|patch|{
-            let patch_path = patch.path();
-            let patch_tag = patch.tag();
-            if let Some(target_node) = nodes_lookup.get(patch_path) {
-                let target_tag = target_node.tag();
-                match (patch_tag, target_tag){
-                    (Some(patch_tag), Some(target_tag)) => {
-                        if **patch_tag != target_tag{
-                            panic!(
-                                "expecting a tag: {patch_tag:?}, but found: {target_tag:?}"
-                            );
-                        }
+        patches
+            .iter()
+            .map(|patch| {
+                let patch_path = patch.path();
+                let patch_tag = patch.tag();
+                // a patch which doesn't match the DOM is an error, such as the patches
+                // which are received from a server that is out of sync
+                let Some(target_node) = nodes_lookup.get(patch_path) else {
+                    return Err(ApplyPatchError::NodeNotFound(patch_path.clone()));
+                };
+                if let (Some(patch_tag), Some(target_tag)) = (patch_tag, target_node.tag()) {
+                    if !patch_tag.eq_ignore_ascii_case(&target_tag) {
+                        return Err(ApplyPatchError::TagMismatch {
+                            path: patch_path.clone(),
+                            expected: patch_tag.to_string(),
+                            found: Some(target_tag),
+                        });
                     }
-                    _ => (),
                 }
-                self.convert_patch(&nodes_lookup, target_node, patch)
-            } else {
-                unreachable!("Getting here means we didn't find the element of next node that we are supposed to patch, patch_path: {:?}, with tag: {:?}", patch_path, patch_tag);
-            }
-        }).collect();
-
-        Ok(dom_patches)
+                Ok(Self::convert(creator, &nodes_lookup, target_node, patch))
+            })
+            .collect()
     }
+
     /// convert a virtual DOM Patch into a created DOM node Patch
-    pub fn convert_patch(
-        &self,
-        nodes_lookup: &IndexMap<TreePath, DomNode>,
-        target_element: &DomNode,
-        patch: &Patch<APP::MSG>,
-    ) -> DomPatch {
+    pub(crate) fn convert<C: CreateDomNode<B>>(
+        creator: &C,
+        nodes_lookup: &IndexMap<TreePath, DomNode<B>>,
+        target_element: &DomNode<B>,
+        patch: &Patch<C::MSG>,
+    ) -> Self {
         let target_element = target_element.clone();
         let Patch {
             patch_path,
@@ -179,7 +168,7 @@ where
             PatchType::InsertBeforeNode { nodes } => {
                 let nodes = nodes
                     .iter()
-                    .map(|for_insert| self.create_dom_node(Rc::new(None), for_insert))
+                    .map(|for_insert| creator.create_dom_node(Rc::new(None), for_insert))
                     .collect();
                 DomPatch {
                     patch_path,
@@ -190,7 +179,7 @@ where
             PatchType::InsertAfterNode { nodes } => {
                 let nodes = nodes
                     .iter()
-                    .map(|for_insert| self.create_dom_node(Rc::new(None), for_insert))
+                    .map(|for_insert| creator.create_dom_node(Rc::new(None), for_insert))
                     .collect();
                 DomPatch {
                     patch_path,
@@ -206,7 +195,7 @@ where
                     patch_path,
                     target_element,
                     patch_variant: PatchVariant::AddAttributes {
-                        attrs: attrs.iter().map(|a| self.convert_attr(a)).collect(),
+                        attrs: attrs.iter().map(|a| creator.convert_attr(a)).collect(),
                     },
                 }
             }
@@ -214,14 +203,14 @@ where
                 patch_path,
                 target_element,
                 patch_variant: PatchVariant::RemoveAttributes {
-                    attrs: attrs.iter().map(|a| self.convert_attr(a)).collect(),
+                    attrs: attrs.iter().map(|a| creator.convert_attr(a)).collect(),
                 },
             },
 
             PatchType::ReplaceNode { replacement } => {
                 let replacement = replacement
                     .iter()
-                    .map(|node| self.create_dom_node(Rc::new(None), node))
+                    .map(|node| creator.create_dom_node(Rc::new(None), node))
                     .collect();
                 DomPatch {
                     patch_path,
@@ -271,10 +260,45 @@ where
                     patch_variant: PatchVariant::MoveAfterNode { for_moving },
                 }
             }
+            PatchType::SetStyles { styles } => DomPatch {
+                patch_path,
+                target_element,
+                patch_variant: PatchVariant::SetStyles {
+                    styles: styles.iter().map(|s| (*s).clone()).collect(),
+                },
+            },
+            PatchType::RemoveStyles { names } => DomPatch {
+                patch_path,
+                target_element,
+                patch_variant: PatchVariant::RemoveStyles {
+                    names: names.iter().map(|n| n.to_string()).collect(),
+                },
+            },
+            PatchType::SetText { text } => DomPatch {
+                patch_path,
+                target_element,
+                patch_variant: PatchVariant::SetText {
+                    text: text.to_string(),
+                },
+            },
+            PatchType::AddClasses { classes } => DomPatch {
+                patch_path,
+                target_element,
+                patch_variant: PatchVariant::AddClasses {
+                    classes: classes.clone(),
+                },
+            },
+            PatchType::RemoveClasses { classes } => DomPatch {
+                patch_path,
+                target_element,
+                patch_variant: PatchVariant::RemoveClasses {
+                    classes: classes.clone(),
+                },
+            },
             PatchType::AppendChildren { children } => {
                 let children = children
                     .iter()
-                    .map(|for_insert| self.create_dom_node(Rc::new(None), for_insert))
+                    .map(|for_insert| creator.create_dom_node(Rc::new(None), for_insert))
                     .collect();
 
                 DomPatch {
@@ -286,27 +310,14 @@ where
         }
     }
 
-    /// TODO: this should not have access to root_node, so it can generically
-    /// apply patch to any dom node
-    pub(crate) fn apply_dom_patches(
-        &self,
-        dom_patches: impl IntoIterator<Item = DomPatch>,
-    ) -> Result<(), JsValue> {
-        for dom_patch in dom_patches {
-            self.apply_dom_patch(dom_patch)?;
-        }
-        Ok(())
-    }
-
-    /// apply a dom patch to this root node,
-    /// return a new root_node if it would replace the original root_node
-    /// TODO: this should have no access to root_node, so it can be used in general sense
-    pub(crate) fn apply_dom_patch(&self, dom_patch: DomPatch) -> Result<(), JsValue> {
+    /// apply this patch to its target node, the `mount_node` is where the root node is mounted into.
+    /// Return the new root node if this replaces the root node
+    pub(crate) fn apply(self, mount_node: Option<&DomNode<B>>) -> Option<DomNode<B>> {
         let DomPatch {
             patch_path,
             target_element,
             patch_variant,
-        } = dom_patch;
+        } = self;
 
         match patch_variant {
             PatchVariant::InsertBeforeNode { nodes } => {
@@ -321,27 +332,21 @@ where
             }
 
             PatchVariant::AddAttributes { attrs } => {
-                target_element.set_dom_attrs(attrs).unwrap();
+                target_element.set_dom_attrs(attrs);
             }
             PatchVariant::RemoveAttributes { attrs } => {
                 for attr in attrs.iter() {
                     for att_value in attr.value.iter() {
                         match att_value {
                             DomAttrValue::Simple(_) => {
-                                target_element.remove_dom_attr(attr)?;
+                                target_element.remove_dom_attr(attr);
                             }
                             // it is an event listener
-                            DomAttrValue::EventListener(_) => {
-                                let DomInner::Element { listeners, .. } = &target_element.inner
-                                else {
-                                    unreachable!("must be an element");
-                                };
-                                listeners.borrow_mut().as_mut().map(|listener| {
-                                    listener.retain(|event, _| *event != attr.name)
-                                });
+                            DomAttrValue::EventListener(..) => {
+                                target_element.remove_event_listener(attr.name);
                             }
                             DomAttrValue::Style(_) => {
-                                target_element.remove_dom_attr(attr)?;
+                                target_element.remove_dom_attr(attr);
                             }
                             DomAttrValue::Empty => (),
                         }
@@ -349,6 +354,22 @@ where
                 }
             }
 
+            PatchVariant::SetStyles { styles } => {
+                target_element.set_style_properties(&styles);
+            }
+            PatchVariant::RemoveStyles { names } => {
+                target_element.remove_style_properties(&names);
+            }
+            PatchVariant::SetText { text } => {
+                target_element.set_text(&text);
+            }
+            PatchVariant::AddClasses { classes } => {
+                target_element.add_classes(&classes);
+            }
+            PatchVariant::RemoveClasses { classes } => {
+                target_element.remove_classes(&classes);
+            }
+
             // This also removes the associated closures and event listeners to the node being replaced
             // including the associated closures of the descendant of replaced node
             // before it is actully replaced in the DOM
@@ -357,8 +378,7 @@ where
                 let mut first_node = replacement.remove(0);
 
                 let parent_node = if patch_path.path.is_empty() {
-                    let mount_node = self.mount_node.borrow();
-                    let mount_node = mount_node.as_ref().expect("must have a mount node");
+                    let mount_node = mount_node.expect("must have a mount node");
                     Rc::new(Some(mount_node.clone()))
                 } else {
                     if let Some(parent_target) = target_element.parent.as_ref() {
@@ -378,14 +398,12 @@ where
                         patch_path.is_empty(),
                         "this should only happen to root node"
                     );
-                    let mut mount_node = self.mount_node.borrow_mut();
-                    let mount_node = mount_node.as_mut().expect("must have a mount node");
+                    let mount_node = mount_node.expect("must have a mount node");
                     mount_node.append_children(vec![first_node.clone()]);
                     mount_node.append_children(replacement);
                 } else {
                     if patch_path.path.is_empty() {
-                        let mut mount_node = self.mount_node.borrow_mut();
-                        let mount_node = mount_node.as_mut().expect("must have a mount node");
+                        let mount_node = mount_node.expect("must have a mount node");
                         mount_node.replace_child(&target_element, first_node.clone());
                     } else {
                         target_element.replace_node(first_node.clone());
@@ -394,7 +412,7 @@ where
                     first_node.insert_after(replacement);
                 }
                 if patch_path.path.is_empty() {
-                    *self.root_node.borrow_mut() = Some(first_node);
+                    return Some(first_node);
                 }
             }
             PatchVariant::RemoveNode => {
@@ -403,9 +421,11 @@ where
             PatchVariant::ClearChildren => {
                 target_element.clear_children();
             }
+            // the moved nodes are only taken out of the tracked children of their parent,
+            // since inserting them moves them in the document
             PatchVariant::MoveBeforeNode { for_moving } => {
                 if let Some(target_parent) = target_element.parent.as_ref() {
-                    target_parent.remove_children(&for_moving.iter().collect::<Vec<_>>());
+                    target_parent.take_children(&for_moving.iter().collect::<Vec<_>>());
                     target_element.insert_before(for_moving);
                 } else {
                     panic!("unable to get the parent node of the target element");
@@ -414,11 +434,56 @@ where
 
             PatchVariant::MoveAfterNode { for_moving } => {
                 if let Some(target_parent) = target_element.parent.as_ref() {
-                    target_parent.remove_children(&for_moving.iter().collect::<Vec<_>>());
+                    target_parent.take_children(&for_moving.iter().collect::<Vec<_>>());
                     target_element.insert_after(for_moving);
                 }
             }
         }
+        None
+    }
+}
+
+impl<APP> Program<APP>
+where
+    APP: Application + 'static,
+{
+    /// get the real DOM target node and make a DomPatch object for each of the Patch
+    pub(crate) fn convert_patches(
+        &self,
+        target_node: &DomNode,
+        patches: &[Patch<APP::MSG>],
+    ) -> Result<Vec<DomPatch>, JsValue> {
+        DomPatch::convert_all(self, target_node, patches)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// convert a virtual DOM Patch into a created DOM node Patch
+    pub fn convert_patch(
+        &self,
+        nodes_lookup: &IndexMap<TreePath, DomNode>,
+        target_element: &DomNode,
+        patch: &Patch<APP::MSG>,
+    ) -> DomPatch {
+        DomPatch::convert(self, nodes_lookup, target_element, patch)
+    }
+
+    pub(crate) fn apply_dom_patches(
+        &self,
+        dom_patches: impl IntoIterator<Item = DomPatch>,
+    ) -> Result<(), JsValue> {
+        for dom_patch in dom_patches {
+            self.apply_dom_patch(dom_patch)?;
+        }
+        Ok(())
+    }
+
+    /// apply a dom patch into the mount node of this program,
+    /// the root_node is replaced if the patch replaces it
+    pub(crate) fn apply_dom_patch(&self, dom_patch: DomPatch) -> Result<(), JsValue> {
+        let mount_node = self.mount_node.borrow().clone();
+        if let Some(root_node) = dom_patch.apply(mount_node.as_ref()) {
+            *self.root_node.borrow_mut() = Some(root_node);
+        }
         Ok(())
     }
 }
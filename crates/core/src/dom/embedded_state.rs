@@ -0,0 +1,38 @@
+//! Reading the app state which was embedded by the server with [`embed_state`](crate::html::embed_state)
+use crate::dom::{document, Application, Program};
+use crate::html::EMBEDDED_STATE_ID;
+use serde::de::DeserializeOwned;
+
+/// An error while reading the embedded app state
+#[derive(Debug, thiserror::Error)]
+pub enum EmbeddedStateError {
+    /// there is no element with the embedded state in the document
+    #[error("no embedded state element with id: {0}")]
+    Missing(&'static str),
+    /// the embedded state can not be deserialized into the app
+    #[error("unable to deserialize the embedded state: {0}")]
+    Deserialize(#[from] serde_json::Error),
+}
+
+/// deserialize the app state which was embedded by the server
+pub fn embedded_state<T>() -> Result<T, EmbeddedStateError>
+where
+    T: DeserializeOwned,
+{
+    let json = document()
+        .get_element_by_id(EMBEDDED_STATE_ID)
+        .and_then(|element| element.text_content())
+        .ok_or(EmbeddedStateError::Missing(EMBEDDED_STATE_ID))?;
+    Ok(serde_json::from_str(&json)?)
+}
+
+impl<APP> Program<APP>
+where
+    APP: Application + DeserializeOwned,
+{
+    /// Create a program with the app deserialized from the state embedded by the server,
+    /// so the first `view` is the same as the server rendered view.
+    pub fn from_embedded_state() -> Result<Self, EmbeddedStateError> {
+        Ok(Self::new(embedded_state()?))
+    }
+}
@@ -5,12 +5,13 @@ use crate::dom::DomNode;
 use crate::dom::{document, window, Event};
 use crate::vdom;
 use crate::vdom::{Attribute, AttributeValue, EventCallback};
-use wasm_bindgen::JsCast;
+use std::any::TypeId;
+use wasm_bindgen::{JsCast, JsValue};
 #[cfg(web_sys_unstable_apis)]
 pub use web_sys::ClipboardEvent;
 pub use web_sys::{
-    AnimationEvent, FocusEvent, HashChangeEvent, KeyboardEvent, MouseEvent, Selection, TouchEvent,
-    TransitionEvent,
+    AnimationEvent, DataTransfer, DragEvent, FocusEvent, HashChangeEvent, KeyboardEvent,
+    MouseEvent, PointerEvent, Selection, TouchEvent, TransitionEvent,
 };
 use web_sys::{
     EventTarget, HtmlDetailsElement, HtmlElement, HtmlInputElement, HtmlSelectElement,
@@ -57,6 +58,71 @@ impl Event {
             _ => None,
         }
     }
+
+    /// return the remote event, if this event was sent from a remote client
+    pub fn as_remote(&self) -> Option<&RemoteEvent> {
+        match self {
+            Event::RemoteEvent(remote_event) => Some(remote_event),
+            _ => None,
+        }
+    }
+}
+
+/// An event which was triggered in a remote client, such as the client of a `ServerProgram`.
+///
+/// Only the serializable parts of the event are sent, the typed event listeners such as `on_click`
+/// can not be used with remote events, use `on` and `Event::as_remote` instead.
+/// `on_input` and `on_change` can be used, the value of their `InputEvent` is the remote value.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RemoteEvent {
+    /// the event type, ie: click, input
+    pub name: String,
+    /// the value of the event target, if it is an input, textarea or select element
+    pub value: Option<String>,
+}
+
+impl RemoteEvent {
+    /// create a remote event with this event type
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: None,
+        }
+    }
+
+    /// set the value of the event target
+    pub fn with_value(mut self, value: impl Into<String>) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+}
+
+impl From<&web_sys::Event> for RemoteEvent {
+    fn from(web_event: &web_sys::Event) -> Self {
+        let target = web_event.target();
+        let value = target.and_then(|target| {
+            if let Some(input) = target.dyn_ref::<HtmlInputElement>() {
+                Some(input.value())
+            } else if let Some(textarea) = target.dyn_ref::<HtmlTextAreaElement>() {
+                Some(textarea.value())
+            } else {
+                target
+                    .dyn_ref::<HtmlSelectElement>()
+                    .map(|select| select.value())
+            }
+        });
+        Self {
+            name: web_event.type_(),
+            value,
+        }
+    }
+}
+
+impl From<RemoteEvent> for Event {
+    fn from(remote_event: RemoteEvent) -> Self {
+        Event::RemoteEvent(remote_event)
+    }
 }
 
 impl From<MountEvent> for Event {
@@ -92,13 +158,35 @@ where
     )
 }
 
+/// attach a listener which maps the event into an `EV` before calling `f`,
+/// the listener is tagged with `EV` to tell whether it can be given a [`RemoteEvent`]
+fn on_mapped<EV, F, MSG>(event_name: &'static str, f: F) -> Attribute<MSG>
+where
+    EV: 'static,
+    F: Fn(Event) -> MSG + 'static,
+    MSG: 'static,
+{
+    vdom::attr(
+        event_name,
+        AttributeValue::EventListener(EventCallback::from(f).with_event_type::<EV>()),
+    )
+}
+
+/// returns true if the listener can be given a [`RemoteEvent`], which are the listeners of `on`
+/// and the listeners of an [`InputEvent`], such as `on_input` and `on_change`.
+/// The other typed listeners, such as `on_click`, need a browser event.
+pub(crate) fn accepts_remote_event<MSG>(listener: &EventCallback<MSG>) -> bool {
+    let event_type_id = listener.event_type_id();
+    event_type_id == TypeId::of::<Event>() || event_type_id == TypeId::of::<InputEvent>()
+}
+
 /// on click event
 pub fn on_click<F, MSG>(f: F) -> Attribute<MSG>
 where
     F: Fn(MouseEvent) -> MSG + 'static,
     MSG: 'static,
 {
-    on("click", move |event: Event| f(to_mouse_event(event)))
+    on_mapped::<MouseEvent, _, _>("click", move |event: Event| f(to_mouse_event(event)))
 }
 
 /// attach callback to the scroll event
@@ -107,7 +195,7 @@ where
     F: Fn((i32, i32)) -> MSG + 'static,
     MSG: 'static,
 {
-    on("scroll", move |event: Event| {
+    on_mapped::<(i32, i32), _, _>("scroll", move |event: Event| {
         let web_event = event.as_web().expect("must be a web event");
         let target = web_event.target().expect("can't get target");
         if let Some(element) = target.dyn_ref::<web_sys::Element>() {
@@ -144,7 +232,7 @@ where
     F: Fn(MountEvent) -> MSG + 'static,
     MSG: 'static,
 {
-    on("mount", move |event: Event| {
+    on_mapped::<MountEvent, _, _>("mount", move |event: Event| {
         let web_event = event.as_web().expect("must be a web event");
         let event_target = web_event.target().expect("must have a target");
         let target_node: web_sys::Node = event_target.unchecked_into();
@@ -171,7 +259,7 @@ macro_rules! declare_events {
                     where CB: Fn($ret) -> MSG + 'static,
                           MSG: 'static,
                     {
-                        on(stringify!($event), move|event:Event|{
+                        on_mapped::<$ret, _, _>(stringify!($event), move|event:Event|{
                             cb($mapper(event))
                         })
                 }
@@ -235,6 +323,18 @@ fn to_touch_event(event: Event) -> TouchEvent {
     web_event.dyn_into().expect("unable to cast to touch event")
 }
 
+fn to_pointer_event(event: Event) -> PointerEvent {
+    let web_event = event.as_web().expect("must be a web_sys event");
+    web_event
+        .dyn_into()
+        .expect("unable to cast to pointer event")
+}
+
+fn to_drag_event(event: Event) -> DragEvent {
+    let web_event = event.as_web().expect("must be a web_sys event");
+    web_event.dyn_into().expect("unable to cast to drag event")
+}
+
 fn to_webevent(event: Event) -> web_sys::Event {
     match event {
         Event::WebEvent(event) => event,
@@ -254,20 +354,35 @@ fn to_hashchange_event(event: Event) -> HashChangeEvent {
 /// a custom InputEvent to contain the input string value
 #[derive(Debug)]
 pub struct InputEvent {
-    /// the actual dom event
-    pub event: web_sys::Event,
+    /// the actual dom event, `None` if the event was triggered in a remote client
+    pub event: Option<web_sys::Event>,
+    /// the value of the remote event
+    remote_value: Option<String>,
 }
 
 impl InputEvent {
     fn new(event: web_sys::Event) -> Self {
-        InputEvent { event }
+        InputEvent {
+            event: Some(event),
+            remote_value: None,
+        }
+    }
+
+    fn from_remote(remote_event: RemoteEvent) -> Self {
+        InputEvent {
+            event: None,
+            remote_value: remote_event.value,
+        }
     }
 
     /// the input value
     /// TODO: this should be optional since there will be custom component
     /// aside from `input`, `textarea`, `select`
     pub fn value(&self) -> String {
-        let target: EventTarget = self.event.target().expect("Unable to get event target");
+        let Some(event) = &self.event else {
+            return self.remote_value.clone().unwrap_or_default();
+        };
+        let target: EventTarget = event.target().expect("Unable to get event target");
         if let Some(input) = target.dyn_ref::<HtmlInputElement>() {
             input.value()
         } else if let Some(textarea) = target.dyn_ref::<HtmlTextAreaElement>() {
@@ -295,8 +410,11 @@ impl InputEvent {
 }
 
 fn to_input_event(event: Event) -> InputEvent {
-    let web_event = event.as_web().expect("must be a web event");
-    InputEvent::new(web_event)
+    match event {
+        Event::WebEvent(web_event) => InputEvent::new(web_event),
+        Event::RemoteEvent(remote_event) => InputEvent::from_remote(remote_event),
+        _ => panic!("must be a web event or a remote event"),
+    }
 }
 
 fn to_checked(event: Event) -> bool {
@@ -366,6 +484,24 @@ declare_html_events! {
     on_touchstart => touchstart => to_touch_event => TouchEvent;
     on_touchend => touchend => to_touch_event => TouchEvent;
     on_touchmove => touchmove => to_touch_event => TouchEvent;
+    on_pointerdown => pointerdown => to_pointer_event => PointerEvent;
+    on_pointermove => pointermove => to_pointer_event => PointerEvent;
+    on_pointerup => pointerup => to_pointer_event => PointerEvent;
+    on_pointercancel => pointercancel => to_pointer_event => PointerEvent;
+    on_pointerover => pointerover => to_pointer_event => PointerEvent;
+    on_pointerout => pointerout => to_pointer_event => PointerEvent;
+    on_pointerenter => pointerenter => to_pointer_event => PointerEvent;
+    on_pointerleave => pointerleave => to_pointer_event => PointerEvent;
+    on_gotpointercapture => gotpointercapture => to_pointer_event => PointerEvent;
+    on_lostpointercapture => lostpointercapture => to_pointer_event => PointerEvent;
+    on_drag => drag => to_drag_event => DragEvent;
+    on_dragstart => dragstart => to_drag_event => DragEvent;
+    on_dragend => dragend => to_drag_event => DragEvent;
+    on_dragenter => dragenter => to_drag_event => DragEvent;
+    on_dragleave => dragleave => to_drag_event => DragEvent;
+    /// Note: the default of this event has to be prevented for the element to accept a drop
+    on_dragover => dragover => to_drag_event => DragEvent;
+    on_drop => drop => to_drag_event => DragEvent;
     on_focus => focus => to_focus_event => FocusEvent;
     on_blur => blur => to_focus_event => FocusEvent;
     on_reset => reset => to_webevent => web_sys::Event;
@@ -382,3 +518,57 @@ declare_html_events! {
     on_readystatechange => readystatechange => to_webevent => web_sys::Event;
     on_selectionchange => selectionchange => to_selection => Option<Selection>;
 }
+
+/// the element which the listener of this event is attached to,
+/// which is only known while the event is being dispatched
+fn current_element(event: &web_sys::Event) -> Result<web_sys::Element, JsValue> {
+    event
+        .current_target()
+        .ok_or_else(|| JsValue::from_str("the event is not being dispatched"))?
+        .dyn_into()
+        .map_err(|_| JsValue::from_str("the event listener is not attached to an element"))
+}
+
+/// Capture the pointer of this event into the element which listens to it,
+/// such that the element keeps receiving the pointer events even when the pointer moves outside of it,
+/// ie: dragging a column resize handle.
+/// The capture is released when the pointer is released, or with [`release_pointer_capture`].
+///
+/// Returns an error if the event is not being dispatched to an element, or the pointer is no longer active.
+pub fn set_pointer_capture(event: &PointerEvent) -> Result<(), JsValue> {
+    current_element(event)?.set_pointer_capture(event.pointer_id())
+}
+
+/// Release the capture of the pointer of this event from the element which listens to it
+///
+/// Returns an error if the event is not being dispatched to an element, or the pointer is no longer active.
+pub fn release_pointer_capture(event: &PointerEvent) -> Result<(), JsValue> {
+    current_element(event)?.release_pointer_capture(event.pointer_id())
+}
+
+/// returns true if the pointer of this event is captured by the element which listens to it
+pub fn has_pointer_capture(event: &PointerEvent) -> bool {
+    current_element(event)
+        .map(|element| element.has_pointer_capture(event.pointer_id()))
+        .unwrap_or(false)
+}
+
+/// Set the data of the drag operation in this `format`, ie: `text/plain`,
+/// which is usually done in `on_dragstart` and read in `on_drop` with [`get_drag_data`]
+///
+/// Returns an error if the event has no `DataTransfer`, or the browser does not allow
+/// changing the data of the drag in this event.
+pub fn set_drag_data(event: &DragEvent, format: &str, data: &str) -> Result<(), JsValue> {
+    event
+        .data_transfer()
+        .ok_or_else(|| JsValue::from_str("the drag event has no data transfer"))?
+        .set_data(format, data)
+}
+
+/// Get the data of the drag operation in this `format`, returns `None` if there is no data in this format.
+///
+/// Note: the browser only allows reading the data in the `drop` event.
+pub fn get_drag_data(event: &DragEvent, format: &str) -> Option<String> {
+    let data = event.data_transfer()?.get_data(format).ok()?;
+    (!data.is_empty()).then_some(data)
+}
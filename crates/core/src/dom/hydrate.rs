@@ -0,0 +1,416 @@
+//! Hydration adopts the DOM that was rendered in the server with `render_to_string`,
+//! instead of creating the DOM from scratch.
+//!
+//! The existing DOM is walked together with the app view, only the event listeners
+//! are attached to the existing elements. When a node doesn't match the view, it is reported
+//! as a [`HydrationMismatch`] and the mismatched node is replaced with a newly created one.
+use crate::dom::dom_node::{symbol_nodes, CreateDomNode, DomInner};
+use crate::dom::{document, Application, DomAttr, DomNode, Program};
+use crate::vdom::{self, Attribute, Leaf, TreePath};
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use web_sys::{self, Node};
+
+/// A difference between the server rendered DOM and the app view,
+/// found while hydrating
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HydrationMismatch {
+    /// the path of the mismatched node in the view
+    pub path: TreePath,
+    /// why the existing DOM node can not be adopted
+    pub reason: String,
+}
+
+impl fmt::Display for HydrationMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "at {:?}: {}", self.path.path, self.reason)
+    }
+}
+
+/// returns true if this is a text node containing only whitespace,
+/// such as the indentation of a pretty rendered html
+fn is_whitespace_text(node: &Node) -> bool {
+    node.node_type() == Node::TEXT_NODE
+        && node
+            .text_content()
+            .map(|text| text.trim().is_empty())
+            .unwrap_or(true)
+}
+
+/// describe the existing DOM node for the mismatch reason
+fn describe(node: &Node) -> String {
+    match node.node_type() {
+        Node::ELEMENT_NODE => {
+            let element: &web_sys::Element = node.unchecked_ref();
+            format!("<{}>", element.local_name())
+        }
+        Node::TEXT_NODE => format!("text {:?}", node.text_content().unwrap_or_default()),
+        Node::COMMENT_NODE => "a comment".to_string(),
+        node_type => format!("node type {node_type}"),
+    }
+}
+
+/// returns true if there is a symbol among the `nodes`, or the nodes which are rendered in place
+/// of them such as the nodes of fragments, node lists, stateless components and lazy views
+fn has_symbol_in_place<MSG>(nodes: &[vdom::Node<MSG>]) -> bool {
+    nodes.iter().any(|node| match node {
+        vdom::Node::Element(_) => false,
+        vdom::Node::Leaf(leaf) => match leaf {
+            Leaf::Symbol(_) => true,
+            Leaf::Fragment(nodes) | Leaf::NodeList(nodes) => has_symbol_in_place(nodes),
+            Leaf::StatelessComponent(comp) => {
+                has_symbol_in_place(std::slice::from_ref(comp.view.unwrap_template_ref()))
+            }
+            Leaf::Lazy(lazy) => has_symbol_in_place(std::slice::from_ref(lazy.view())),
+            _ => false,
+        },
+    })
+}
+
+impl<APP> Program<APP>
+where
+    APP: Application + 'static,
+{
+    /// Create the DomNode of the current view by adopting the existing `mount_node`
+    /// which is the server rendered root node of the view.
+    pub(crate) fn hydrate_initial_view(
+        &self,
+        mount_node: &Node,
+    ) -> (DomNode, Vec<HydrationMismatch>) {
+        let current_view = self.app_context.current_vdom();
+        let real_view = current_view.unwrap_template_ref();
+        let parent_web = mount_node
+            .parent_node()
+            .expect("mount node must have a parent");
+        let mut cursor = Some(mount_node.clone());
+        let mut mismatches = vec![];
+        let root_node = self.hydrate_dom_node(
+            Rc::new(None),
+            &parent_web,
+            real_view,
+            &mut cursor,
+            TreePath::root(),
+            &mut mismatches,
+        );
+        (root_node, mismatches)
+    }
+
+    /// Adopt the DOM node at the `cursor` for the vdom `node`,
+    /// the cursor is then moved to the next sibling.
+    fn hydrate_dom_node(
+        &self,
+        parent_node: Rc<Option<DomNode>>,
+        parent_web: &Node,
+        node: &vdom::Node<APP::MSG>,
+        cursor: &mut Option<Node>,
+        path: TreePath,
+        mismatches: &mut Vec<HydrationMismatch>,
+    ) -> DomNode {
+        // whitespace from the indentation of pretty rendered html is not part of the view
+        if !node.is_text() {
+            while let Some(existing) = cursor.clone().filter(is_whitespace_text) {
+                *cursor = existing.next_sibling();
+                parent_web
+                    .remove_child(&existing)
+                    .expect("must remove whitespace");
+            }
+        }
+
+        match node {
+            vdom::Node::Element(elm) => {
+                match cursor.clone() {
+                    Some(existing)
+                        if existing.node_type() == Node::ELEMENT_NODE
+                            && existing
+                                .unchecked_ref::<web_sys::Element>()
+                                .local_name()
+                                .eq_ignore_ascii_case(elm.tag()) =>
+                    {
+                        *cursor = existing.next_sibling();
+                        self.hydrate_element_node(
+                            parent_node,
+                            existing.unchecked_into(),
+                            elm,
+                            path,
+                            mismatches,
+                        )
+                    }
+                    existing => {
+                        let reason = match &existing {
+                            Some(existing) => format!(
+                                "expecting <{}>, found {}",
+                                elm.tag(),
+                                describe(existing)
+                            ),
+                            None => format!("expecting <{}>, found nothing", elm.tag()),
+                        };
+                        mismatches.push(HydrationMismatch { path, reason });
+                        self.replace_with_created(parent_node, parent_web, node, cursor)
+                    }
+                }
+            }
+            vdom::Node::Leaf(leaf) => match leaf {
+                Leaf::Text(txt) => match cursor.clone() {
+                    Some(existing) if existing.node_type() == Node::TEXT_NODE => {
+                        let content = existing.text_content().unwrap_or_default();
+                        if content == *txt || content.trim() == txt.trim() {
+                            *cursor = existing.next_sibling();
+                            let text_node: web_sys::Text = existing.unchecked_into();
+                            if content != *txt {
+                                text_node.set_data(txt);
+                            }
+                            DomNode {
+                                inner: DomInner::Text(text_node.into()),
+                                parent: parent_node,
+                            }
+                        } else {
+                            mismatches.push(HydrationMismatch {
+                                path,
+                                reason: format!("expecting text {txt:?}, found text {content:?}"),
+                            });
+                            self.replace_with_created(parent_node, parent_web, node, cursor)
+                        }
+                    }
+                    existing => {
+                        mismatches.push(HydrationMismatch {
+                            path,
+                            reason: format!(
+                                "expecting text {txt:?}, found {}",
+                                existing
+                                    .as_ref()
+                                    .map(describe)
+                                    .unwrap_or_else(|| "nothing".to_string())
+                            ),
+                        });
+                        self.replace_with_created(parent_node, parent_web, node, cursor)
+                    }
+                },
+                // the content of the comment is not compared, since it is escaped when rendered
+                Leaf::Comment(_) => match cursor.clone() {
+                    Some(existing) if existing.node_type() == Node::COMMENT_NODE => {
+                        *cursor = existing.next_sibling();
+                        DomNode {
+                            inner: DomInner::Comment(existing),
+                            parent: parent_node,
+                        }
+                    }
+                    existing => {
+                        mismatches.push(HydrationMismatch {
+                            path,
+                            reason: format!(
+                                "expecting a comment, found {}",
+                                existing
+                                    .as_ref()
+                                    .map(describe)
+                                    .unwrap_or_else(|| "nothing".to_string())
+                            ),
+                        });
+                        self.replace_with_created(parent_node, parent_web, node, cursor)
+                    }
+                },
+                // the nodes of the fragment are rendered in place, so they are adopted
+                // from the same siblings
+                Leaf::Fragment(nodes) | Leaf::NodeList(nodes) => {
+                    let dom_node = DomNode {
+                        inner: DomInner::Fragment {
+                            fragment: document().create_document_fragment().into(),
+                            children: Rc::new(RefCell::new(vec![])),
+                        },
+                        parent: parent_node,
+                    };
+                    let dom_node_rc = Rc::new(Some(dom_node.clone()));
+                    let children = nodes
+                        .iter()
+                        .enumerate()
+                        .map(|(i, child)| {
+                            self.hydrate_dom_node(
+                                Rc::clone(&dom_node_rc),
+                                parent_web,
+                                child,
+                                cursor,
+                                path.traverse(i),
+                                mismatches,
+                            )
+                        })
+                        .collect();
+                    if let DomInner::Fragment {
+                        children: fragment_children,
+                        ..
+                    } = &dom_node.inner
+                    {
+                        *fragment_children.borrow_mut() = children;
+                    }
+                    dom_node
+                }
+                Leaf::StatelessComponent(comp) => self.hydrate_dom_node(
+                    parent_node,
+                    parent_web,
+                    comp.view.unwrap_template_ref(),
+                    cursor,
+                    path,
+                    mismatches,
+                ),
+                Leaf::Lazy(lazy) => self.hydrate_dom_node(
+                    parent_node,
+                    parent_web,
+                    lazy.view(),
+                    cursor,
+                    path,
+                    mismatches,
+                ),
+                // stateful component is rendered as a placeholder comment in the server,
+                // so it is always created
+                Leaf::StatefulComponent(_) => {
+                    self.replace_with_created(parent_node, parent_web, node, cursor)
+                }
+                // the children of an element with symbols are created instead of hydrated,
+                // this is only reached when the symbol is at the root of the view
+                Leaf::Symbol(symbol) => {
+                    mismatches.push(HydrationMismatch {
+                        path,
+                        reason: format!("symbol {symbol:?} can not be adopted, it is created"),
+                    });
+                    let nodes = symbol_nodes(symbol);
+                    match cursor.take() {
+                        Some(existing) => {
+                            *cursor = existing.next_sibling();
+                            parent_web
+                                .replace_child(&nodes, &existing)
+                                .expect("must replace mismatched node");
+                        }
+                        None => {
+                            parent_web
+                                .append_child(&nodes)
+                                .expect("must append missing node");
+                        }
+                    }
+                    DomNode {
+                        inner: DomInner::Symbol(symbol.clone()),
+                        parent: parent_node,
+                    }
+                }
+                Leaf::TemplatedView(view) => {
+                    unreachable!("template view should not be hydrated: {:#?}", view)
+                }
+                Leaf::DocType(_) => unreachable!("doc type is never converted"),
+            },
+        }
+    }
+
+    /// adopt the existing `element` attaching only the event listeners
+    fn hydrate_element_node(
+        &self,
+        parent_node: Rc<Option<DomNode>>,
+        element: web_sys::Element,
+        elm: &vdom::Element<APP::MSG>,
+        path: TreePath,
+        mismatches: &mut Vec<HydrationMismatch>,
+    ) -> DomNode {
+        let attrs = Attribute::merge_attributes_of_same_name(elm.attributes().iter());
+        let dom_node = DomNode {
+            inner: DomInner::Element {
+                element: element.clone().into(),
+                listeners: Rc::new(RefCell::new(None)),
+                children: Rc::new(RefCell::new(vec![])),
+            },
+            parent: parent_node,
+        };
+        Self::attach_element_listeners(&dom_node, attrs.iter().map(|a| self.convert_attr(a)));
+        let dom_node_rc = Rc::new(Some(dom_node.clone()));
+
+        // symbols are merged with the adjacent text when parsed by the browser,
+        // so there is no 1 to 1 mapping of the nodes, the children are created instead
+        let has_symbol = has_symbol_in_place(elm.children());
+        if has_symbol {
+            dom_node.clear_children();
+            let children: Vec<DomNode> = elm
+                .children()
+                .iter()
+                .map(|child| self.create_dom_node(Rc::clone(&dom_node_rc), child))
+                .collect();
+            dom_node.append_children(children);
+        } else {
+            let parent_web: &Node = element.unchecked_ref();
+            let mut cursor = parent_web.first_child();
+            let children: Vec<DomNode> = elm
+                .children()
+                .iter()
+                .enumerate()
+                .map(|(i, child)| {
+                    self.hydrate_dom_node(
+                        Rc::clone(&dom_node_rc),
+                        parent_web,
+                        child,
+                        &mut cursor,
+                        path.traverse(i),
+                        mismatches,
+                    )
+                })
+                .collect();
+
+            // the remaining nodes are not in the view
+            while let Some(extra) = cursor {
+                cursor = extra.next_sibling();
+                if !is_whitespace_text(&extra) {
+                    mismatches.push(HydrationMismatch {
+                        path: path.clone(),
+                        reason: format!("found extra {}", describe(&extra)),
+                    });
+                }
+                parent_web
+                    .remove_child(&extra)
+                    .expect("must remove extra node");
+            }
+
+            if let DomInner::Element {
+                children: element_children,
+                ..
+            } = &dom_node.inner
+            {
+                *element_children.borrow_mut() = children;
+            }
+        }
+        dom_node.dispatch_mount_event();
+        dom_node
+    }
+
+    /// create the dom node for `node` and put it in place of the existing node at the cursor
+    fn replace_with_created(
+        &self,
+        parent_node: Rc<Option<DomNode>>,
+        parent_web: &Node,
+        node: &vdom::Node<APP::MSG>,
+        cursor: &mut Option<Node>,
+    ) -> DomNode {
+        let created = self.create_dom_node(parent_node, node);
+        match cursor.take() {
+            Some(existing) => {
+                *cursor = existing.next_sibling();
+                parent_web
+                    .replace_child(&created.as_node(), &existing)
+                    .expect("must replace mismatched node");
+            }
+            None => {
+                parent_web
+                    .append_child(&created.as_node())
+                    .expect("must append missing node");
+            }
+        }
+        created.dispatch_mount_event();
+        created
+    }
+
+    /// attach only the event listeners of the attributes, since the other attributes
+    /// are already set in the existing element
+    fn attach_element_listeners(dom_node: &DomNode, attrs: impl IntoIterator<Item = DomAttr>) {
+        for attr in attrs {
+            let attr_name = attr.name;
+            let listeners = attr.group_values().listeners;
+            if !listeners.is_empty() {
+                dom_node.set_event_listeners(attr_name, listeners);
+            }
+        }
+    }
+}
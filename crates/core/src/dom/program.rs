@@ -4,6 +4,7 @@ use crate::dom::request_animation_frame;
 #[cfg(feature = "with-ric")]
 use crate::dom::request_idle_callback;
 use crate::dom::DomNode;
+use crate::dom::HydrationMismatch;
 use crate::dom::SkipDiff;
 use crate::dom::SkipPath;
 use crate::dom::{document, now, IdleDeadline, Measurements};
@@ -282,6 +283,14 @@ where
         ManuallyDrop::new(program)
     }
 
+    /// Instantiate the app and adopt the server rendered `mount_node` as the root node of the app view.
+    /// The `mount_node` is the root node of the html rendered with `render_to_string`.
+    pub fn hydrate_mount(app: APP, mount_node: &web_sys::Node) -> ManuallyDrop<Self> {
+        let mut program = Self::new(app);
+        program.mount(mount_node, MountProcedure::hydrate());
+        ManuallyDrop::new(program)
+    }
+
     /// clear the existing children of the mount before mounting the app
     pub fn clear_append_to_mount(app: APP, mount_node: &web_sys::Node) -> ManuallyDrop<Self> {
         let mut program = Self::new(app);
@@ -336,14 +345,43 @@ where
     /// each element and it's descendant in the vdom is created into
     /// an actual DOM node.
     pub fn mount(&mut self, mount_node: &web_sys::Node, mount_procedure: MountProcedure) {
+        let mismatches = self.mount_with_mismatches(mount_node, mount_procedure);
+        for mismatch in mismatches {
+            log::warn!("hydration mismatch {mismatch}");
+        }
+    }
+
+    /// adopt the server rendered `mount_node` as the root node of the app view,
+    /// returns the nodes that didn't match the view, which were replaced.
+    pub fn hydrate(&mut self, mount_node: &web_sys::Node) -> Vec<HydrationMismatch> {
+        self.mount_with_mismatches(mount_node, MountProcedure::hydrate())
+    }
+
+    fn mount_with_mismatches(
+        &mut self,
+        mount_node: &web_sys::Node,
+        mount_procedure: MountProcedure,
+    ) -> Vec<HydrationMismatch> {
+        let web_mount_node = mount_node;
         let mount_node = DomNode::from(mount_node.clone());
         *self.mount_node.borrow_mut() = Some(mount_node);
         self.pre_mount();
 
-        #[cfg(feature = "use-template")]
-        let created_node = self.create_initial_view_with_template();
-        #[cfg(not(feature = "use-template"))]
-        let created_node = self.create_initial_view();
+        let mut mismatches = vec![];
+        let created_node = if let MountAction::Hydrate = mount_procedure.action {
+            let (hydrated_node, hydrate_mismatches) = self.hydrate_initial_view(web_mount_node);
+            mismatches = hydrate_mismatches;
+            hydrated_node
+        } else {
+            #[cfg(feature = "use-template")]
+            {
+                self.create_initial_view_with_template()
+            }
+            #[cfg(not(feature = "use-template"))]
+            {
+                self.create_initial_view()
+            }
+        };
 
         let mount_node: DomNode = match mount_procedure.target {
             MountTarget::MountNode => self
@@ -388,9 +426,12 @@ where
             MountAction::Replace => {
                 mount_node.replace_node(created_node.clone());
             }
+            // the hydrated node is already in place of the mount node
+            MountAction::Hydrate => (),
         }
         *self.root_node.borrow_mut() = Some(created_node);
         self.after_mounted();
+        mismatches
     }
 
     #[cfg(feature = "with-ric")]
@@ -448,11 +489,14 @@ where
             let current_vdom = self.app_context.current_vdom();
             let real_current_vdom = current_vdom.unwrap_template_ref();
             let real_view = view.unwrap_template_ref();
-            let patches =
-                self.create_patches_with_skip_diff(&real_current_vdom, &real_view, &skip_diff);
+            let patches = vdom::optimize_patches(self.create_patches_with_skip_diff(
+                &real_current_vdom,
+                &real_view,
+                &skip_diff,
+            ));
             #[cfg(all(feature = "with-debug", feature = "log-patches"))]
             {
-                log::info!("There are {} patches", patches.len());
+                log::info!("{}", vdom::explain_patches(real_current_vdom, &patches));
                 log::info!("patches: {patches:#?}");
             }
             self.convert_patches(
@@ -467,6 +511,12 @@ where
             self.create_dom_patch(&view)
         };
 
+        // validated after diffing, so only the lazy views which the diff built are checked
+        #[cfg(all(feature = "ensure-check", debug_assertions))]
+        for error in view.validate() {
+            log::error!("{error}");
+        }
+
         let total_patches = dom_patches.len();
 
         // update the last DOM node tree with this new view
@@ -528,10 +578,10 @@ where
         self.pending_patches.borrow_mut().extend(dom_patches);
 
         #[cfg(feature = "with-raf")]
-        self.apply_pending_patches_with_raf().expect("raf");
+        self.apply_pending_patches_with_raf()?;
 
         #[cfg(not(feature = "with-raf"))]
-        self.apply_pending_patches().expect("raf");
+        self.apply_pending_patches()?;
 
         Ok(())
     }
@@ -554,11 +604,11 @@ where
 
     fn create_dom_patch(&self, new_vdom: &vdom::Node<APP::MSG>) -> Vec<DomPatch> {
         let current_vdom = self.app_context.current_vdom();
-        let patches = diff(&current_vdom, new_vdom);
+        let patches = vdom::optimize_patches(diff(&current_vdom, new_vdom));
 
         #[cfg(all(feature = "with-debug", feature = "log-patches"))]
         {
-            log::debug!("There are {} patches", patches.len());
+            log::debug!("{}", vdom::explain_patches(&current_vdom, &patches));
             log::debug!("patches: {patches:#?}");
         }
 
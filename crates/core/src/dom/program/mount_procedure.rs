@@ -7,6 +7,9 @@ pub enum MountAction {
     ClearAppend,
     /// replace the target mount node with the APP's root node
     Replace,
+    /// adopt the target mount node, which is the server rendered root node of the APP,
+    /// attaching only the event listeners. Mismatched nodes are replaced.
+    Hydrate,
 }
 
 /// specify whether to attach the Node in shadow_root
@@ -48,6 +51,11 @@ impl MountProcedure {
         Self::new(MountAction::Replace, MountTarget::MountNode)
     }
 
+    /// hydrate the mount node which was rendered in the server
+    pub fn hydrate() -> Self {
+        Self::new(MountAction::Hydrate, MountTarget::MountNode)
+    }
+
     /// append to the mount node but on it's shadow
     pub fn append_to_shadow() -> Self {
         Self::new(MountAction::Append, MountTarget::ShadowRoot)
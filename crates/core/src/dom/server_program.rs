@@ -0,0 +1,309 @@
+//! Run an [`Application`] on the server, where `update` and `view` are executed natively.
+//!
+//! The view is sent to the client when mounted, and each update thereafter is sent as the patches
+//! from diffing the previous view with the new one. The event listeners stay in the server,
+//! the client only knows their handler ids and sends back a [`ClientMessage`] when an event is triggered.
+use crate::dom::cmd::Command;
+use crate::dom::events::RemoteEvent;
+use crate::dom::{Application, Cmd, Event};
+use crate::vdom::wire::{self, HandlerId, HandlerRegistry, OwnedPatch, WireNode, WirePatch};
+use crate::vdom::{
+    diff, find_by_path, Attribute, EventCallback, Node, Patch, PatchType, TreePath, Visitor,
+};
+use futures::executor::LocalPool;
+use futures::task::LocalSpawnExt;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::io;
+use std::rc::Rc;
+
+pub use client::RemoteClient;
+pub use transport::{ChannelTransport, Transport};
+
+mod client;
+mod transport;
+
+/// A message sent from the server to the client
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ServerMessage {
+    /// the whole view to be mounted
+    Mount(WireNode),
+    /// the patches to be applied to the mounted view
+    Patches(Vec<WirePatch>),
+}
+
+/// A message sent from the client to the server when an event is triggered
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClientMessage {
+    /// the handler id of the event listener
+    pub handler: HandlerId,
+    /// the event which triggered the event listener
+    pub event: RemoteEvent,
+}
+
+pub(crate) fn invalid_data(err: impl std::error::Error + Send + Sync + 'static) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+/// encode the message to be sent through the transport
+pub(crate) fn encode<T: Serialize>(message: &T) -> io::Result<Vec<u8>> {
+    wire::to_binary(message).map_err(invalid_data)
+}
+
+/// decode the message received from the transport
+pub(crate) fn decode<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> io::Result<T> {
+    wire::from_binary(bytes).map_err(invalid_data)
+}
+
+/// the listener of the client view, which returns its own handler id
+fn handler_listener(handler: HandlerId) -> EventCallback<HandlerId> {
+    EventCallback::from(move |_event: Event| handler)
+}
+
+/// collects the handler ids of the listeners in the client view
+#[derive(Default)]
+struct Handlers(Vec<HandlerId>);
+
+impl Visitor<HandlerId> for Handlers {
+    fn visit_attribute(&mut self, attr: &Attribute<HandlerId>, _path: &TreePath) {
+        self.0.extend(
+            attr.value()
+                .iter()
+                .filter_map(|v| v.as_event_listener())
+                .map(|listener| listener.emit(Event::RemoteEvent(RemoteEvent::new(attr.name)))),
+        );
+    }
+}
+
+/// collect the handler ids of the listeners which the `patch` removes from the client view
+fn removed_handlers(
+    client_view: &Node<HandlerId>,
+    patch: &Patch<HandlerId>,
+    removed: &mut Handlers,
+) {
+    let path = &patch.patch_path;
+    let Some(target) = find_by_path(client_view, path) else {
+        return;
+    };
+    match &patch.patch_type {
+        PatchType::RemoveNode | PatchType::ReplaceNode { .. } => removed.visit_node(target, path),
+        PatchType::ClearChildren => match target {
+            Node::Element(element) => {
+                for child in element.children() {
+                    removed.visit_node(child, path);
+                }
+            }
+            Node::Leaf(leaf) => removed.visit_leaf(leaf, path),
+        },
+        // the attributes replace the existing attributes of the same name
+        PatchType::AddAttributes { attrs } | PatchType::RemoveAttributes { attrs } => {
+            let Node::Element(element) = target else {
+                return;
+            };
+            for attr in element.attributes().iter() {
+                if attrs.iter().any(|changed| changed.name == attr.name) {
+                    removed.visit_attribute(attr, path);
+                }
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Runs the [`Application`] natively and pushes the view changes to the client through the `transport`.
+///
+/// Note: the event listeners are called with [`Event::RemoteEvent`], so the typed event listeners such
+/// as `on_click` can not be used in the view, use `on` instead.
+/// The `Cmd` from `init` and `update` are run on a local executor, the commands which are waiting
+/// for something outside of the app are polled again on the next [`process_incoming`](Self::process_incoming).
+/// Subscriptions of browser events are not supported and are dropped.
+pub struct ServerProgram<APP, T>
+where
+    APP: Application,
+{
+    app: APP,
+    current_vdom: Node<APP::MSG>,
+    /// the listeners of the nodes in the client view, the listeners of the nodes
+    /// which are removed or replaced in the client are released
+    registry: HandlerRegistry<APP::MSG>,
+    /// the view as it is in the client, where each listener returns its handler id,
+    /// this is `None` until mounted
+    client_view: Option<Node<HandlerId>>,
+    transport: T,
+    pool: LocalPool,
+    /// the msgs from the client events and the completed commands, which are yet to be updated
+    pending_msgs: Rc<RefCell<VecDeque<APP::MSG>>>,
+}
+
+impl<APP, T> ServerProgram<APP, T>
+where
+    APP: Application,
+    APP::MSG: 'static,
+    T: Transport,
+{
+    /// create a server program, the view is not sent until it is mounted
+    pub fn new(app: APP, transport: T) -> Self {
+        let current_vdom = app.view();
+        Self {
+            app,
+            current_vdom,
+            registry: HandlerRegistry::new(),
+            client_view: None,
+            transport,
+            pool: LocalPool::new(),
+            pending_msgs: Rc::new(RefCell::new(VecDeque::new())),
+        }
+    }
+
+    /// return a reference to the app
+    pub fn app(&self) -> &APP {
+        &self.app
+    }
+
+    /// the view that was last sent to the client
+    pub fn current_vdom(&self) -> &Node<APP::MSG> {
+        &self.current_vdom
+    }
+
+    /// the listeners which the client can trigger with their handler ids
+    pub fn handlers(&self) -> &HandlerRegistry<APP::MSG> {
+        &self.registry
+    }
+
+    /// send the whole view to the client, then execute the `init` of the app
+    pub fn mount(&mut self) -> io::Result<()> {
+        let view = self.current_vdom.to_wire(&mut self.registry);
+        self.client_view = Some(
+            view.clone()
+                .into_node(&handler_listener)
+                .map_err(invalid_data)?,
+        );
+        self.send(&ServerMessage::Mount(view))?;
+        let cmd = self.app.init();
+        self.execute(cmd);
+        self.dispatch_pending_msgs()
+    }
+
+    /// dispatch a single msg
+    pub fn dispatch(&mut self, msg: APP::MSG) -> io::Result<()> {
+        self.dispatch_multiple([msg])
+    }
+
+    /// update the app with the msgs and send the resulting patches to the client
+    pub fn dispatch_multiple(
+        &mut self,
+        msgs: impl IntoIterator<Item = APP::MSG>,
+    ) -> io::Result<()> {
+        self.pending_msgs.borrow_mut().extend(msgs);
+        self.dispatch_pending_msgs()
+    }
+
+    /// call the event listener with the event that was triggered in the client
+    pub fn handle_message(&mut self, message: ClientMessage) -> io::Result<()> {
+        let listener = self.registry.get(message.handler).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("unknown handler id: {:?}", message.handler),
+            )
+        })?;
+        let msg = listener.emit(Event::RemoteEvent(message.event));
+        self.dispatch(msg)
+    }
+
+    /// handle all the messages that are received from the client so far,
+    /// then update the app with the msgs of the commands which are completed since the last call.
+    /// Returns the number of messages handled.
+    pub fn process_incoming(&mut self) -> io::Result<usize> {
+        let mut count = 0;
+        while let Some(bytes) = self.transport.try_recv()? {
+            self.handle_message(decode(&bytes)?)?;
+            count += 1;
+        }
+        self.dispatch_pending_msgs()?;
+        Ok(count)
+    }
+
+    /// run the commands until they can no longer make progress, updating the app with their msgs,
+    /// then send the patches of the view if the app is updated
+    fn dispatch_pending_msgs(&mut self) -> io::Result<()> {
+        let mut updated = false;
+        loop {
+            self.pool.run_until_stalled();
+            let msgs: Vec<APP::MSG> = self.pending_msgs.borrow_mut().drain(..).collect();
+            if msgs.is_empty() {
+                break;
+            }
+            for msg in msgs {
+                let cmd = self.app.update(msg);
+                self.execute(cmd);
+            }
+            updated = true;
+        }
+        if updated {
+            self.update_view()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// spawn the commands into the executor, their resulting msgs are queued
+    fn execute(&mut self, cmd: Cmd<APP::MSG>) {
+        for mut command in cmd.commands {
+            if let Command::Sub(_) = command {
+                log::warn!("subscriptions are not supported in the server program");
+                continue;
+            }
+            let pending_msgs = Rc::clone(&self.pending_msgs);
+            self.pool
+                .spawner()
+                .spawn_local(async move {
+                    if let Some(msg) = command.next().await {
+                        pending_msgs.borrow_mut().push_back(msg);
+                    }
+                })
+                .expect("must spawn the command");
+        }
+    }
+
+    fn update_view(&mut self) -> io::Result<()> {
+        let new_vdom = self.app.view();
+        let patches: Vec<WirePatch> = diff(&self.current_vdom, &new_vdom)
+            .iter()
+            .map(|patch| patch.to_wire(&mut self.registry))
+            .collect();
+        self.current_vdom = new_vdom;
+        if patches.is_empty() {
+            return Ok(());
+        }
+        self.track_client_view(&patches)?;
+        self.send(&ServerMessage::Patches(patches))
+    }
+
+    /// apply the patches to the client view, releasing the listeners which the patches remove from it
+    fn track_client_view(&mut self, patches: &[WirePatch]) -> io::Result<()> {
+        let Some(client_view) = self.client_view.as_mut() else {
+            return Ok(());
+        };
+        let patches: Vec<OwnedPatch<HandlerId>> = patches
+            .iter()
+            .map(|patch| patch.clone().into_patch(&handler_listener))
+            .collect::<Result<_, _>>()
+            .map_err(invalid_data)?;
+        let patches: Vec<Patch<HandlerId>> = patches.iter().map(|patch| patch.as_patch()).collect();
+        // the patch paths point to the nodes of the client view before the patches are applied
+        let mut removed = Handlers::default();
+        for patch in patches.iter() {
+            removed_handlers(client_view, patch, &mut removed);
+        }
+        client_view.apply_patches(&patches).map_err(invalid_data)?;
+        for id in removed.0 {
+            self.registry.release(id);
+        }
+        Ok(())
+    }
+
+    fn send(&mut self, message: &ServerMessage) -> io::Result<()> {
+        self.transport.send(encode(message)?)
+    }
+}
@@ -0,0 +1,131 @@
+use super::{decode, encode, invalid_data, ClientMessage, ServerMessage, Transport};
+use crate::dom::events::RemoteEvent;
+use crate::dom::{Application, Cmd, Event, MountProcedure, Program};
+use crate::vdom::wire::{HandlerId, OwnedPatch};
+use crate::vdom::{EventCallback, Node, Patch};
+use std::cell::RefCell;
+use std::io;
+use std::rc::Rc;
+use wasm_bindgen::JsValue;
+
+/// The app of the client, its view is only the mounted view from the server
+/// and every msg is an event which is sent back to the server.
+struct RemoteView<T> {
+    view: Node<ClientMessage>,
+    transport: Rc<RefCell<T>>,
+}
+
+impl<T> Application for RemoteView<T>
+where
+    T: Transport + 'static,
+{
+    type MSG = ClientMessage;
+
+    fn update(&mut self, msg: ClientMessage) -> Cmd<ClientMessage> {
+        let sent = encode(&msg).and_then(|bytes| self.transport.borrow_mut().send(bytes));
+        if let Err(err) = sent {
+            log::error!("unable to send the event to the server: {err}");
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<ClientMessage> {
+        self.view.clone()
+    }
+}
+
+/// the DOM error of a patch which can not be applied, such as when the client is out of sync
+fn js_error(err: JsValue) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("unable to patch the view: {err:?}"),
+    )
+}
+
+/// the listener which sends the event to the server with its handler id
+fn remote_listener(handler: HandlerId) -> EventCallback<ClientMessage> {
+    EventCallback::from(move |event: Event| ClientMessage {
+        handler,
+        event: match event {
+            Event::WebEvent(web_event) => RemoteEvent::from(&web_event),
+            Event::RemoteEvent(remote_event) => remote_event,
+            Event::MountEvent(_) => RemoteEvent::new("mount"),
+        },
+    })
+}
+
+/// A thin client for the [`ServerProgram`](crate::dom::ServerProgram), which mounts the view
+/// sent by the server and applies the patches that follow.
+/// The triggered events are sent back to the server through the `transport`.
+pub struct RemoteClient<T>
+where
+    T: Transport + 'static,
+{
+    mount_node: web_sys::Node,
+    transport: Rc<RefCell<T>>,
+    program: Option<Program<RemoteView<T>>>,
+}
+
+impl<T> RemoteClient<T>
+where
+    T: Transport + 'static,
+{
+    /// create a client, where the view from the server will be appended into the `mount_node`
+    pub fn new(mount_node: &web_sys::Node, transport: T) -> Self {
+        Self {
+            mount_node: mount_node.clone(),
+            transport: Rc::new(RefCell::new(transport)),
+            program: None,
+        }
+    }
+
+    /// apply an encoded message from the server
+    pub fn receive(&mut self, message: &[u8]) -> io::Result<()> {
+        match decode(message)? {
+            ServerMessage::Mount(view) => {
+                let app = RemoteView {
+                    view: view.into_node(&remote_listener).map_err(invalid_data)?,
+                    transport: Rc::clone(&self.transport),
+                };
+                let mut program = Program::new(app);
+                program.mount(&self.mount_node, MountProcedure::append());
+                self.program = Some(program);
+            }
+            ServerMessage::Patches(patches) => {
+                let program = self.program.as_mut().ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidData, "patches received before mount")
+                })?;
+                let patches: Vec<OwnedPatch<ClientMessage>> = patches
+                    .into_iter()
+                    .map(|patch| patch.into_patch(&remote_listener))
+                    .collect::<Result<_, _>>()
+                    .map_err(invalid_data)?;
+                let patches: Vec<Patch<ClientMessage>> =
+                    patches.iter().map(|patch| patch.as_patch()).collect();
+                let root_node = program.root_node.borrow().clone().ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidData, "the view is not mounted")
+                })?;
+                let dom_patches = program
+                    .convert_patches(&root_node, &patches)
+                    .map_err(js_error)?;
+                program.queue_dom_patches(dom_patches).map_err(js_error)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// apply all the messages that are received from the server so far,
+    /// returns the number of messages applied
+    pub fn process_incoming(&mut self) -> io::Result<usize> {
+        let mut count = 0;
+        loop {
+            let message = self.transport.borrow_mut().try_recv()?;
+            let Some(message) = message else {
+                break;
+            };
+            self.receive(&message)?;
+            count += 1;
+        }
+        Ok(count)
+    }
+}
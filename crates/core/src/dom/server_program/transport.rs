@@ -0,0 +1,59 @@
+use std::io;
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+
+/// The connection between the [`ServerProgram`](crate::dom::ServerProgram) and its client,
+/// which carries the encoded messages in each direction.
+pub trait Transport {
+    /// send an encoded message to the other side
+    fn send(&mut self, message: Vec<u8>) -> io::Result<()>;
+
+    /// receive the next encoded message from the other side without blocking,
+    /// returns `None` if there is no message yet
+    fn try_recv(&mut self) -> io::Result<Option<Vec<u8>>>;
+}
+
+/// An in-memory transport, where the messages are passed through a channel.
+///
+/// This is used for running the server and the client in the same process, such as in tests.
+#[derive(Debug)]
+pub struct ChannelTransport {
+    sender: Sender<Vec<u8>>,
+    receiver: Receiver<Vec<u8>>,
+}
+
+impl ChannelTransport {
+    /// create 2 transports which are connected to each other
+    pub fn pair() -> (Self, Self) {
+        let (a_sender, b_receiver) = mpsc::channel();
+        let (b_sender, a_receiver) = mpsc::channel();
+        (
+            Self {
+                sender: a_sender,
+                receiver: a_receiver,
+            },
+            Self {
+                sender: b_sender,
+                receiver: b_receiver,
+            },
+        )
+    }
+}
+
+impl Transport for ChannelTransport {
+    fn send(&mut self, message: Vec<u8>) -> io::Result<()> {
+        self.sender
+            .send(message)
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "the other side is dropped"))
+    }
+
+    fn try_recv(&mut self) -> io::Result<Option<Vec<u8>>> {
+        match self.receiver.try_recv() {
+            Ok(message) => Ok(Some(message)),
+            Err(TryRecvError::Empty) => Ok(None),
+            Err(TryRecvError::Disconnected) => Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "the other side is dropped",
+            )),
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! Run an [`Application`] natively in tests, without a browser.
+//!
+//! The event listeners in the view are triggered with a [`RemoteEvent`] as the synthetic event,
+//! and the `Cmd` from `init` and `update` are run on a local executor.
+use crate::dom::cmd::Command;
+use crate::dom::events::{accepts_remote_event, RemoteEvent};
+use crate::dom::{Application, Cmd, Event};
+use crate::vdom::{find_by_path, Node, SelectorError, TreePath};
+use futures::executor::LocalPool;
+use futures::task::LocalSpawnExt;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+/// An error while triggering an event in a [`TestApp`]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum TestAppError {
+    /// the selector is not valid
+    #[error("invalid selector: {0}")]
+    Selector(#[from] SelectorError),
+    /// there is no element matching the selector
+    #[error("no element matches the selector: {0:?}")]
+    NotFound(String),
+    /// there is no element at this path
+    #[error("no element at path: {0:?}")]
+    NoElement(TreePath),
+    /// the element has no event listener for this event
+    #[error("no {event:?} event listener in the element at path: {path:?}")]
+    NoListener {
+        /// the name of the event
+        event: String,
+        /// the path of the element
+        path: TreePath,
+    },
+    /// the event listener requires a browser event, such as the typed listener `on_click`
+    #[error(
+        "the {event:?} event listener in the element at path {path:?} requires a browser event"
+    )]
+    UnsupportedListener {
+        /// the name of the event
+        event: String,
+        /// the path of the element
+        path: TreePath,
+    },
+}
+
+/// Runs the [`Application`] natively, keeping its current view.
+///
+/// Note: the event listeners are called with [`Event::RemoteEvent`], so the typed event listeners such
+/// as `on_click` can not be triggered and return [`TestAppError::UnsupportedListener`], use `on` instead.
+/// `on_input` and `on_change` are supported, their [`InputEvent`](crate::dom::events::InputEvent)
+/// has the value of the remote event.
+/// Subscriptions of browser events are not supported and are dropped.
+/// # Examples
+/// ```rust
+/// use sauron::{dom::TestApp, html::events::on, *};
+///
+/// struct Counter(i32);
+///
+/// impl Application for Counter {
+///     type MSG = i32;
+///
+///     fn update(&mut self, n: i32) -> Cmd<i32> {
+///         self.0 += n;
+///         Cmd::none()
+///     }
+///
+///     fn view(&self) -> Node<i32> {
+///         div([], [button([id("inc"), on("click", |_| 1)], []), p([], [text(self.0)])])
+///     }
+/// }
+///
+/// let mut test_app = TestApp::new(Counter(0));
+/// test_app.click("#inc").unwrap();
+/// assert!(test_app.render().contains("<p>1</p>"));
+/// ```
+pub struct TestApp<APP>
+where
+    APP: Application,
+{
+    app: APP,
+    current_vdom: Node<APP::MSG>,
+    pool: LocalPool,
+    /// the msgs from the triggered events and the completed commands, which are yet to be updated
+    pending_msgs: Rc<RefCell<VecDeque<APP::MSG>>>,
+}
+
+impl<APP> TestApp<APP>
+where
+    APP: Application,
+    APP::MSG: 'static,
+{
+    /// create the view of the app, then execute its `init`
+    pub fn new(mut app: APP) -> Self {
+        let current_vdom = app.view();
+        let cmd = app.init();
+        let mut test_app = Self {
+            app,
+            current_vdom,
+            pool: LocalPool::new(),
+            pending_msgs: Rc::new(RefCell::new(VecDeque::new())),
+        };
+        test_app.execute(cmd);
+        test_app.settle();
+        test_app
+    }
+
+    /// return a reference to the app
+    pub fn app(&self) -> &APP {
+        &self.app
+    }
+
+    /// the view of the app after the last update
+    pub fn view(&self) -> &Node<APP::MSG> {
+        &self.current_vdom
+    }
+
+    /// render the current view into html
+    pub fn render(&self) -> String {
+        self.current_vdom.render_to_string()
+    }
+
+    /// update the app with the msg, then run the resulting commands
+    pub fn dispatch(&mut self, msg: APP::MSG) {
+        self.dispatch_multiple([msg])
+    }
+
+    /// update the app with the msgs, then run the resulting commands
+    pub fn dispatch_multiple(&mut self, msgs: impl IntoIterator<Item = APP::MSG>) {
+        self.pending_msgs.borrow_mut().extend(msgs);
+        self.settle();
+    }
+
+    /// trigger the event listeners of the first element matching the `selector`
+    pub fn trigger(&mut self, selector: &str, event: RemoteEvent) -> Result<(), TestAppError> {
+        let (_, path) = self
+            .current_vdom
+            .query_selector(selector)?
+            .ok_or_else(|| TestAppError::NotFound(selector.to_string()))?;
+        self.trigger_at(&path, event)
+    }
+
+    /// trigger the event listeners of the element at the `path`
+    pub fn trigger_at(&mut self, path: &TreePath, event: RemoteEvent) -> Result<(), TestAppError> {
+        let Some(Node::Element(element)) = find_by_path(&self.current_vdom, path) else {
+            return Err(TestAppError::NoElement(path.clone()));
+        };
+        let listeners: Vec<_> = element
+            .attributes()
+            .iter()
+            .filter(|attr| attr.name == event.name)
+            .flat_map(|attr| attr.value.iter())
+            .filter_map(|value| value.as_event_listener())
+            .collect();
+        if !listeners
+            .iter()
+            .all(|listener| accepts_remote_event(listener))
+        {
+            return Err(TestAppError::UnsupportedListener {
+                event: event.name,
+                path: path.clone(),
+            });
+        }
+        let msgs: Vec<_> = listeners
+            .into_iter()
+            .map(|listener| listener.emit(Event::RemoteEvent(event.clone())))
+            .collect();
+        if msgs.is_empty() {
+            return Err(TestAppError::NoListener {
+                event: event.name,
+                path: path.clone(),
+            });
+        }
+        self.dispatch_multiple(msgs);
+        Ok(())
+    }
+
+    /// trigger the click event listeners of the first element matching the `selector`
+    pub fn click(&mut self, selector: &str) -> Result<(), TestAppError> {
+        self.trigger(selector, RemoteEvent::new("click"))
+    }
+
+    /// trigger the input event listeners of the first element matching the `selector`,
+    /// where the `value` is the value of the input element
+    pub fn input(&mut self, selector: &str, value: impl Into<String>) -> Result<(), TestAppError> {
+        self.trigger(selector, RemoteEvent::new("input").with_value(value))
+    }
+
+    /// spawn the commands into the executor, their resulting msgs are queued
+    fn execute(&mut self, cmd: Cmd<APP::MSG>) {
+        for mut command in cmd.commands {
+            if let Command::Sub(_) = command {
+                log::warn!("subscriptions are not supported in the test app");
+                continue;
+            }
+            let pending_msgs = Rc::clone(&self.pending_msgs);
+            self.pool
+                .spawner()
+                .spawn_local(async move {
+                    if let Some(msg) = command.next().await {
+                        pending_msgs.borrow_mut().push_back(msg);
+                    }
+                })
+                .expect("must spawn the command");
+        }
+    }
+
+    /// run the commands until they can no longer make progress, updating the app with their msgs.
+    /// The commands which are waiting for something outside of the app are left pending.
+    pub fn settle(&mut self) {
+        let mut updated = false;
+        loop {
+            self.pool.run_until_stalled();
+            let msgs: Vec<APP::MSG> = self.pending_msgs.borrow_mut().drain(..).collect();
+            if msgs.is_empty() {
+                break;
+            }
+            for msg in msgs {
+                let cmd = self.app.update(msg);
+                self.execute(cmd);
+            }
+            updated = true;
+        }
+        if updated {
+            self.current_vdom = self.app.view();
+        }
+    }
+}
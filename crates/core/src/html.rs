@@ -58,6 +58,48 @@ where
     }
 }
 
+/// A node where the view is built by calling `view_fn` with the `args`, only when it is needed.
+/// When the `args` is equal to the args of the same lazy node in the previous view,
+/// the previous view is reused and `view_fn` is not called, skipping the diff of this node entirely.
+///
+/// Note: the key, skip and replace attributes of the view are not used in diffing the lazy node,
+/// use [`lazy_keyed`] for lazy nodes which are keyed siblings.
+/// # Examples
+/// ```rust
+/// use sauron::*;
+///
+/// let row = |(id, label): &(usize, String)| tr([], [td([], [text(id)]), td([], [text(label)])]);
+/// let old: Node<()> = lazy((1, "one".to_string()), row);
+/// let new: Node<()> = lazy((1, "one".to_string()), row);
+/// assert!(diff(&old, &new).is_empty());
+/// ```
+pub fn lazy<ARGS, F, MSG>(args: ARGS, view_fn: F) -> Node<MSG>
+where
+    ARGS: PartialEq + 'static,
+    F: Fn(&ARGS) -> Node<MSG> + 'static,
+{
+    Node::Leaf(Leaf::Lazy(vdom::Lazy::new(args, view_fn)))
+}
+
+/// A lazy node with a `key`, so it is matched with the lazy node of the same key in the previous view
+/// when its siblings are inserted, removed or reordered, instead of the lazy node at the same index.
+/// # Examples
+/// ```rust
+/// use sauron::*;
+///
+/// let row = |label: &&str| li([], [text(label)]);
+/// let old: Node<()> = ul([], [lazy_keyed(2, "two", row)]);
+/// let new: Node<()> = ul([], [lazy_keyed(1, "one", row), lazy_keyed(2, "two", row)]);
+/// assert_eq!(diff(&old, &new).len(), 1);
+/// ```
+pub fn lazy_keyed<ARGS, F, MSG>(key: impl Into<vdom::Value>, args: ARGS, view_fn: F) -> Node<MSG>
+where
+    ARGS: PartialEq + 'static,
+    F: Fn(&ARGS) -> Node<MSG> + 'static,
+{
+    Node::Leaf(Leaf::Lazy(vdom::Lazy::new(args, view_fn).with_key(key)))
+}
+
 /// Creates an html element with the element tag name and namespace
 /// This is specifically used for creating svg element where a namespace is needed, otherwise the
 /// browser will not render it correctly.
@@ -148,7 +190,7 @@ pub fn doctype<MSG>(s: impl Into<Cow<'static, str>>) -> Node<MSG> {
 
 /// create a node which contains a list of nodes
 pub fn node_list<MSG>(nodes: impl IntoIterator<Item = Node<MSG>>) -> Node<MSG> {
-    Node::Leaf(Leaf::NodeList(nodes.into_iter().collect()))
+    vdom::node_list(nodes)
 }
 
 /// Create html entities such as `&nbsp;` `&gt`
@@ -169,3 +211,33 @@ fn escape_html_text(s: &str) -> String {
         })
         .collect()
 }
+
+/// The id of the script element which contains the app state embedded with [`embed_state`]
+pub const EMBEDDED_STATE_ID: &str = "sauron-embedded-state";
+
+/// Embed the json serialized `state` of the app in a `<script type="application/json">` element,
+/// so the client can start with the same state using `Program::from_embedded_state`,
+/// instead of fetching the initial data again.
+/// # Example
+/// ```rust
+/// use sauron::{*, html::embed_state};
+///
+/// let node: Node<()> = embed_state(&vec!["</script>"]).expect("must serialize");
+/// assert_eq!(
+///     node.render_to_string(),
+///     r#"<script type="application/json" id="sauron-embedded-state">["\u003c/script>"]</script>"#
+/// );
+/// ```
+#[cfg(feature = "with-serde")]
+pub fn embed_state<MSG>(state: &impl serde::Serialize) -> Result<Node<MSG>, serde_json::Error> {
+    // `<` only appears inside json strings, where it can be written as a unicode escape,
+    // so the json can not close the script element or open a comment.
+    let json = serde_json::to_string(state)?.replace('<', "\\u003c");
+    Ok(script(
+        [
+            attributes::r#type("application/json"),
+            attributes::id(EMBEDDED_STATE_ID),
+        ],
+        [text(json)],
+    ))
+}
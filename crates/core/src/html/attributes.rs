@@ -10,9 +10,11 @@ pub use crate::vdom::EventCallback;
 pub use crate::vdom::Style;
 pub use crate::vdom::{key, replace, skip, skip_criteria};
 pub use crate::{dom::Event, vdom::Attribute};
+pub use aria::*;
 pub use attribute_macros::commons::*;
 pub use attribute_macros::*;
 
+pub mod aria;
 #[macro_use]
 mod attribute_macros;
 
@@ -0,0 +1,365 @@
+//! Create typed [ARIA][0] attributes and roles
+//!
+//! # Examples
+//! ```rust
+//! use sauron::{html::attributes::aria::*, *};
+//!
+//! let view: Node<()> = div(
+//!     [role(Role::Dialog), aria_modal(true), aria_labelledby(["title"])],
+//!     [h1([id("title")], [text("Settings")])],
+//! );
+//! assert_eq!(
+//!     view.render_to_string(),
+//!     r#"<div role="dialog" aria-modal="true" aria-labelledby="title"><h1 id="title">Settings</h1></div>"#
+//! );
+//! ```
+//!
+//! [0]: https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA
+use crate::vdom::{attr, Attribute, Value};
+use std::borrow::Cow;
+use std::fmt;
+
+/// declare an enum of the tokens which an aria attribute accepts
+macro_rules! declare_tokens {
+    (
+        $(#[$attr:meta])*
+        $name:ident {
+            $($variant:ident => $token:literal,)*
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $(
+                #[doc = concat!("`", $token, "`")]
+                $variant,
+            )*
+        }
+
+        impl $name {
+            /// the attribute value of this token
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $token,)*
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}", self.as_str())
+            }
+        }
+
+        impl From<$name> for Value {
+            fn from(token: $name) -> Self {
+                Value::from(token.as_str())
+            }
+        }
+    };
+}
+
+/// declare the function of each aria attribute, at the same time this also
+/// fills up the ARIA_ATTRS const with the function and attribute names
+///
+/// The value of an attribute is either a type `(T)`, or a list `[T]` which is joined with spaces.
+macro_rules! declare_aria_attributes {
+    ( $(
+         $(#[$attr:meta])*
+         $name:ident : $attribute:literal => $kind:tt;
+       )*
+     ) => {
+        $(
+            declare_aria_attributes!(@builder $(#[$attr])* $name, $attribute, $kind);
+         )*
+
+        #[cfg(feature = "with-lookup")]
+        /// The aria attributes and `role`, as pairs of the function name and the attribute name
+        pub const ARIA_ATTRS: &[(&'static str, &'static str)] = &[$((stringify!($name), $attribute),)*];
+    };
+    (@builder $(#[$attr:meta])* $name:ident, $attribute:literal, ($ty:ty)) => {
+        doc_comment!{
+            concat!("Creates the [`", $attribute, "`](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/", $attribute, ") attribute"),
+            $(#[$attr])*
+            #[inline]
+            pub fn $name<V, MSG>(v: V) -> Attribute<MSG>
+                where V: Into<$ty>,
+                {
+                    attr($attribute, Value::from(v.into()))
+            }
+        }
+    };
+    (@builder $(#[$attr:meta])* $name:ident, $attribute:literal, [$ty:ty]) => {
+        doc_comment!{
+            concat!("Creates the [`", $attribute, "`](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/", $attribute, ") attribute from a list of values"),
+            $(#[$attr])*
+            #[inline]
+            pub fn $name<V, MSG>(values: impl IntoIterator<Item = V>) -> Attribute<MSG>
+                where V: Into<$ty>,
+                {
+                    let values: Vec<String> = values.into_iter().map(|v| v.into().to_string()).collect();
+                    attr($attribute, Value::from(values.join(" ")))
+            }
+        }
+    };
+}
+
+declare_tokens! {
+    /// The [roles](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Roles) of the elements
+    Role {
+        Alert => "alert",
+        AlertDialog => "alertdialog",
+        Application => "application",
+        Article => "article",
+        Banner => "banner",
+        Button => "button",
+        Cell => "cell",
+        Checkbox => "checkbox",
+        ColumnHeader => "columnheader",
+        Combobox => "combobox",
+        Complementary => "complementary",
+        ContentInfo => "contentinfo",
+        Definition => "definition",
+        Dialog => "dialog",
+        Document => "document",
+        Feed => "feed",
+        Figure => "figure",
+        Form => "form",
+        Grid => "grid",
+        GridCell => "gridcell",
+        Group => "group",
+        Heading => "heading",
+        Img => "img",
+        Link => "link",
+        List => "list",
+        Listbox => "listbox",
+        ListItem => "listitem",
+        Log => "log",
+        Main => "main",
+        Marquee => "marquee",
+        Math => "math",
+        Menu => "menu",
+        Menubar => "menubar",
+        MenuItem => "menuitem",
+        MenuItemCheckbox => "menuitemcheckbox",
+        MenuItemRadio => "menuitemradio",
+        Meter => "meter",
+        Navigation => "navigation",
+        None => "none",
+        Note => "note",
+        Option => "option",
+        Presentation => "presentation",
+        ProgressBar => "progressbar",
+        Radio => "radio",
+        RadioGroup => "radiogroup",
+        Region => "region",
+        Row => "row",
+        RowGroup => "rowgroup",
+        RowHeader => "rowheader",
+        Scrollbar => "scrollbar",
+        Search => "search",
+        Searchbox => "searchbox",
+        Separator => "separator",
+        Slider => "slider",
+        SpinButton => "spinbutton",
+        Status => "status",
+        Switch => "switch",
+        Tab => "tab",
+        Table => "table",
+        TabList => "tablist",
+        TabPanel => "tabpanel",
+        Term => "term",
+        Textbox => "textbox",
+        Timer => "timer",
+        Toolbar => "toolbar",
+        Tooltip => "tooltip",
+        Tree => "tree",
+        TreeGrid => "treegrid",
+        TreeItem => "treeitem",
+    }
+}
+
+declare_tokens! {
+    /// A state which can also be mixed, used in `aria-checked` and `aria-pressed`
+    Tristate {
+        True => "true",
+        False => "false",
+        Mixed => "mixed",
+    }
+}
+
+impl From<bool> for Tristate {
+    fn from(v: bool) -> Self {
+        if v {
+            Self::True
+        } else {
+            Self::False
+        }
+    }
+}
+
+declare_tokens! {
+    /// How urgent the updates of a live region are announced, used in `aria-live`
+    Politeness {
+        Off => "off",
+        Polite => "polite",
+        Assertive => "assertive",
+    }
+}
+
+declare_tokens! {
+    /// The kind of the current item in a set, used in `aria-current`
+    Current {
+        Page => "page",
+        Step => "step",
+        Location => "location",
+        Date => "date",
+        Time => "time",
+        True => "true",
+        False => "false",
+    }
+}
+
+impl From<bool> for Current {
+    fn from(v: bool) -> Self {
+        if v {
+            Self::True
+        } else {
+            Self::False
+        }
+    }
+}
+
+declare_tokens! {
+    /// The kind of popup an element opens, used in `aria-haspopup`
+    HasPopup {
+        False => "false",
+        True => "true",
+        Menu => "menu",
+        Listbox => "listbox",
+        Tree => "tree",
+        Grid => "grid",
+        Dialog => "dialog",
+    }
+}
+
+impl From<bool> for HasPopup {
+    fn from(v: bool) -> Self {
+        if v {
+            Self::True
+        } else {
+            Self::False
+        }
+    }
+}
+
+declare_tokens! {
+    /// Why the value of an element is invalid, used in `aria-invalid`
+    Invalid {
+        False => "false",
+        True => "true",
+        Grammar => "grammar",
+        Spelling => "spelling",
+    }
+}
+
+impl From<bool> for Invalid {
+    fn from(v: bool) -> Self {
+        if v {
+            Self::True
+        } else {
+            Self::False
+        }
+    }
+}
+
+declare_tokens! {
+    /// The order a column is sorted in, used in `aria-sort`
+    Sort {
+        None => "none",
+        Ascending => "ascending",
+        Descending => "descending",
+        Other => "other",
+    }
+}
+
+declare_tokens! {
+    /// The orientation of an element, used in `aria-orientation`
+    Orientation {
+        Horizontal => "horizontal",
+        Vertical => "vertical",
+        Undefined => "undefined",
+    }
+}
+
+declare_tokens! {
+    /// How the suggestions of an input are shown, used in `aria-autocomplete`
+    AutoComplete {
+        None => "none",
+        Inline => "inline",
+        List => "list",
+        Both => "both",
+    }
+}
+
+declare_tokens! {
+    /// The changes of a live region which are announced, used in `aria-relevant`
+    Relevant {
+        Additions => "additions",
+        Removals => "removals",
+        Text => "text",
+        All => "all",
+    }
+}
+
+declare_aria_attributes! {
+    /// the role of the element
+    role : "role" => (Role);
+    aria_activedescendant : "aria-activedescendant" => (Cow<'static, str>);
+    aria_atomic : "aria-atomic" => (bool);
+    aria_autocomplete : "aria-autocomplete" => (AutoComplete);
+    aria_busy : "aria-busy" => (bool);
+    aria_checked : "aria-checked" => (Tristate);
+    aria_colcount : "aria-colcount" => (i32);
+    aria_colindex : "aria-colindex" => (i32);
+    aria_colspan : "aria-colspan" => (i32);
+    aria_controls : "aria-controls" => [Cow<'static, str>];
+    aria_current : "aria-current" => (Current);
+    aria_describedby : "aria-describedby" => [Cow<'static, str>];
+    aria_description : "aria-description" => (Cow<'static, str>);
+    aria_details : "aria-details" => (Cow<'static, str>);
+    aria_disabled : "aria-disabled" => (bool);
+    aria_errormessage : "aria-errormessage" => (Cow<'static, str>);
+    aria_expanded : "aria-expanded" => (bool);
+    aria_flowto : "aria-flowto" => [Cow<'static, str>];
+    aria_haspopup : "aria-haspopup" => (HasPopup);
+    aria_hidden : "aria-hidden" => (bool);
+    aria_invalid : "aria-invalid" => (Invalid);
+    aria_keyshortcuts : "aria-keyshortcuts" => (Cow<'static, str>);
+    aria_label : "aria-label" => (Cow<'static, str>);
+    aria_labelledby : "aria-labelledby" => [Cow<'static, str>];
+    aria_level : "aria-level" => (i32);
+    aria_live : "aria-live" => (Politeness);
+    aria_modal : "aria-modal" => (bool);
+    aria_multiline : "aria-multiline" => (bool);
+    aria_multiselectable : "aria-multiselectable" => (bool);
+    aria_orientation : "aria-orientation" => (Orientation);
+    aria_owns : "aria-owns" => [Cow<'static, str>];
+    aria_placeholder : "aria-placeholder" => (Cow<'static, str>);
+    aria_posinset : "aria-posinset" => (i32);
+    aria_pressed : "aria-pressed" => (Tristate);
+    aria_readonly : "aria-readonly" => (bool);
+    aria_relevant : "aria-relevant" => [Relevant];
+    aria_required : "aria-required" => (bool);
+    aria_roledescription : "aria-roledescription" => (Cow<'static, str>);
+    aria_rowcount : "aria-rowcount" => (i32);
+    aria_rowindex : "aria-rowindex" => (i32);
+    aria_rowspan : "aria-rowspan" => (i32);
+    aria_selected : "aria-selected" => (bool);
+    aria_setsize : "aria-setsize" => (i32);
+    aria_sort : "aria-sort" => (Sort);
+    aria_valuemax : "aria-valuemax" => (f64);
+    aria_valuemin : "aria-valuemin" => (f64);
+    aria_valuenow : "aria-valuenow" => (f64);
+    aria_valuetext : "aria-valuetext" => (Cow<'static, str>);
+}
@@ -1,7 +1,7 @@
 //! Provides list of HTML and SVG tags, style properties
 use crate::{
     html::{
-        attributes::{HTML_ATTRS, HTML_ATTRS_SPECIAL},
+        attributes::{ARIA_ATTRS, HTML_ATTRS, HTML_ATTRS_SPECIAL},
         tags::{
             commons::HTML_TAGS, self_closing::HTML_SC_TAGS, HTML_TAGS_NON_COMMON,
             HTML_TAGS_WITH_MACRO_NON_COMMON,
@@ -41,6 +41,12 @@ static ALL_HTML_TAGS: Lazy<BTreeSet<&&'static str>> = Lazy::new(|| {
 static SELF_CLOSING_TAGS: Lazy<BTreeSet<&&'static str>> =
     Lazy::new(|| HTML_SC_TAGS.iter().collect());
 
+/// The html tags which can be focused and operated with the keyboard by default
+const INTERACTIVE_TAGS: [&str; 13] = [
+    "a", "area", "audio", "button", "details", "embed", "iframe", "input", "label", "option",
+    "select", "summary", "textarea",
+];
+
 static ALL_ATTRS: Lazy<BTreeMap<&'static str, &'static str>> = Lazy::new(|| {
     BTreeMap::from_iter(
         HTML_ATTRS
@@ -52,6 +58,7 @@ static ALL_ATTRS: Lazy<BTreeMap<&'static str, &'static str>> = Lazy::new(|| {
                     .iter()
                     .chain(SVG_ATTRS_SPECIAL.iter())
                     .chain(SVG_ATTRS_XLINK.iter())
+                    .chain(ARIA_ATTRS.iter())
                     .map(|(func, att)| (*func, *att)),
             ),
     )
@@ -111,3 +118,10 @@ pub fn match_tag(tag: &str) -> Option<&'static str> {
 pub fn is_self_closing(tag: &str) -> bool {
     SELF_CLOSING_TAGS.contains(&tag)
 }
+
+/// Returns true if this html tag is interactive, that is it can be focused
+/// and operated with the keyboard without any extra attributes
+#[inline]
+pub fn is_interactive(tag: &str) -> bool {
+    INTERACTIVE_TAGS.contains(&tag)
+}
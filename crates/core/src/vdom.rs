@@ -10,30 +10,55 @@ pub use attribute::Attribute;
 pub use attribute::Callback;
 pub use attribute::GroupedAttributeValues;
 pub use element::Element;
+pub use lazy::Lazy;
 pub use leaf::Leaf;
 pub use templated_view::TemplatedView;
 
+mod apply;
 mod attribute;
+mod compare;
 mod element;
+mod lazy;
 mod leaf;
 mod map_msg;
 mod render;
+mod selector;
 mod templated_view;
+mod validate;
 
+pub use apply::{apply_patches, ApplyPatchError};
 pub use attribute::special::{
     key, replace, skip, skip_criteria, KEY, REPLACE, SKIP, SKIP_CRITERIA,
 };
 #[cfg(feature = "ensure-attr-set")]
 pub(crate) use attribute::special::{CHECKED, DISABLED, OPEN, VALUE};
-pub use attribute::{attr, attr_ns, AttributeName, AttributeValue, Namespace, Style, Tag, Value};
+pub(crate) use attribute::special::{CLASS, STYLE};
+pub use attribute::{
+    attr, attr_ns, AttributeName, AttributeValue, ListenerOptions, Namespace, Style, Tag, Value,
+};
+pub use compare::{compare_nodes, AttributeMismatch, CompareOptions, NodeMismatch};
 pub use diff::{diff, diff_recursive};
 pub use node::{element, element_ns, fragment, leaf, node_list, Node};
-pub use patch::{Patch, PatchType, TreePath};
+pub use patch::{
+    explain_patches, optimize_patches, Patch, PatchExplanation, PatchReport, PatchType, TreePath,
+};
+pub(crate) use render::escape;
+pub use render::{FlushPolicy, RenderChunks};
+pub(crate) use selector::find_by_path;
+pub use selector::{Selector, SelectorError};
+pub use validate::{InvalidReason, ValidationError};
+pub use visit::{Fold, Visitor};
 
+#[cfg(feature = "with-lookup")]
+pub mod a11y;
 pub mod diff;
 mod diff_lis;
 mod node;
 pub mod patch;
+pub mod snapshot;
+pub mod visit;
+#[cfg(feature = "with-serde")]
+pub mod wire;
 
 /// Callback where Event type is supplied
 /// for Components
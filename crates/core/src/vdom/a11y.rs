@@ -0,0 +1,371 @@
+//! Find the common accessibility problems in a node tree, such as images without alt text,
+//! clickable elements which can not be reached with the keyboard and form controls without labels.
+//!
+//! A rule can be turned off for the whole tree with [`A11yChecker::disable`],
+//! or for a single element with the [`a11y_ignore`] attribute.
+use crate::html::lookup;
+use crate::vdom::visit::{walk_element, walk_leaf};
+use crate::vdom::{attr, Attribute, AttributeName, Element, Leaf, Node, Tag, TreePath, Visitor};
+use std::collections::HashSet;
+use std::fmt;
+
+/// The attribute which lists the rules which are not checked on the element
+pub static A11Y_IGNORE: &AttributeName = &"data-a11y-ignore";
+
+/// The input types which are not form controls that need a label
+const UNLABELED_INPUT_TYPES: [&str; 5] = ["hidden", "submit", "reset", "button", "image"];
+
+/// The keyboard events which can activate an element the same way as a click
+const KEY_EVENTS: [&str; 3] = ["keydown", "keyup", "keypress"];
+
+/// An accessibility rule checked on the elements
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rule {
+    /// an `<img>` or `<input type="image">` must have an `alt` attribute,
+    /// which is empty for images that are only decorative
+    ImgAlt,
+    /// a non-interactive element with a click listener must have a `role`
+    ClickRole,
+    /// a non-interactive element with a click listener must have a `tabindex`,
+    /// so it can be focused with the keyboard
+    ClickFocus,
+    /// a non-interactive element with a click listener must also listen to keyboard events
+    ClickKeyboard,
+    /// an `<input>`, `<select>` or `<textarea>` must have a label
+    InputLabel,
+    /// a `<button>` must have text content or an `aria-label`
+    ButtonName,
+    /// an `<a>` with an `href` must have text content or an `aria-label`
+    LinkName,
+}
+
+impl Rule {
+    /// all the rules
+    pub const ALL: [Rule; 7] = [
+        Rule::ImgAlt,
+        Rule::ClickRole,
+        Rule::ClickFocus,
+        Rule::ClickKeyboard,
+        Rule::InputLabel,
+        Rule::ButtonName,
+        Rule::LinkName,
+    ];
+
+    /// the name of the rule, which is used in the [`a11y_ignore`] attribute
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rule::ImgAlt => "img-alt",
+            Rule::ClickRole => "click-role",
+            Rule::ClickFocus => "click-focus",
+            Rule::ClickKeyboard => "click-keyboard",
+            Rule::InputLabel => "input-label",
+            Rule::ButtonName => "button-name",
+            Rule::LinkName => "link-name",
+        }
+    }
+
+    /// find the rule with this name
+    pub fn from_name(name: &str) -> Option<Rule> {
+        Rule::ALL.into_iter().find(|rule| rule.name() == name)
+    }
+
+    /// what is wrong with an element which violates this rule
+    pub fn description(&self) -> &'static str {
+        match self {
+            Rule::ImgAlt => "the image has no alt text",
+            Rule::ClickRole => "the clickable element has no role",
+            Rule::ClickFocus => "the clickable element can not be focused, it has no tabindex",
+            Rule::ClickKeyboard => "the clickable element has no keyboard event listener",
+            Rule::InputLabel => "the form control has no label",
+            Rule::ButtonName => "the button has no text",
+            Rule::LinkName => "the link has no text",
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// An element which violates an accessibility rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct A11yViolation {
+    /// the path of the element
+    pub path: TreePath,
+    /// the tag of the element
+    pub tag: Tag,
+    /// the rule which is violated
+    pub rule: Rule,
+}
+
+impl fmt::Display for A11yViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: <{}> at path: {:?}, {}",
+            self.rule,
+            self.tag,
+            self.path.path,
+            self.rule.description()
+        )
+    }
+}
+
+/// Skip checking the `rules` on this element, all the rules are skipped when `rules` is empty.
+///
+/// # Examples
+/// ```rust
+/// use sauron::{vdom::a11y::{a11y_ignore, A11yChecker, Rule}, *};
+///
+/// let view: Node<()> = div([], [img([a11y_ignore([Rule::ImgAlt])], [])]);
+/// assert_eq!(A11yChecker::new().check(&view), []);
+/// ```
+pub fn a11y_ignore<MSG>(rules: impl IntoIterator<Item = Rule>) -> Attribute<MSG> {
+    let names: Vec<&str> = rules.into_iter().map(|rule| rule.name()).collect();
+    attr(A11Y_IGNORE, names.join(" "))
+}
+
+/// Checks the elements of a node tree against the enabled accessibility rules
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct A11yChecker {
+    rules: Vec<Rule>,
+}
+
+impl Default for A11yChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl A11yChecker {
+    /// a checker with all the rules enabled
+    pub fn new() -> Self {
+        Self {
+            rules: Rule::ALL.to_vec(),
+        }
+    }
+
+    /// a checker with only the `rules` enabled
+    pub fn only(rules: impl IntoIterator<Item = Rule>) -> Self {
+        Self {
+            rules: rules.into_iter().collect(),
+        }
+    }
+
+    /// do not check this rule
+    pub fn disable(mut self, rule: Rule) -> Self {
+        self.rules.retain(|r| *r != rule);
+        self
+    }
+
+    /// check this rule too
+    pub fn enable(mut self, rule: Rule) -> Self {
+        if !self.is_enabled(rule) {
+            self.rules.push(rule);
+        }
+        self
+    }
+
+    /// returns true if the rule is checked
+    pub fn is_enabled(&self, rule: Rule) -> bool {
+        self.rules.contains(&rule)
+    }
+
+    /// Check the elements of the node tree, returning the violations in depth first order,
+    /// which is empty when no enabled rule is violated.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use sauron::{vdom::a11y::{A11yChecker, Rule}, html::attributes::*, *};
+    ///
+    /// let view: Node<()> = div([], [img([src("logo.png")], [])]);
+    /// let violations = A11yChecker::new().check(&view);
+    /// assert_eq!(violations[0].path, TreePath::new([0]));
+    /// assert_eq!(violations[0].rule, Rule::ImgAlt);
+    /// ```
+    pub fn check<MSG>(&self, node: &Node<MSG>) -> Vec<A11yViolation> {
+        let mut labels = LabelTargets::default();
+        if self.is_enabled(Rule::InputLabel) {
+            labels.visit_node(node, &TreePath::root());
+        }
+        let mut linter = Linter {
+            checker: self,
+            labelled_ids: labels.0,
+            in_label: 0,
+            violations: vec![],
+        };
+        linter.visit_node(node, &TreePath::root());
+        linter.violations
+    }
+}
+
+impl<MSG> Node<MSG> {
+    /// Check this node tree against all the accessibility rules, see [`A11yChecker::check`]
+    pub fn check_a11y(&self) -> Vec<A11yViolation> {
+        A11yChecker::new().check(self)
+    }
+}
+
+/// the simple values of the attribute, joined with a space
+fn attribute_string<MSG>(element: &Element<MSG>, name: &AttributeName) -> Option<String> {
+    let values = element.attribute_value(name)?;
+    let value = values
+        .iter()
+        .filter_map(|v| v.get_simple())
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+    Some(value)
+}
+
+fn has_attribute<MSG>(element: &Element<MSG>, name: &AttributeName) -> bool {
+    element.attributes().iter().any(|attr| attr.name() == name)
+}
+
+fn has_listener<MSG>(element: &Element<MSG>, event: &str) -> bool {
+    element
+        .attributes()
+        .iter()
+        .any(|attr| *attr.name() == event && attr.is_event_listener())
+}
+
+/// the element is named by its attributes rather than its content
+fn has_label_attribute<MSG>(element: &Element<MSG>) -> bool {
+    ["aria-label", "aria-labelledby", "title"]
+        .iter()
+        .any(|name| attribute_string(element, name).is_some_and(|v| !v.trim().is_empty()))
+}
+
+/// The ids of the form controls which are referred by the `for` of a `<label>`
+#[derive(Default)]
+struct LabelTargets(HashSet<String>);
+
+impl<MSG> Visitor<MSG> for LabelTargets {
+    fn visit_element(&mut self, element: &Element<MSG>, path: &TreePath) {
+        if *element.tag() == "label" {
+            if let Some(id) = attribute_string(element, &"for") {
+                self.0.insert(id);
+            }
+        }
+        walk_element(self, element, path);
+    }
+}
+
+/// Finds the text which names an element, either a non-blank text or an image with alt text
+#[derive(Default)]
+struct NameFinder {
+    found: bool,
+}
+
+impl NameFinder {
+    fn has_name<MSG>(element: &Element<MSG>) -> bool {
+        let mut finder = NameFinder::default();
+        walk_element(&mut finder, element, &TreePath::root());
+        finder.found
+    }
+}
+
+impl<MSG> Visitor<MSG> for NameFinder {
+    fn visit_element(&mut self, element: &Element<MSG>, path: &TreePath) {
+        let is_named_image = *element.tag() == "img"
+            && attribute_string(element, &"alt").is_some_and(|alt| !alt.trim().is_empty());
+        if is_named_image || has_label_attribute(element) {
+            self.found = true;
+        } else if !self.found {
+            walk_element(self, element, path);
+        }
+    }
+
+    fn visit_leaf(&mut self, leaf: &Leaf<MSG>, path: &TreePath) {
+        match leaf {
+            Leaf::Text(text) | Leaf::Symbol(text) if !text.trim().is_empty() => self.found = true,
+            _ if !self.found => walk_leaf(self, leaf, path),
+            _ => (),
+        }
+    }
+}
+
+struct Linter<'a> {
+    checker: &'a A11yChecker,
+    /// the ids of the form controls which have a `<label>` pointing to them
+    labelled_ids: HashSet<String>,
+    /// the number of `<label>` elements enclosing the current node
+    in_label: usize,
+    violations: Vec<A11yViolation>,
+}
+
+impl Linter<'_> {
+    fn check_element<MSG>(&self, element: &Element<MSG>) -> Vec<Rule> {
+        let tag = *element.tag();
+        let mut violated = vec![];
+        match tag {
+            "img" if !has_attribute(element, &"alt") => violated.push(Rule::ImgAlt),
+            "input" | "select" | "textarea" => {
+                let input_type = attribute_string(element, &"type").unwrap_or_default();
+                let input_type = input_type.to_lowercase();
+                if tag == "input" && input_type == "image" && !has_attribute(element, &"alt") {
+                    violated.push(Rule::ImgAlt);
+                }
+                let needs_label = tag != "input" || !UNLABELED_INPUT_TYPES.contains(&&*input_type);
+                let is_labelled = self.in_label > 0
+                    || has_label_attribute(element)
+                    || attribute_string(element, &"id")
+                        .is_some_and(|id| self.labelled_ids.contains(&id));
+                if needs_label && !is_labelled {
+                    violated.push(Rule::InputLabel);
+                }
+            }
+            "button" if !NameFinder::has_name(element) => violated.push(Rule::ButtonName),
+            "a" if has_attribute(element, &"href") && !NameFinder::has_name(element) => {
+                violated.push(Rule::LinkName)
+            }
+            _ => (),
+        }
+        // custom elements are not checked, since they may be made interactive by their own code
+        let is_known_tag = lookup::match_tag(tag).is_some();
+        if is_known_tag && !lookup::is_interactive(tag) && has_listener(element, "click") {
+            if !has_attribute(element, &"role") {
+                violated.push(Rule::ClickRole);
+            }
+            if !has_attribute(element, &"tabindex") {
+                violated.push(Rule::ClickFocus);
+            }
+            if !KEY_EVENTS.iter().any(|event| has_listener(element, event)) {
+                violated.push(Rule::ClickKeyboard);
+            }
+        }
+        violated
+    }
+}
+
+impl<MSG> Visitor<MSG> for Linter<'_> {
+    fn visit_element(&mut self, element: &Element<MSG>, path: &TreePath) {
+        // only the html elements are checked
+        if element.namespace().is_some() {
+            return walk_element(self, element, path);
+        }
+        let ignored = attribute_string(element, A11Y_IGNORE);
+        let is_ignored = |rule: &Rule| match &ignored {
+            Some(names) => {
+                names.trim().is_empty() || names.split_whitespace().any(|name| name == rule.name())
+            }
+            None => false,
+        };
+        let tag = *element.tag();
+        for rule in self.check_element(element) {
+            if self.checker.is_enabled(rule) && !is_ignored(&rule) {
+                self.violations.push(A11yViolation {
+                    path: path.clone(),
+                    tag,
+                    rule,
+                });
+            }
+        }
+        let is_label = tag == "label";
+        self.in_label += usize::from(is_label);
+        walk_element(self, element, path);
+        self.in_label -= usize::from(is_label);
+    }
+}
@@ -0,0 +1,509 @@
+//! Apply patches to a virtual node tree, the same way `DomPatch` applies them to the real DOM.
+//!
+//! All the nodes targeted by the patches are looked up first before any of the patches are applied,
+//! so the patch paths always point to the nodes of the original tree.
+use crate::dom::StatelessModel;
+use crate::vdom::diff::class_names;
+use crate::vdom::{
+    Attribute, AttributeValue, Element, Leaf, Node, Patch, PatchType, Style, TreePath, Value,
+    CLASS, STYLE,
+};
+use std::any::TypeId;
+use std::borrow::Cow;
+
+/// An error while applying patches to a virtual node tree
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ApplyPatchError {
+    /// there is no node at this path
+    #[error("no node found at path: {0:?}")]
+    NodeNotFound(TreePath),
+    /// the node at the patch path has a different tag than the patch
+    #[error("expecting a tag: {expected:?} at path: {path:?}, but found: {found:?}")]
+    TagMismatch {
+        /// the path of the patch
+        path: TreePath,
+        /// the tag of the patch
+        expected: String,
+        /// the tag of the node at the path
+        found: Option<String>,
+    },
+    /// the node at this path has no parent, so it can not have siblings inserted or be removed
+    #[error("the node at path: {0:?} has no parent")]
+    NoParent(TreePath),
+    /// the node at this path can not have children
+    #[error("the node at path: {0:?} can not have children")]
+    NotAContainer(TreePath),
+    /// the node at this path is not a text or comment node, so its content can not be set
+    #[error("the node at path: {0:?} is not a text or comment node")]
+    NotText(TreePath),
+}
+
+type Id = usize;
+
+/// a node in the arena, where the children are referred by their ids
+enum Slot<MSG> {
+    /// the element without its children
+    Element {
+        element: Element<MSG>,
+        children: Vec<Id>,
+    },
+    Fragment {
+        children: Vec<Id>,
+    },
+    NodeList {
+        children: Vec<Id>,
+    },
+    /// a stateless component is patched as its view
+    Stateless {
+        type_id: TypeId,
+        view: Id,
+    },
+    /// the other leaf nodes, which has no children
+    Leaf(Leaf<MSG>),
+}
+
+/// The nodes are stored in an arena, so the nodes can be referred by their id
+/// while they are moved around in the tree
+struct Arena<MSG> {
+    slots: Vec<Slot<MSG>>,
+    parents: Vec<Option<Id>>,
+    root: Id,
+}
+
+impl<MSG> Arena<MSG> {
+    fn from_node(node: Node<MSG>) -> Self {
+        let mut arena = Arena {
+            slots: vec![],
+            parents: vec![],
+            root: 0,
+        };
+        arena.root = arena.insert(node);
+        arena
+    }
+
+    /// add the node and its descendants into the arena, returns the id of the node
+    fn insert(&mut self, node: Node<MSG>) -> Id {
+        let slot = match node {
+            Node::Element(mut element) => {
+                let children = std::mem::take(&mut element.children);
+                Slot::Element {
+                    element,
+                    children: self.insert_children(children),
+                }
+            }
+            Node::Leaf(Leaf::Fragment(nodes)) => Slot::Fragment {
+                children: self.insert_children(nodes),
+            },
+            Node::Leaf(Leaf::NodeList(nodes)) => Slot::NodeList {
+                children: self.insert_children(nodes),
+            },
+            Node::Leaf(Leaf::StatelessComponent(comp)) => Slot::Stateless {
+                type_id: comp.type_id,
+                view: self.insert(*comp.view),
+            },
+            // the lazy node is patched as its view, the same as in the DOM
+            Node::Leaf(Leaf::Lazy(lazy)) => return self.insert(lazy.view().clone()),
+            Node::Leaf(leaf) => Slot::Leaf(leaf),
+        };
+        let id = self.slots.len();
+        self.slots.push(slot);
+        self.parents.push(None);
+        if let Some(children) = self.children(id) {
+            for child in children.clone() {
+                self.parents[child] = Some(id);
+            }
+        }
+        id
+    }
+
+    fn insert_children(&mut self, nodes: Vec<Node<MSG>>) -> Vec<Id> {
+        nodes.into_iter().map(|node| self.insert(node)).collect()
+    }
+
+    fn children(&self, id: Id) -> Option<&Vec<Id>> {
+        match &self.slots[id] {
+            Slot::Element { children, .. }
+            | Slot::Fragment { children }
+            | Slot::NodeList { children } => Some(children),
+            _ => None,
+        }
+    }
+
+    fn children_mut(&mut self, id: Id) -> Option<&mut Vec<Id>> {
+        match &mut self.slots[id] {
+            Slot::Element { children, .. }
+            | Slot::Fragment { children }
+            | Slot::NodeList { children } => Some(children),
+            _ => None,
+        }
+    }
+
+    /// the node which is materialized in place of this node, ie: the view of a stateless component
+    fn materialized(&self, mut id: Id) -> Id {
+        while let Slot::Stateless { view, .. } = &self.slots[id] {
+            id = *view;
+        }
+        id
+    }
+
+    fn find(&self, path: &TreePath) -> Option<Id> {
+        let mut id = self.root;
+        for idx in path.path.iter() {
+            id = *self.children(self.materialized(id))?.get(*idx)?;
+        }
+        Some(id)
+    }
+
+    fn container(&mut self, id: Id, path: &TreePath) -> Result<&mut Vec<Id>, ApplyPatchError> {
+        let materialized = self.materialized(id);
+        self.children_mut(materialized)
+            .ok_or_else(|| ApplyPatchError::NotAContainer(path.clone()))
+    }
+
+    fn element_mut(
+        &mut self,
+        id: Id,
+        path: &TreePath,
+    ) -> Result<&mut Element<MSG>, ApplyPatchError> {
+        let materialized = self.materialized(id);
+        match &mut self.slots[materialized] {
+            Slot::Element { element, .. } => Ok(element),
+            _ => Err(ApplyPatchError::NotAContainer(path.clone())),
+        }
+    }
+
+    fn check_tag(&self, id: Id, patch: &Patch<MSG>) -> Result<(), ApplyPatchError> {
+        let Some(expected) = patch.tag else {
+            return Ok(());
+        };
+        let found = match &self.slots[self.materialized(id)] {
+            Slot::Element { element, .. } => Some(element.tag),
+            _ => None,
+        };
+        match found {
+            Some(found) if found == *expected => Ok(()),
+            // only elements are checked, the same as in the DOM
+            None => Ok(()),
+            found => Err(ApplyPatchError::TagMismatch {
+                path: patch.patch_path.clone(),
+                expected: expected.to_string(),
+                found: found.map(str::to_string),
+            }),
+        }
+    }
+
+    /// remove the node from its parent, returns the index it was in
+    fn detach(&mut self, id: Id, path: &TreePath) -> Result<(Id, usize), ApplyPatchError> {
+        let parent = self.parents[id].ok_or_else(|| ApplyPatchError::NoParent(path.clone()))?;
+        let siblings = self
+            .children_mut(parent)
+            .expect("parent must have children");
+        let index = siblings
+            .iter()
+            .position(|sibling| *sibling == id)
+            .expect("must be in the parent");
+        siblings.remove(index);
+        self.parents[id] = None;
+        Ok((parent, index))
+    }
+
+    fn insert_at(&mut self, parent: Id, index: usize, ids: Vec<Id>) {
+        for id in ids.iter() {
+            self.parents[*id] = Some(parent);
+        }
+        let siblings = self
+            .children_mut(parent)
+            .expect("parent must have children");
+        siblings.splice(index..index, ids);
+    }
+
+    fn insert_beside(
+        &mut self,
+        target: Id,
+        ids: Vec<Id>,
+        after: bool,
+        path: &TreePath,
+    ) -> Result<(), ApplyPatchError> {
+        let parent = self.parents[target].ok_or_else(|| ApplyPatchError::NoParent(path.clone()))?;
+        let index = self
+            .children(parent)
+            .expect("parent must have children")
+            .iter()
+            .position(|sibling| *sibling == target)
+            .expect("must be in the parent");
+        self.insert_at(parent, if after { index + 1 } else { index }, ids);
+        Ok(())
+    }
+
+    fn append(&mut self, target: Id, ids: Vec<Id>, path: &TreePath) -> Result<(), ApplyPatchError> {
+        let parent = self.materialized(target);
+        let len = self.container(target, path)?.len();
+        self.insert_at(parent, len, ids);
+        Ok(())
+    }
+
+    fn insert_nodes<'a>(&mut self, nodes: impl IntoIterator<Item = &'a Node<MSG>>) -> Vec<Id>
+    where
+        MSG: 'a,
+    {
+        nodes
+            .into_iter()
+            .map(|node| self.insert(node.clone()))
+            .collect()
+    }
+
+    fn apply(
+        &mut self,
+        target: Id,
+        moving: Vec<Id>,
+        patch: &Patch<MSG>,
+    ) -> Result<(), ApplyPatchError> {
+        let path = &patch.patch_path;
+        match &patch.patch_type {
+            PatchType::InsertBeforeNode { nodes } => {
+                let ids = self.insert_nodes(nodes.iter().map(|node| node.as_ref()));
+                self.insert_beside(target, ids, false, path)?;
+            }
+            PatchType::InsertAfterNode { nodes } => {
+                let ids = self.insert_nodes(nodes.iter().copied());
+                self.insert_beside(target, ids, true, path)?;
+            }
+            PatchType::AppendChildren { children } => {
+                let ids = self.insert_nodes(children.iter().copied());
+                self.append(target, ids, path)?;
+            }
+            PatchType::ClearChildren => {
+                self.container(target, path)?.clear();
+            }
+            PatchType::RemoveNode => {
+                self.detach(target, path)?;
+            }
+            PatchType::MoveBeforeNode { .. } | PatchType::MoveAfterNode { .. } => {
+                for id in moving.iter() {
+                    self.detach(*id, path)?;
+                }
+                let after = matches!(patch.patch_type, PatchType::MoveAfterNode { .. });
+                self.insert_beside(target, moving, after, path)?;
+            }
+            PatchType::ReplaceNode { replacement } => {
+                let ids = self.insert_nodes(replacement.iter().copied());
+                if target == self.root {
+                    self.root = if ids.len() == 1 {
+                        ids[0]
+                    } else {
+                        let id = self.slots.len();
+                        self.slots.push(Slot::NodeList { children: vec![] });
+                        self.parents.push(None);
+                        self.insert_at(id, 0, ids);
+                        id
+                    };
+                } else {
+                    let (parent, index) = self.detach(target, path)?;
+                    self.insert_at(parent, index, ids);
+                }
+            }
+            PatchType::AddAttributes { attrs } => {
+                let element = self.element_mut(target, path)?;
+                // the attributes of the same name replaces the existing ones in their place
+                let mut names: Vec<&str> = attrs.iter().map(|a| a.name).collect();
+                names.dedup();
+                for name in names {
+                    let same_name = attrs
+                        .iter()
+                        .filter(|a| a.name == name)
+                        .map(|a| (*a).clone());
+                    match element.attrs.iter().position(|a| a.name == name) {
+                        Some(existing) => {
+                            element.attrs.retain(|a| a.name != name);
+                            element.attrs.splice(existing..existing, same_name);
+                        }
+                        None => element.attrs.extend(same_name),
+                    }
+                }
+            }
+            PatchType::RemoveAttributes { attrs } => {
+                let element = self.element_mut(target, path)?;
+                element
+                    .attrs
+                    .retain(|a| !attrs.iter().any(|removed| removed.name == a.name));
+            }
+            PatchType::SetStyles { styles } => {
+                let element = self.element_mut(target, path)?;
+                for style in styles {
+                    let mut is_set = false;
+                    for existing in element_styles(element).filter(|s| s.name == style.name) {
+                        existing.value = style.value.clone();
+                        is_set = true;
+                    }
+                    if is_set {
+                        continue;
+                    }
+                    if let Some(last) = element_styles_values(element).last() {
+                        last.push((*style).clone());
+                    } else {
+                        element.attrs.push(Attribute::new(
+                            None,
+                            STYLE,
+                            AttributeValue::from_styles([(*style).clone()]),
+                        ));
+                    }
+                }
+            }
+            PatchType::RemoveStyles { names } => {
+                let element = self.element_mut(target, path)?;
+                for styles in element_styles_values(element) {
+                    styles.retain(|s| !names.contains(&s.name.as_ref()));
+                }
+            }
+            PatchType::SetText { text } => {
+                let materialized = self.materialized(target);
+                match &mut self.slots[materialized] {
+                    Slot::Leaf(Leaf::Text(content) | Leaf::Comment(content)) => {
+                        *content = Cow::Owned(text.to_string());
+                    }
+                    _ => return Err(ApplyPatchError::NotText(path.clone())),
+                }
+            }
+            PatchType::AddClasses { classes } => {
+                let element = self.element_mut(target, path)?;
+                let mut class_list = element_class_list(element);
+                for class in classes {
+                    if !class_list.contains(class) {
+                        class_list.push(class.clone());
+                    }
+                }
+                set_element_class_list(element, class_list);
+            }
+            PatchType::RemoveClasses { classes } => {
+                let element = self.element_mut(target, path)?;
+                let mut class_list = element_class_list(element);
+                class_list.retain(|class| !classes.contains(class));
+                set_element_class_list(element, class_list);
+            }
+        }
+        Ok(())
+    }
+
+    fn into_node(mut self) -> Node<MSG> {
+        let root = self.root;
+        self.take(root)
+    }
+
+    fn take(&mut self, id: Id) -> Node<MSG> {
+        let slot = std::mem::replace(&mut self.slots[id], Slot::NodeList { children: vec![] });
+        match slot {
+            Slot::Element {
+                mut element,
+                children,
+            } => {
+                element.children = children.into_iter().map(|c| self.take(c)).collect();
+                Node::Element(element)
+            }
+            Slot::Fragment { children } => Node::Leaf(Leaf::Fragment(
+                children.into_iter().map(|c| self.take(c)).collect(),
+            )),
+            Slot::NodeList { children } => Node::Leaf(Leaf::NodeList(
+                children.into_iter().map(|c| self.take(c)).collect(),
+            )),
+            Slot::Stateless { type_id, view } => {
+                Node::Leaf(Leaf::StatelessComponent(StatelessModel {
+                    view: Box::new(self.take(view)),
+                    type_id,
+                }))
+            }
+            Slot::Leaf(leaf) => Node::Leaf(leaf),
+        }
+    }
+}
+
+/// Apply the `patches` to the `node`, such that applying `diff(old, new)` to `old` makes it the same as `new`.
+///
+/// The patches are applied in the same way as they are applied to the real DOM.
+/// The node is left unchanged if there is an error.
+pub fn apply_patches<MSG>(
+    node: &mut Node<MSG>,
+    patches: &[Patch<MSG>],
+) -> Result<(), ApplyPatchError> {
+    if patches.is_empty() {
+        return Ok(());
+    }
+    let mut arena = Arena::from_node(node.clone());
+    // find all the nodes before applying, since the paths are relative to the original tree
+    let targets = patches
+        .iter()
+        .map(|patch| {
+            let find = |path: &TreePath| {
+                arena
+                    .find(path)
+                    .ok_or_else(|| ApplyPatchError::NodeNotFound(path.clone()))
+            };
+            let target = find(&patch.patch_path)?;
+            arena.check_tag(target, patch)?;
+            let moving = patch
+                .node_paths()
+                .iter()
+                .map(find)
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok((target, moving))
+        })
+        .collect::<Result<Vec<_>, ApplyPatchError>>()?;
+
+    for (patch, (target, moving)) in patches.iter().zip(targets) {
+        arena.apply(target, moving, patch)?;
+    }
+    *node = arena.into_node();
+    Ok(())
+}
+
+impl<MSG> Node<MSG> {
+    /// apply the patches to this node, see [`apply_patches`]
+    pub fn apply_patches(&mut self, patches: &[Patch<MSG>]) -> Result<(), ApplyPatchError> {
+        apply_patches(self, patches)
+    }
+}
+
+/// the style values of the style attributes of the element
+fn element_styles_values<MSG>(element: &mut Element<MSG>) -> impl Iterator<Item = &mut Vec<Style>> {
+    element
+        .attrs
+        .iter_mut()
+        .filter(|a| a.name == *STYLE)
+        .flat_map(|a| a.value.iter_mut())
+        .filter_map(|v| match v {
+            AttributeValue::Style(styles) => Some(styles),
+            _ => None,
+        })
+}
+
+/// all the styles in the style attributes of the element
+fn element_styles<MSG>(element: &mut Element<MSG>) -> impl Iterator<Item = &mut Style> {
+    element_styles_values(element).flatten()
+}
+
+/// the class names in the class attributes of the element
+fn element_class_list<MSG>(element: &Element<MSG>) -> Vec<String> {
+    class_names(
+        element
+            .attrs
+            .iter()
+            .filter(|a| a.name == *CLASS)
+            .flat_map(|a| a.value.iter()),
+    )
+    .unwrap_or_default()
+}
+
+/// replace the class attributes of the element with a single class attribute of these class names
+fn set_element_class_list<MSG>(element: &mut Element<MSG>, class_list: Vec<String>) {
+    let class_attr = Attribute::new(
+        None,
+        CLASS,
+        AttributeValue::Simple(Value::from(class_list.join(" "))),
+    );
+    match element.attrs.iter().position(|a| a.name == *CLASS) {
+        Some(existing) => {
+            element.attrs.retain(|a| a.name != *CLASS);
+            element.attrs.insert(existing, class_attr);
+        }
+        None => element.attrs.push(class_attr),
+    }
+}
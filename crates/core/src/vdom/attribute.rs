@@ -5,7 +5,7 @@ use indexmap::IndexMap;
 
 use crate::vdom::EventCallback;
 pub use attribute_value::AttributeValue;
-pub use callback::Callback;
+pub use callback::{Callback, ListenerOptions};
 pub use style::Style;
 pub use value::Value;
 
@@ -98,6 +98,76 @@ impl<MSG> Attribute<MSG> {
             .unwrap_or(false)
     }
 
+    /// the options of the event listeners of this attribute, merged into one,
+    /// returns `None` if this attribute has no event listener
+    pub fn listener_options(&self) -> Option<ListenerOptions> {
+        self.value
+            .iter()
+            .filter_map(|v| v.as_event_listener())
+            .map(|listener| listener.options())
+            .reduce(ListenerOptions::merge)
+    }
+
+    /// set the options of the event listeners of this attribute
+    ///
+    /// # Examples
+    /// ```rust
+    /// use sauron::{html::events::on_wheel, vdom::ListenerOptions, *};
+    ///
+    /// let options = ListenerOptions { passive: true, ..Default::default() };
+    /// let wheel: Attribute<()> = on_wheel(|_| ()).with_listener_options(options);
+    /// assert_eq!(wheel.listener_options(), Some(options));
+    /// ```
+    pub fn with_listener_options(self, options: ListenerOptions) -> Self {
+        self.update_listener_options(|o| *o = options)
+    }
+
+    /// mark the event listeners of this attribute as passive,
+    /// the browser then does not wait for them before scrolling.
+    ///
+    /// This has no effect when the listeners also `prevent_default`.
+    pub fn passive(self) -> Self {
+        self.update_listener_options(|o| o.passive = true)
+    }
+
+    /// call the event listeners of this attribute in the capture phase
+    pub fn capture(self) -> Self {
+        self.update_listener_options(|o| o.capture = true)
+    }
+
+    /// remove the event listeners of this attribute after they are called once
+    pub fn once(self) -> Self {
+        self.update_listener_options(|o| o.once = true)
+    }
+
+    /// prevent the default action of the event before the event listeners of this attribute are called
+    ///
+    /// # Examples
+    /// ```rust
+    /// use sauron::{html::events::on_submit, *};
+    ///
+    /// let view: Node<()> = form([on_submit(|_| ()).prevent_default()], []);
+    /// ```
+    pub fn prevent_default(self) -> Self {
+        self.update_listener_options(|o| o.prevent_default = true)
+    }
+
+    /// stop the propagation of the event before the event listeners of this attribute are called
+    pub fn stop_propagation(self) -> Self {
+        self.update_listener_options(|o| o.stop_propagation = true)
+    }
+
+    fn update_listener_options(mut self, update: impl Fn(&mut ListenerOptions)) -> Self {
+        for value in self.value.iter_mut() {
+            if let AttributeValue::EventListener(listener) = value {
+                let mut options = listener.options();
+                update(&mut options);
+                listener.set_options(options);
+            }
+        }
+        self
+    }
+
     /// grouped values into plain, function calls, styles and event listeners
     pub(crate) fn group_values(attr: &Attribute<MSG>) -> GroupedAttributeValues<MSG> {
         let mut listeners = vec![];
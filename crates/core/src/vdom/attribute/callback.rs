@@ -32,6 +32,56 @@ pub struct Callback<IN, OUT> {
     event_type_id: TypeId,
     /// the type_id of the return type of this callback when executed.
     msg_type_id: TypeId,
+    /// the options of the event listener
+    options: ListenerOptions,
+}
+
+/// The options of how an event listener is attached to the DOM element and how the event is handled
+/// before the listener is called.
+///
+/// The options are part of the identity of the listener, changing them replaces the listener.
+///
+/// A passive listener can not prevent the default action of the event, the browser ignores it
+/// with only a warning in the console. So `prevent_default` wins over `passive`:
+/// the listener is not passive when both are set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ListenerOptions {
+    /// the listener will never prevent the default action of the event,
+    /// so the browser can scroll right away without waiting for it, ie: listeners of `wheel` and `touchmove`
+    pub passive: bool,
+    /// the listener is called in the capture phase, before the listeners of the descendants of the element
+    pub capture: bool,
+    /// the listener is removed after it is called once
+    pub once: bool,
+    /// the default action of the event is prevented before the listener is called
+    pub prevent_default: bool,
+    /// the event is stopped from propagating to the other elements before the listener is called
+    pub stop_propagation: bool,
+}
+
+impl ListenerOptions {
+    /// merge the options of the listeners of the same event,
+    /// which is used when they are attached as one listener
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            passive: self.passive || other.passive,
+            capture: self.capture || other.capture,
+            once: self.once || other.once,
+            prevent_default: self.prevent_default || other.prevent_default,
+            stop_propagation: self.stop_propagation || other.stop_propagation,
+        }
+        .resolved()
+    }
+
+    /// drop `passive` when `prevent_default` is also set, since a passive listener
+    /// can not prevent the default action of the event
+    pub fn resolved(self) -> Self {
+        Self {
+            passive: self.passive && !self.prevent_default,
+            ..self
+        }
+    }
 }
 
 impl<IN, F, OUT> From<F> for Callback<IN, OUT>
@@ -46,6 +96,7 @@ where
             func_type_id: TypeId::of::<F>(),
             event_type_id: TypeId::of::<IN>(),
             msg_type_id: TypeId::of::<OUT>(),
+            options: ListenerOptions::default(),
         }
     }
 }
@@ -59,8 +110,8 @@ impl<IN, OUT> fmt::Debug for Callback<IN, OUT> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "in: {:?}, out: {:?}, func: {:?}",
-            self.event_type_id, self.msg_type_id, self.func_type_id
+            "in: {:?}, out: {:?}, func: {:?}, options: {:?}",
+            self.event_type_id, self.msg_type_id, self.func_type_id, self.options
         )
     }
 }
@@ -83,6 +134,8 @@ where
         MSG2: 'static,
     {
         let source_func_type_id = self.func_type_id;
+        let event_type_id = self.event_type_id;
+        let options = self.options;
         let cb = move |input| {
             let out = self.emit(input);
             cb2(out)
@@ -90,12 +143,50 @@ where
         Callback {
             func: Rc::new(cb),
             func_type_id: source_func_type_id,
-            event_type_id: TypeId::of::<IN>(),
+            event_type_id,
             msg_type_id: TypeId::of::<OUT>(),
+            options,
         }
     }
 }
 
+impl<IN, OUT> Callback<IN, OUT> {
+    /// the type_id of the event this callback is given, which is `IN`
+    /// unless it is tagged with [`Callback::with_event_type`]
+    pub fn event_type_id(&self) -> TypeId {
+        self.event_type_id
+    }
+
+    /// tag this callback with the type of the event that `IN` is mapped into before
+    /// it is given to the function, ie: `MouseEvent` for the listeners of `on_click`
+    pub fn with_event_type<EV: 'static>(mut self) -> Self {
+        self.event_type_id = TypeId::of::<EV>();
+        self
+    }
+
+    /// the address of the function, which is shared by this callback and its clones
+    #[cfg(feature = "with-serde")]
+    pub(crate) fn func_addr(&self) -> usize {
+        Rc::as_ptr(&self.func).cast::<()>() as usize
+    }
+
+    /// the options of this callback when it is attached as an event listener
+    pub fn options(&self) -> ListenerOptions {
+        self.options
+    }
+
+    /// set the options of this callback when it is attached as an event listener,
+    /// `prevent_default` wins over `passive` when both are set
+    pub fn with_options(mut self, options: ListenerOptions) -> Self {
+        self.set_options(options);
+        self
+    }
+
+    pub(crate) fn set_options(&mut self, options: ListenerOptions) {
+        self.options = options.resolved();
+    }
+}
+
 /// Note:
 /// using the #[derive(Clone)] needs IN and OUT to also be Clone
 ///
@@ -109,6 +200,7 @@ impl<IN, OUT> Clone for Callback<IN, OUT> {
             func_type_id: self.func_type_id,
             event_type_id: self.event_type_id,
             msg_type_id: self.msg_type_id,
+            options: self.options,
         }
     }
 }
@@ -129,5 +221,6 @@ impl<IN, OUT> PartialEq for Callback<IN, OUT> {
         self.event_type_id == other.event_type_id
             && self.msg_type_id == other.msg_type_id
             && self.func_type_id == other.func_type_id
+            && self.options == other.options
     }
 }
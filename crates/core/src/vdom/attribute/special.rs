@@ -23,6 +23,12 @@ pub static SKIP: &AttributeName = &"skip";
 /// The skip criteria attribute
 pub static SKIP_CRITERIA: &AttributeName = &"skip_criteria";
 
+/// The style attribute, its style values are diffed for each style property
+pub(crate) static STYLE: &AttributeName = &"style";
+
+/// The class attribute, its values are diffed for each class name
+pub(crate) static CLASS: &AttributeName = &"class";
+
 ///
 /// NOTE: The following attributes have special behaviour in the dom, the framework
 /// need to call the specific methods to reflect the state of this attribute to the element
@@ -4,7 +4,8 @@ use std::fmt;
 
 /// css styles
 /// style can be converted into an attribute
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Style {
     /// style name such as border, width, etc
     pub name: Cow<'static, str>,
@@ -6,6 +6,7 @@ use std::fmt;
 /// such as checked(bool), name(String), tab_index(i32)
 /// Note: memory size of Value is 32 bytes, in comparison String is 24 bytes
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Value {
     /// bool value
     Bool(bool),
@@ -215,6 +216,12 @@ impl From<String> for Value {
     }
 }
 
+impl From<Cow<'static, str>> for Value {
+    fn from(v: Cow<'static, str>) -> Self {
+        Self::Cow(v)
+    }
+}
+
 impl<T, const N: usize> From<[T; N]> for Value
 where
     T: Into<Value> + Clone,
@@ -0,0 +1,266 @@
+//! Compare two virtual node trees and report where they first differ, used by [`assert_node_eq!`](crate::assert_node_eq).
+//!
+//! The attributes are compared by name regardless of their order,
+//! and the views of components and lazy nodes are compared in their place.
+use crate::vdom::selector::materialized;
+use crate::vdom::{
+    Attribute, AttributeName, AttributeValue, Element, Leaf, Node, Style, TreePath, Value, KEY,
+    REPLACE, SKIP, SKIP_CRITERIA,
+};
+use std::fmt;
+
+/// The options of comparing nodes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompareOptions {
+    /// the event listeners are not compared
+    pub ignore_listeners: bool,
+    /// the special attributes `key`, `skip`, `skip_criteria` and `replace` are not compared
+    pub ignore_special_attributes: bool,
+}
+
+impl CompareOptions {
+    /// compare everything
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// do not compare the event listeners
+    pub fn ignore_listeners(mut self) -> Self {
+        self.ignore_listeners = true;
+        self
+    }
+
+    /// do not compare the special attributes `key`, `skip`, `skip_criteria` and `replace`
+    pub fn ignore_special_attributes(mut self) -> Self {
+        self.ignore_special_attributes = true;
+        self
+    }
+}
+
+/// An attribute which has different values in the two elements
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttributeMismatch {
+    /// the name of the attribute
+    pub name: AttributeName,
+    /// the values in the left element, None if it does not have the attribute
+    pub left: Option<String>,
+    /// the values in the right element, None if it does not have the attribute
+    pub right: Option<String>,
+}
+
+/// The first difference found when comparing two nodes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeMismatch {
+    /// the path of the differing nodes
+    pub path: TreePath,
+    /// what is different in the nodes
+    pub reason: String,
+    /// the attributes with different values, when the elements differ in their attributes
+    pub attributes: Vec<AttributeMismatch>,
+    /// the left node rendered as pretty html
+    pub left: String,
+    /// the right node rendered as pretty html
+    pub right: String,
+}
+
+/// Compare the nodes, returning the first difference if they are not equal.
+///
+/// # Examples
+/// ```rust
+/// use sauron::{vdom::{compare_nodes, CompareOptions}, html::attributes::*, *};
+///
+/// let left: Node<()> = div([class("a")], [span([], [text("hello")])]);
+/// let right: Node<()> = div([class("a")], [span([], [text("world")])]);
+/// let mismatch = compare_nodes(&left, &right, CompareOptions::new()).unwrap_err();
+/// assert_eq!(mismatch.path, TreePath::new([0, 0]));
+/// ```
+pub fn compare_nodes<MSG>(
+    left: &Node<MSG>,
+    right: &Node<MSG>,
+    options: CompareOptions,
+) -> Result<(), NodeMismatch> {
+    compare(left, right, options, TreePath::root())
+}
+
+fn compare<MSG>(
+    left: &Node<MSG>,
+    right: &Node<MSG>,
+    options: CompareOptions,
+    path: TreePath,
+) -> Result<(), NodeMismatch> {
+    let left = materialized(left);
+    let right = materialized(right);
+    let mismatch = |reason: String, attributes| NodeMismatch {
+        path: path.clone(),
+        reason,
+        attributes,
+        left: left.render_to_string_pretty(),
+        right: right.render_to_string_pretty(),
+    };
+    let (left_children, right_children) = match (left, right) {
+        (Node::Element(left_elm), Node::Element(right_elm)) => {
+            if left_elm.tag() != right_elm.tag() || left_elm.namespace() != right_elm.namespace() {
+                return Err(mismatch(
+                    format!(
+                        "different tags: {:?} != {:?}",
+                        left_elm.tag(),
+                        right_elm.tag()
+                    ),
+                    vec![],
+                ));
+            }
+            let attributes = compare_attributes(left_elm, right_elm, options);
+            if !attributes.is_empty() {
+                return Err(mismatch("different attributes".to_string(), attributes));
+            }
+            (left_elm.children(), right_elm.children())
+        }
+        (
+            Node::Leaf(Leaf::Fragment(left_nodes) | Leaf::NodeList(left_nodes)),
+            Node::Leaf(Leaf::Fragment(right_nodes) | Leaf::NodeList(right_nodes)),
+        ) => (&left_nodes[..], &right_nodes[..]),
+        (Node::Leaf(left_leaf), Node::Leaf(right_leaf)) if left_leaf == right_leaf => return Ok(()),
+        _ => return Err(mismatch("different nodes".to_string(), vec![])),
+    };
+    for (index, (left_child, right_child)) in left_children.iter().zip(right_children).enumerate() {
+        compare(left_child, right_child, options, path.traverse(index))?;
+    }
+    if left_children.len() != right_children.len() {
+        return Err(mismatch(
+            format!(
+                "different number of children: {} != {}",
+                left_children.len(),
+                right_children.len()
+            ),
+            vec![],
+        ));
+    }
+    Ok(())
+}
+
+/// the attributes which has different values, in the order they appear in the left then the right element
+fn compare_attributes<MSG>(
+    left: &Element<MSG>,
+    right: &Element<MSG>,
+    options: CompareOptions,
+) -> Vec<AttributeMismatch> {
+    let left_attrs = compared_attributes(left, options);
+    let right_attrs = compared_attributes(right, options);
+    let value_of = |attrs: &[Attribute<MSG>], name| {
+        attrs
+            .iter()
+            .find(|attr| attr.name == name)
+            .map(|attr| attr.value.clone())
+    };
+    let mut names: Vec<AttributeName> = left_attrs.iter().map(|attr| attr.name).collect();
+    for attr in right_attrs.iter() {
+        if !names.contains(&attr.name) {
+            names.push(attr.name);
+        }
+    }
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let left = value_of(&left_attrs, name);
+            let right = value_of(&right_attrs, name);
+            (left != right).then(|| AttributeMismatch {
+                name,
+                left: left.map(|values| describe_values(&values)),
+                right: right.map(|values| describe_values(&values)),
+            })
+        })
+        .collect()
+}
+
+/// the attributes of the element merged by name, excluding the ignored ones
+fn compared_attributes<MSG>(
+    element: &Element<MSG>,
+    options: CompareOptions,
+) -> Vec<Attribute<MSG>> {
+    let special = [KEY, SKIP, SKIP_CRITERIA, REPLACE];
+    Attribute::merge_attributes_of_same_name(element.attributes().iter())
+        .into_iter()
+        .filter(|attr| !(options.ignore_special_attributes && special.contains(&attr.name())))
+        .filter_map(|mut attr| {
+            if options.ignore_listeners {
+                attr.value.retain(|value| !value.is_event_listener());
+                if attr.value.is_empty() {
+                    return None;
+                }
+            }
+            Some(attr)
+        })
+        .collect()
+}
+
+/// describe the attribute values, the same way they are rendered
+fn describe_values<MSG>(values: &[AttributeValue<MSG>]) -> String {
+    let plain = values.iter().filter_map(|value| value.get_simple());
+    let styles = values.iter().filter_map(|value| value.as_style()).flatten();
+    let listeners = values.iter().filter(|value| value.is_event_listener());
+    let mut described = vec![];
+    if let Some(plain) = Value::merge_to_string(plain) {
+        described.push(format!("{plain:?}"));
+    }
+    if let Some(styles) = Style::merge_to_string(styles) {
+        described.push(format!("{styles:?}"));
+    }
+    let listeners = listeners.count();
+    if listeners > 0 {
+        described.push(format!("{listeners} event listener(s)"));
+    }
+    described.join(", ")
+}
+
+impl fmt::Display for NodeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "nodes differ at path: {:?}, {}",
+            self.path.path, self.reason
+        )?;
+        for attr in self.attributes.iter() {
+            let none = "(none)".to_string();
+            writeln!(
+                f,
+                "  {}: {} != {}",
+                attr.name,
+                attr.left.as_ref().unwrap_or(&none),
+                attr.right.as_ref().unwrap_or(&none)
+            )?;
+        }
+        writeln!(f, "left:\n{}", self.left)?;
+        write!(f, "right:\n{}", self.right)
+    }
+}
+
+/// Assert that two nodes are equal, otherwise panic showing the path where they first differ,
+/// both of the differing nodes rendered as html, and the attributes that are different.
+///
+/// The options can be passed to ignore the event listeners and special attributes, see [`CompareOptions`](crate::vdom::CompareOptions).
+///
+/// # Examples
+/// ```rust
+/// use sauron::{assert_node_eq, vdom::CompareOptions, html::{attributes::*, events::*}, *};
+///
+/// let left: Node<()> = ul([], [li([key(1), on_click(|_| ())], [text("one")])]);
+/// let right: Node<()> = ul([], [li([], [text("one")])]);
+/// assert_node_eq!(
+///     left,
+///     right,
+///     CompareOptions::new()
+///         .ignore_listeners()
+///         .ignore_special_attributes()
+/// );
+/// ```
+#[macro_export]
+macro_rules! assert_node_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_node_eq!($left, $right, $crate::vdom::CompareOptions::default())
+    };
+    ($left:expr, $right:expr, $options:expr $(,)?) => {
+        if let Err(mismatch) = $crate::vdom::compare_nodes(&$left, &$right, $options) {
+            panic!("assertion `left == right` failed\n{mismatch}");
+        }
+    };
+}
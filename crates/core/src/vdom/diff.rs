@@ -1,10 +1,11 @@
 //! provides diffing algorithm which returns patches
 use super::{diff_lis, Attribute, Element, Node, Patch, TreePath};
-use super::{Tag, KEY, REPLACE, SKIP, SKIP_CRITERIA};
+use super::{Style, Tag, CLASS, KEY, REPLACE, SKIP, SKIP_CRITERIA, STYLE};
 use crate::dom::skip_diff::SkipAttrs;
 use crate::dom::SkipPath;
 use crate::vdom::AttributeValue;
 use crate::vdom::Leaf;
+use indexmap::IndexMap;
 use std::{cmp, mem};
 
 #[cfg(feature = "use-skipdiff")]
@@ -67,14 +68,16 @@ fn is_any_keyed<MSG>(nodes: &[Node<MSG>]) -> bool {
 
 /// returns true any attributes of this node attribute has key in it
 fn is_keyed_node<MSG>(node: &Node<MSG>) -> bool {
-    if let Some(attributes) = node.attributes() {
+    if let Node::Leaf(Leaf::Lazy(lazy)) = node {
+        lazy.key().is_some()
+    } else if let Some(attributes) = node.attributes() {
         attributes.iter().any(|att| att.name == *KEY)
     } else {
         false
     }
 }
 
-fn should_replace<'a, MSG>(old_node: &'a Node<MSG>, new_node: &'a Node<MSG>) -> bool {
+pub(crate) fn should_replace<'a, MSG>(old_node: &'a Node<MSG>, new_node: &'a Node<MSG>) -> bool {
     // replace if they have different enum variants
     if mem::discriminant(old_node) != mem::discriminant(new_node) {
         log::warn!("different discriminant: old_node: {:#?}", old_node);
@@ -136,6 +139,20 @@ pub fn diff_recursive<'a, MSG>(
                 .unwrap_or(false),
         }
     };
+    // lazy nodes are diffed as their views, which are only built when the arguments changed
+    if let Node::Leaf(Leaf::Lazy(new_lazy)) = new_node {
+        if let Node::Leaf(Leaf::Lazy(old_lazy)) = old_node {
+            if new_lazy.same_args(old_lazy) {
+                new_lazy.reuse_view(old_lazy);
+                return vec![];
+            }
+        }
+        return diff_recursive(old_node, new_lazy.view(), path);
+    }
+    if let Node::Leaf(Leaf::Lazy(old_lazy)) = old_node {
+        return diff_recursive(old_lazy.view(), new_node, path);
+    }
+
     // skip diffing if the function evaluates to true
     if skip(old_node, new_node) {
         return vec![];
@@ -158,9 +175,14 @@ pub fn diff_recursive<'a, MSG>(
     match (old_node, new_node) {
         (Node::Leaf(old_leaf), Node::Leaf(new_leaf)) => {
             match (old_leaf, new_leaf) {
-                (Leaf::Text(_), Leaf::Text(_))
-                | (Leaf::Comment(_), Leaf::Comment(_))
-                | (Leaf::DocType(_), Leaf::DocType(_)) => {
+                (Leaf::Text(old_text), Leaf::Text(new_text))
+                | (Leaf::Comment(old_text), Leaf::Comment(new_text)) => {
+                    if old_text != new_text {
+                        let patch = Patch::set_text(path.path.clone(), new_text);
+                        patches.push(patch);
+                    }
+                }
+                (Leaf::DocType(_), Leaf::DocType(_)) => {
                     if old_leaf != new_leaf {
                         let patch = Patch::replace_node(None, path.path.clone(), vec![new_node]);
                         patches.push(patch);
@@ -354,6 +376,10 @@ fn create_attribute_patches<'a, MSG>(
 
     let mut add_attributes: Vec<&Attribute<MSG>> = vec![];
     let mut remove_attributes: Vec<&Attribute<MSG>> = vec![];
+    let mut set_styles: Vec<&Style> = vec![];
+    let mut remove_styles: Vec<&str> = vec![];
+    let mut add_classes: Vec<String> = vec![];
+    let mut remove_classes: Vec<String> = vec![];
 
     let new_attributes_grouped = new_element.group_indexed_attributes_per_name();
     let old_attributes_grouped = old_element.group_indexed_attributes_per_name();
@@ -389,6 +415,23 @@ fn create_attribute_patches<'a, MSG>(
                 //
             } else {
                 if old_attr_values != new_attr_values {
+                    if *new_attr_name == STYLE {
+                        if let Some((styles, style_names)) =
+                            diff_styles(&old_attr_values, &new_attr_values)
+                        {
+                            set_styles.extend(styles);
+                            remove_styles.extend(style_names);
+                            continue;
+                        }
+                    } else if *new_attr_name == CLASS {
+                        if let Some((classes, removed_classes)) =
+                            diff_classes(&old_attr_values, &new_attr_values)
+                        {
+                            add_classes.extend(classes);
+                            remove_classes.extend(removed_classes);
+                            continue;
+                        }
+                    }
                     for (_i, new_att) in new_attrs {
                         add_attributes.push(new_att);
                     }
@@ -429,9 +472,118 @@ fn create_attribute_patches<'a, MSG>(
             remove_attributes,
         ));
     }
+    if !set_styles.is_empty() {
+        patches.push(Patch::set_styles(
+            &old_element.tag,
+            path.path.clone(),
+            set_styles,
+        ));
+    }
+    if !remove_styles.is_empty() {
+        patches.push(Patch::remove_styles(
+            &old_element.tag,
+            path.path.clone(),
+            remove_styles,
+        ));
+    }
+    if !add_classes.is_empty() {
+        patches.push(Patch::add_classes(
+            &old_element.tag,
+            path.path.clone(),
+            add_classes,
+        ));
+    }
+    if !remove_classes.is_empty() {
+        patches.push(Patch::remove_classes(
+            &old_element.tag,
+            path.path.clone(),
+            remove_classes,
+        ));
+    }
     patches
 }
 
+/// return the class names to be added and the class names to be removed,
+/// only when the values of the class attribute in both old and new are all plain values.
+/// The order of the class names doesn't matter, so only the class names that changed are returned.
+fn diff_classes<MSG>(
+    old_values: &[&Vec<AttributeValue<MSG>>],
+    new_values: &[&Vec<AttributeValue<MSG>>],
+) -> Option<(Vec<String>, Vec<String>)> {
+    let old_classes = class_names(old_values.iter().flat_map(|v| v.iter()))?;
+    let new_classes = class_names(new_values.iter().flat_map(|v| v.iter()))?;
+    let added = new_classes
+        .iter()
+        .filter(|class| !old_classes.contains(class))
+        .cloned()
+        .collect();
+    let removed = old_classes
+        .iter()
+        .filter(|class| !new_classes.contains(class))
+        .cloned()
+        .collect();
+    Some((added, removed))
+}
+
+/// the distinct class names in the class attribute values, None if there are non plain values
+pub(crate) fn class_names<'a, MSG: 'a>(
+    values: impl IntoIterator<Item = &'a AttributeValue<MSG>>,
+) -> Option<Vec<String>> {
+    let mut classes: Vec<String> = vec![];
+    for value in values {
+        match value {
+            AttributeValue::Simple(v) => {
+                for class in v.to_string().split_whitespace() {
+                    if !classes.iter().any(|c| c == class) {
+                        classes.push(class.to_string());
+                    }
+                }
+            }
+            AttributeValue::Empty => (),
+            _ => return None,
+        }
+    }
+    Some(classes)
+}
+
+/// return the style properties to be set and the names of the style properties to be removed,
+/// only when the values of the style attribute in both old and new are all styles.
+/// Styles of the same name are overridden by the latter one, the same way they are rendered.
+fn diff_styles<'a, MSG>(
+    old_values: &[&'a Vec<AttributeValue<MSG>>],
+    new_values: &[&'a Vec<AttributeValue<MSG>>],
+) -> Option<(Vec<&'a Style>, Vec<&'a str>)> {
+    let merge = |values: &[&'a Vec<AttributeValue<MSG>>]| {
+        let mut merged: IndexMap<&'a str, &'a Style> = IndexMap::new();
+        for value in values.iter().flat_map(|v| v.iter()) {
+            match value {
+                AttributeValue::Style(styles) => {
+                    for style in styles {
+                        merged.insert(&style.name, style);
+                    }
+                }
+                AttributeValue::Empty => (),
+                _ => return None,
+            }
+        }
+        Some(merged)
+    };
+    let old_styles = merge(old_values)?;
+    let new_styles = merge(new_values)?;
+
+    let set_styles = new_styles
+        .iter()
+        .filter(|(name, style)| old_styles.get(*name) != Some(*style))
+        .map(|(_, style)| *style)
+        .collect();
+    let remove_styles = old_styles
+        .keys()
+        .filter(|name| !new_styles.contains_key(*name))
+        .copied()
+        .collect();
+    Some((set_styles, remove_styles))
+}
+
 /// returns true if all the elements in subset is in big_set
 /// This also returns the indices of big_set that are not found in the subset
 fn is_subset_of<T: PartialEq>(subset: &[T], big_set: &[T]) -> bool {
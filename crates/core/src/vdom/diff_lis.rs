@@ -1,10 +1,10 @@
 //! diff with longest increasing subsequence
 
-use super::diff::diff_recursive;
-use super::{AttributeValue, Tag, KEY};
+use super::diff::{diff_recursive, should_replace};
 use super::{Node, Patch};
+use super::{Tag, KEY};
 use crate::dom::SkipPath;
-use indexmap::IndexMap;
+use std::collections::HashMap;
 
 pub fn diff_keyed_nodes<'a, MSG>(
     old_tag: Option<&'a Tag>,
@@ -94,27 +94,46 @@ pub fn diff_keyed_nodes<'a, MSG>(
     all_patches
 }
 
+/// the old node is reused for the new node, when they have the same key and the old node
+/// will not be replaced, so it can be used as a target of the succeeding patches.
+/// Leaf nodes such as text are replaced when changed, so they are only reused when unchanged.
+fn is_reusable<MSG>(old: &Node<MSG>, new: &Node<MSG>) -> bool {
+    old.attribute_value(KEY) == new.attribute_value(KEY)
+        && !should_replace(old, new)
+        && (old.is_element() || old == new)
+}
+
+/// the key of the node as a string, which is used to look up the old children with the same key
+fn key_string<MSG>(node: &Node<MSG>) -> Option<String> {
+    let values = node.attribute_value(KEY)?;
+    Some(
+        values
+            .iter()
+            .filter_map(|v| v.get_simple())
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>()
+            .join(" "),
+    )
+}
+
 fn diff_keyed_ends<'a, MSG>(
     old_tag: Option<&'a Tag>,
     old_children: &'a [Node<MSG>],
     new_children: &'a [Node<MSG>],
     path: &SkipPath,
 ) -> (Vec<Patch<'a, MSG>>, Option<(usize, usize)>) {
-    // keep track of the old index that has been matched already
-    let mut old_index_matched = vec![];
     let mut all_patches = vec![];
 
     let mut left_offset = 0;
     for (index, (old, new)) in old_children.iter().zip(new_children.iter()).enumerate() {
-        // abort early if we run into nodes with different keys
-        if old.attribute_value(KEY) != new.attribute_value(KEY) {
+        // abort early if we run into nodes with different keys or which can not be reused
+        if !is_reusable(old, new) {
             break;
         }
         let child_path = path.traverse(index);
         // diff the children and add to patches
         let patches = diff_recursive(old, new, &child_path);
         all_patches.extend(patches);
-        old_index_matched.push(index);
         left_offset += 1;
     }
 
@@ -143,6 +162,7 @@ fn diff_keyed_ends<'a, MSG>(
     }
 
     // if the shared key is less than either length, then we need to walk backwards
+    let shared_len = old_children.len().min(new_children.len());
     let mut right_offset = 0;
     for (index, (old, new)) in old_children
         .iter()
@@ -151,10 +171,8 @@ fn diff_keyed_ends<'a, MSG>(
         .enumerate()
     {
         let old_index = old_children.len() - index - 1;
-        // break if already matched this old_index or did not matched key
-        if old_index_matched.contains(&old_index)
-            || old.attribute_value(KEY) != new.attribute_value(KEY)
-        {
+        // break if reaching the children matched from the left or did not matched key
+        if left_offset + right_offset >= shared_len || !is_reusable(old, new) {
             break;
         }
         let child_path = path.traverse(old_index);
@@ -167,6 +185,10 @@ fn diff_keyed_ends<'a, MSG>(
 }
 
 /// derived from dioxus core/src/diff.rs
+///
+/// The old children that are in the longest increasing subsequence stay in place,
+/// the rest of the old children are moved before the next child in the subsequence
+/// or after the last one, and the new children are inserted likewise.
 fn diff_keyed_middle<'a, MSG>(
     old_children: &'a [Node<MSG>],
     new_children: &'a [Node<MSG>],
@@ -175,223 +197,147 @@ fn diff_keyed_middle<'a, MSG>(
 ) -> Vec<Patch<'a, MSG>> {
     let mut all_patches = vec![];
 
-    let old_children_keys: Vec<_> = old_children
-        .iter()
-        .map(|c| c.attribute_value(KEY))
-        .collect();
-
-    let new_children_keys: Vec<_> = new_children
-        .iter()
-        .map(|c| c.attribute_value(KEY))
-        .collect();
-
-    debug_assert_ne!(new_children_keys.first(), old_children_keys.first());
-    debug_assert_ne!(new_children_keys.last(), old_children_keys.last());
-
-    // make a map of old_index -> old_key
-    let old_key_to_old_index: IndexMap<usize, &Vec<&AttributeValue<MSG>>> =
-        IndexMap::from_iter(old_children_keys.iter().enumerate().filter_map(
-            |(old_index, old_key)| old_key.as_ref().map(|old_key| (old_index, old_key)),
-        ));
-
-    let mut shared_keys: Vec<Vec<&AttributeValue<MSG>>> = vec![];
+    // the old indexes of each key, in order, so only the old children with the same key are checked
+    let mut old_indexes_by_key: HashMap<String, Vec<usize>> = HashMap::new();
+    for (old_index, old) in old_children.iter().enumerate() {
+        if let Some(key) = key_string(old) {
+            old_indexes_by_key.entry(key).or_default().push(old_index);
+        }
+    }
 
-    // map each new key to the old key, carrying over the old index
-    let new_index_to_old_index: Vec<usize> = new_children
+    // map each new child to the old child with the same key
+    let mut old_index_matched = vec![false; old_children.len()];
+    let new_index_to_old_index: Vec<Option<usize>> = new_children
         .iter()
         .map(|new| {
-            if let Some(new_key) = new.attribute_value(KEY) {
-                let index = old_key_to_old_index
-                    .iter()
-                    .find_map(|(old_index, old_key)| {
-                        if new_key == **old_key {
-                            Some(*old_index)
-                        } else {
-                            None
-                        }
-                    });
-                if let Some(index) = index {
-                    shared_keys.push(new_key);
-                    index
-                } else {
-                    u32::MAX as usize
-                }
-            } else {
-                u32::MAX as usize
-            }
+            let old_indexes = old_indexes_by_key.get(&key_string(new)?)?;
+            let old_index = old_indexes.iter().copied().find(|old_index| {
+                !old_index_matched[*old_index] && is_reusable(&old_children[*old_index], new)
+            })?;
+            old_index_matched[old_index] = true;
+            Some(old_index)
         })
         .collect();
 
+    let old_path = |old_index: usize| path.traverse(left_offset + old_index).path;
+    let moved_paths = |run: &Run| {
+        run.iter()
+            .map(|(_, old_index)| old_path(old_index.expect("must be reused")))
+            .collect::<Vec<_>>()
+    };
+    let inserted = |run: &Run| {
+        run.iter()
+            .map(|(new_index, _)| &new_children[*new_index])
+            .collect::<Vec<_>>()
+    };
+
     // if none of the old keys are reused by the new children,
     // then we remove all the remaining old children and create the new children afresh.
-    if shared_keys.is_empty() && old_children.first().is_some() {
+    if !old_index_matched.contains(&true) {
         // skip the first one, so we can use it as our foothold for inserting the new children
-        for (index, old) in old_children.iter().skip(1).enumerate() {
-            let patch = Patch::remove_node(old.tag(), path.traverse(index + 1).path);
-            all_patches.push(patch);
+        for (index, old) in old_children.iter().enumerate().skip(1) {
+            all_patches.push(Patch::remove_node(old.tag(), old_path(index)));
         }
-
-        let first = 0;
-        log::info!("Creating a replace node in diff_keyed_middle");
-
         let patch = Patch::replace_node(
-            old_children[left_offset + first].tag(),
-            path.traverse(left_offset + first).path,
+            old_children[0].tag(),
+            old_path(0),
             new_children.iter().collect::<Vec<_>>(),
         );
         all_patches.push(patch);
         return all_patches;
     }
 
-    // remove any old children that are not shared
+    // remove any old children that are not reused, including the ones with no key
     for (index, old_child) in old_children.iter().enumerate() {
-        if let Some(old_key) = old_child.attribute_value(KEY) {
-            if !shared_keys.contains(&old_key) {
-                let patch =
-                    Patch::remove_node(old_child.tag(), path.traverse(left_offset + index).path);
-                all_patches.push(patch);
-            }
-        } else {
-            // also remove the node that has no key
-            let patch =
-                Patch::remove_node(old_child.tag(), path.traverse(left_offset + index).path);
-            all_patches.push(patch);
+        if !old_index_matched[index] {
+            all_patches.push(Patch::remove_node(old_child.tag(), old_path(index)));
         }
     }
 
-    // Compute the LIS of this list
-    let mut lis_sequence = Vec::with_capacity(new_index_to_old_index.len());
+    // diff the reused children in their old location
+    for (new_child, old_index) in new_children.iter().zip(new_index_to_old_index.iter()) {
+        if let Some(old_index) = old_index {
+            let child_path = path.traverse(left_offset + old_index);
+            let patches = diff_recursive(&old_children[*old_index], new_child, &child_path);
+            all_patches.extend(patches);
+        }
+    }
+
+    // Compute the LIS of the old indexes of the reused children
+    let reused: Vec<(usize, usize)> = new_index_to_old_index
+        .iter()
+        .enumerate()
+        .filter_map(|(new_index, old_index)| old_index.map(|old_index| (new_index, old_index)))
+        .collect();
+    let reused_old_indexes: Vec<usize> = reused.iter().map(|(_, old_index)| *old_index).collect();
 
-    let mut predecessors = vec![0; new_index_to_old_index.len()];
-    let mut starts = vec![0; new_index_to_old_index.len()];
+    let mut lis_sequence = Vec::with_capacity(reused.len());
+    let mut predecessors = vec![0; reused.len()];
+    let mut starts = vec![0; reused.len()];
 
     longest_increasing_subsequence::lis_with(
-        &new_index_to_old_index,
+        &reused_old_indexes,
         &mut lis_sequence,
         |a, b| a < b,
         &mut predecessors,
         &mut starts,
     );
-
-    // the list_sequence came out from high to low, so we just reverse it back to arrange from low to high
+    // the lis_sequence came out from high to low, so we just reverse it back to arrange from low to high
     lis_sequence.reverse();
-
-    // if a new node gets u32 max and is at the end, then it might be part of our LIS (because u32 max is a valid LIS)
-    if lis_sequence.last().map(|f| new_index_to_old_index[*f]) == Some(u32::MAX as usize) {
-        lis_sequence.pop();
-    }
-
-    for idx in lis_sequence.iter() {
-        let patches = diff_recursive(
-            &old_children[new_index_to_old_index[*idx]],
-            &new_children[*idx],
-            path,
-        );
-        all_patches.extend(patches);
-    }
-
-    // add mount instruction for the first items not covered by the lis
-    let mut move_after_nodes = vec![];
-    let last = *lis_sequence.last().unwrap();
-    if last < (new_children.len() - 1) {
-        let mut new_nodes = vec![];
-        let mut node_paths = vec![];
-        for (idx, new_node) in new_children[(last + 1)..].iter().enumerate() {
-            let new_idx = idx + last + 1;
-            let old_index = new_index_to_old_index[new_idx];
-            if old_index == u32::MAX as usize {
-                new_nodes.push(new_node);
+    let in_place: Vec<usize> = lis_sequence.iter().map(|i| reused[*i].0).collect();
+
+    // the children in between the children that stay in place are moved or inserted before
+    // the next child that stays in place, keeping their order
+    let mut start = 0;
+    for stay in in_place.iter() {
+        let anchor = new_index_to_old_index[*stay].expect("must be reused");
+        let segment = start..*stay;
+        for (is_moved, run) in runs(&new_index_to_old_index[segment.clone()], segment.start) {
+            let tag = old_children[anchor].tag();
+            let patch = if is_moved {
+                Patch::move_before_node(tag, old_path(anchor), moved_paths(&run))
             } else {
-                let patches = diff_recursive(&old_children[old_index], new_node, path);
-                all_patches.extend(patches);
-
-                node_paths.push(path.traverse(left_offset + old_index).path);
-            }
-        }
-        if !node_paths.is_empty() {
-            let patch = Patch::move_after_node(
-                old_children[left_offset + last].tag(),
-                path.traverse(left_offset + last).path, //target element
-                node_paths,
-            );
-            move_after_nodes.push(patch);
-        }
-        let old_index = new_index_to_old_index[last];
-        let tag = old_children[old_index].tag();
-        if !new_nodes.is_empty() {
-            let patch = Patch::insert_after_node(
-                tag,
-                path.traverse(left_offset + old_index).path,
-                new_nodes,
-            );
+                Patch::insert_before_node(tag, old_path(anchor), inserted(&run))
+            };
             all_patches.push(patch);
         }
+        start = stay + 1;
     }
 
-    // for each spacing, generate a mount instruction
-    let mut lis_iter = lis_sequence.iter().rev();
-    let last = *lis_iter.next().unwrap();
-    let lowest = lis_iter.min();
-    if let Some(next) = lowest {
-        let mut new_nodes = vec![];
-        for (idx, new_node) in new_children[(next + 1)..last].iter().enumerate() {
-            let new_idx = idx + next + 1;
-            let old_index = new_index_to_old_index[new_idx];
-            if old_index == u32::MAX as usize {
-                new_nodes.push(new_node)
-            } else {
-                let patches = diff_recursive(&old_children[old_index], new_node, path);
-                all_patches.extend(patches);
-            }
-        }
-
-        if !new_nodes.is_empty() {
-            let tag = old_children[last].tag();
-            let patch =
-                Patch::insert_before_node(tag, path.traverse(left_offset + last).path, new_nodes);
-            all_patches.push(patch);
-        }
+    // the children after the last one in place are placed right after it,
+    // starting from the last so each of them ends up before the ones placed earlier
+    let last = *in_place.last().expect("must have at least 1 reused child");
+    let anchor = new_index_to_old_index[last].expect("must be reused");
+    let segment = (last + 1)..new_children.len();
+    for (is_moved, run) in runs(&new_index_to_old_index[segment.clone()], segment.start)
+        .into_iter()
+        .rev()
+    {
+        let tag = old_children[anchor].tag();
+        let patch = if is_moved {
+            Patch::move_after_node(tag, old_path(anchor), moved_paths(&run))
+        } else {
+            Patch::insert_after_node(tag, old_path(anchor), inserted(&run))
+        };
+        all_patches.push(patch);
     }
+    all_patches
+}
 
-    // add mount instruction for the last items not covered by the list
-    let mut move_before_nodes = vec![];
-    let first_lis = *lis_sequence.first().unwrap();
-    if first_lis > 0 {
-        let mut new_nodes = vec![];
-        let mut node_paths = vec![];
-        for (idx, new_node) in new_children[..first_lis].iter().enumerate() {
-            let old_index = new_index_to_old_index[idx];
-            if old_index == u32::MAX as usize {
-                new_nodes.push(new_node);
-            } else {
-                let patches = diff_recursive(&old_children[old_index], new_node, path);
-                all_patches.extend(patches);
-                node_paths.push(path.traverse(left_offset + old_index).path);
+/// consecutive children, each with its new index and old index if it is reused
+type Run = Vec<(usize, Option<usize>)>;
+
+/// group the consecutive children which are either all moved or all newly inserted
+fn runs(old_indexes: &[Option<usize>], start: usize) -> Vec<(bool, Run)> {
+    let mut runs: Vec<(bool, Run)> = vec![];
+    for (i, old_index) in old_indexes.iter().enumerate() {
+        let is_moved = old_index.is_some();
+        match runs.last_mut() {
+            Some((run_is_moved, run)) if *run_is_moved == is_moved => {
+                run.push((start + i, *old_index))
             }
-        }
-        if !node_paths.is_empty() {
-            let first = 0;
-            let patch = Patch::move_before_node(
-                old_children[left_offset + first].tag(),
-                path.traverse(left_offset + first).path, //target_element
-                node_paths,                              //to be move after the target_element
-            );
-            move_before_nodes.push(patch);
-        }
-
-        if !new_nodes.is_empty() {
-            let old_index = new_index_to_old_index[first_lis];
-            let tag = old_children[old_index].tag();
-            let patch = Patch::insert_before_node(
-                tag,
-                path.traverse(left_offset + old_index).path,
-                new_nodes,
-            );
-            all_patches.push(patch);
+            _ => runs.push((is_moved, vec![(start + i, *old_index)])),
         }
     }
-
-    all_patches.extend(move_before_nodes);
-    all_patches.extend(move_after_nodes);
-    all_patches
+    runs
 }
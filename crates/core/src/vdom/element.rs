@@ -1,8 +1,8 @@
 use super::attribute::{AttributeName, Namespace, Tag};
+use super::node::unroll_node_lists;
 use super::{Attribute, Node};
 
 use crate::vdom::AttributeValue;
-use crate::vdom::Leaf;
 use crate::vdom::Value;
 use derive_where::derive_where;
 use indexmap::IndexMap;
@@ -44,19 +44,12 @@ impl<MSG> Element<MSG> {
         children: impl IntoIterator<Item = Node<MSG>>,
         self_closing: bool,
     ) -> Self {
-        //unroll the nodelist
-        let children = children
-            .into_iter()
-            .flat_map(|child| match child {
-                Node::Leaf(Leaf::NodeList(node_list)) => node_list,
-                _ => vec![child],
-            })
-            .collect();
         Self {
             namespace,
             tag,
             attrs: attrs.into_iter().collect(),
-            children,
+            //unroll the nodelist
+            children: unroll_node_lists(children),
             self_closing,
         }
     }
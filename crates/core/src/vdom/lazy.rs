@@ -0,0 +1,146 @@
+//! Lazy node, which only builds its view when its arguments changed
+use crate::vdom::{AttributeValue, Node, Value};
+use std::any::{Any, TypeId};
+use std::cell::OnceCell;
+use std::fmt;
+use std::rc::Rc;
+
+type ViewFn<MSG> = Rc<dyn Fn(&dyn Any) -> Node<MSG>>;
+
+/// A node where its view is built from the arguments only when it is needed.
+///
+/// When the arguments are equal to the arguments of the lazy node in the previous view,
+/// the view of the previous lazy node is reused and is not diffed at all.
+pub struct Lazy<MSG> {
+    /// the type id of the view function
+    type_id: TypeId,
+    /// the arguments of the view function
+    args: Rc<dyn Any>,
+    /// compare the arguments of two lazy nodes, which are of the same type
+    args_eq: fn(&dyn Any, &dyn Any) -> bool,
+    /// build the view from the arguments
+    view_fn: ViewFn<MSG>,
+    /// the view, which is built at most once
+    view: OnceCell<Rc<Node<MSG>>>,
+    /// the key of this lazy node, used to match the lazy nodes when diffing keyed siblings
+    key: Option<AttributeValue<MSG>>,
+}
+
+fn args_eq<ARGS>(args: &dyn Any, other: &dyn Any) -> bool
+where
+    ARGS: PartialEq + 'static,
+{
+    match (args.downcast_ref::<ARGS>(), other.downcast_ref::<ARGS>()) {
+        (Some(args), Some(other)) => args == other,
+        _ => false,
+    }
+}
+
+impl<MSG> Lazy<MSG> {
+    /// create a lazy node, the view is built by calling `view_fn` with the `args`
+    pub fn new<ARGS, F>(args: ARGS, view_fn: F) -> Self
+    where
+        ARGS: PartialEq + 'static,
+        F: Fn(&ARGS) -> Node<MSG> + 'static,
+    {
+        Self {
+            type_id: TypeId::of::<F>(),
+            args: Rc::new(args),
+            args_eq: args_eq::<ARGS>,
+            view_fn: Rc::new(move |args| {
+                view_fn(
+                    args.downcast_ref::<ARGS>()
+                        .expect("must be the same args type"),
+                )
+            }),
+            view: OnceCell::new(),
+            key: None,
+        }
+    }
+
+    /// set the key of this lazy node, so it is matched with the lazy node of the same key
+    /// in the previous view when diffing its siblings
+    pub fn with_key(mut self, key: impl Into<Value>) -> Self {
+        self.key = Some(AttributeValue::Simple(key.into()));
+        self
+    }
+
+    /// the key of this lazy node
+    pub fn key(&self) -> Option<&AttributeValue<MSG>> {
+        self.key.as_ref()
+    }
+
+    /// the view of this lazy node, which is built on the first call
+    pub fn view(&self) -> &Node<MSG> {
+        self.view
+            .get_or_init(|| Rc::new((self.view_fn)(&*self.args)))
+    }
+
+    /// returns true if the view is already built
+    pub fn is_built(&self) -> bool {
+        self.view.get().is_some()
+    }
+
+    /// returns true if this lazy node has the same view function and arguments as `other`,
+    /// so they will have the same view
+    pub fn same_args(&self, other: &Self) -> bool {
+        self.type_id == other.type_id && (self.args_eq)(&*self.args, &*other.args)
+    }
+
+    /// use the view of the `old` lazy node, so the view of this node is not built again
+    pub(crate) fn reuse_view(&self, old: &Self) {
+        if let Some(view) = old.view.get() {
+            let _ = self.view.set(Rc::clone(view));
+        }
+    }
+
+    /// map the msg of this lazy node such that `Lazy<MSG>` becomes `Lazy<MSG2>`
+    pub fn map_msg<F, MSG2>(self, cb: F) -> Lazy<MSG2>
+    where
+        F: Fn(MSG) -> MSG2 + Clone + 'static,
+        MSG2: 'static,
+        MSG: 'static,
+    {
+        let view_fn = self.view_fn;
+        Lazy {
+            type_id: self.type_id,
+            args: self.args,
+            args_eq: self.args_eq,
+            view_fn: Rc::new(move |args| view_fn(args).map_msg(cb.clone())),
+            view: OnceCell::new(),
+            key: self
+                .key
+                .and_then(|key| key.get_simple().cloned())
+                .map(AttributeValue::Simple),
+        }
+    }
+}
+
+impl<MSG> Clone for Lazy<MSG> {
+    fn clone(&self) -> Self {
+        Self {
+            type_id: self.type_id,
+            args: Rc::clone(&self.args),
+            args_eq: self.args_eq,
+            view_fn: Rc::clone(&self.view_fn),
+            view: self.view.clone(),
+            key: self.key.clone(),
+        }
+    }
+}
+
+impl<MSG> PartialEq for Lazy<MSG> {
+    fn eq(&self, other: &Self) -> bool {
+        self.same_args(other)
+    }
+}
+
+impl<MSG> fmt::Debug for Lazy<MSG> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Lazy")
+            .field("type_id", &self.type_id)
+            .field("key", &self.key.as_ref().and_then(|key| key.get_simple()))
+            .field("view", &self.view.get())
+            .finish()
+    }
+}
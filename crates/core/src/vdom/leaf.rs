@@ -4,8 +4,10 @@ use crate::dom::StatelessModel;
 use crate::vdom::Attribute;
 use crate::vdom::AttributeName;
 use crate::vdom::AttributeValue;
+use crate::vdom::Lazy;
 use crate::vdom::Node;
 use crate::vdom::TemplatedView;
+use crate::vdom::KEY;
 use derive_where::derive_where;
 use std::borrow::Cow;
 
@@ -33,6 +35,8 @@ pub enum Leaf<MSG> {
     StatelessComponent(StatelessModel<MSG>),
     /// a view where a template and skip diff is provided
     TemplatedView(TemplatedView<MSG>),
+    /// a view which is only built and diffed when its arguments changed
+    Lazy(Lazy<MSG>),
 }
 
 impl<MSG> PartialEq for Leaf<MSG> {
@@ -47,6 +51,7 @@ impl<MSG> PartialEq for Leaf<MSG> {
             #[cfg(feature = "with-dom")]
             (Self::StatefulComponent(v), Self::StatefulComponent(o)) => v == o,
             (Self::StatelessComponent(v), Self::StatelessComponent(o)) => v == o,
+            (Self::Lazy(v), Self::Lazy(o)) => v == o,
             _ => false,
         }
     }
@@ -73,6 +78,7 @@ impl<MSG> Leaf<MSG> {
         match self {
             Self::StatelessComponent(comp) => comp.attribute_value(name),
             Self::TemplatedView(templated_view) => templated_view.view.attribute_value(name),
+            Self::Lazy(lazy) if name == KEY => lazy.key().map(|key| vec![key]),
             _ => None,
         }
     }
@@ -113,6 +113,7 @@ impl<MSG> Leaf<MSG> {
             Self::StatefulComponent(v) => Leaf::StatefulComponent(v.map_msg(cb)),
             Self::StatelessComponent(v) => Leaf::StatelessComponent(v.map_msg(cb)),
             Self::TemplatedView(v) => Leaf::TemplatedView(v.map_msg(cb)),
+            Self::Lazy(v) => Leaf::Lazy(v.map_msg(cb)),
         }
     }
 }
@@ -5,6 +5,7 @@ use crate::vdom::AttributeValue;
 use crate::vdom::Element;
 use crate::vdom::Leaf;
 use crate::vdom::Value;
+use crate::vdom::{visit::walk_node, TreePath, Visitor};
 use derive_where::derive_where;
 use std::fmt;
 use std::fmt::{Debug, Formatter};
@@ -253,13 +254,21 @@ impl<MSG> Node<MSG> {
 
     /// only count the descendant node
     pub fn descendant_node_count(&self) -> usize {
-        let mut cnt = 0;
-        if let Node::Element(element) = self {
-            for child in element.children().iter() {
-                cnt += child.node_count();
+        /// counts the nodes, without going into the leaves
+        struct NodeCount(usize);
+
+        impl<MSG> Visitor<MSG> for NodeCount {
+            fn visit_node(&mut self, node: &Node<MSG>, path: &TreePath) {
+                self.0 += 1;
+                walk_node(self, node, path);
             }
+
+            fn visit_leaf(&mut self, _leaf: &Leaf<MSG>, _path: &TreePath) {}
         }
-        cnt
+
+        let mut count = NodeCount(0);
+        count.visit_node(self, &TreePath::root());
+        count.0 - 1
     }
 
     /// remove the existing attributes and set with the new value
@@ -387,10 +396,22 @@ pub fn leaf<MSG>(leaf: impl Into<Leaf<MSG>>) -> Node<MSG> {
 
 /// create a node list
 pub fn node_list<MSG>(nodes: impl IntoIterator<Item = Node<MSG>>) -> Node<MSG> {
-    Node::Leaf(Leaf::NodeList(nodes.into_iter().collect()))
+    Node::Leaf(Leaf::NodeList(unroll_node_lists(nodes)))
 }
 
 /// create fragment node
 pub fn fragment<MSG>(nodes: impl IntoIterator<Item = Node<MSG>>) -> Node<MSG> {
-    Node::Leaf(Leaf::Fragment(nodes.into_iter().collect()))
+    Node::Leaf(Leaf::Fragment(unroll_node_lists(nodes)))
+}
+
+/// flatten the node lists, including the nested ones, into the nodes
+pub(crate) fn unroll_node_lists<MSG>(nodes: impl IntoIterator<Item = Node<MSG>>) -> Vec<Node<MSG>> {
+    let mut unrolled = vec![];
+    for node in nodes {
+        match node {
+            Node::Leaf(Leaf::NodeList(node_list)) => unrolled.extend(unroll_node_lists(node_list)),
+            _ => unrolled.push(node),
+        }
+    }
+    unrolled
 }
@@ -1,13 +1,17 @@
 //! patch module
 
 use super::Tag;
-use super::{Attribute, Node};
+use super::{Attribute, Node, Style};
 use std::borrow::Cow;
 
 use derive_where::derive_where;
 
+pub use explain::{explain_patches, PatchExplanation, PatchReport};
+pub use optimize::optimize_patches;
 pub use tree_path::TreePath;
 
+mod explain;
+mod optimize;
 mod tree_path;
 
 /// A Patch encodes an operation that modifies a real DOM element or native UI element
@@ -113,6 +117,12 @@ pub enum PatchType<'a, MSG> {
         /// the node that will replace the target node
         replacement: Vec<&'a Node<MSG>>,
     },
+    /// Change the content of the target text or comment node in place,
+    /// so the text node is kept in the DOM, along with its text selection.
+    SetText {
+        /// the new content of the text or comment node
+        text: &'a str,
+    },
     /// Add attributes that the new node has that the old node does not
     /// Note: the attributes is not a reference since attributes of same
     /// name are merged to produce a new unify attribute
@@ -125,6 +135,28 @@ pub enum PatchType<'a, MSG> {
         /// attributes that are to be removed from this target node
         attrs: Vec<&'a Attribute<MSG>>,
     },
+    /// Set the style properties that are added or changed in the new node,
+    /// the rest of the style properties of the target node are left as is
+    SetStyles {
+        /// the style properties to be set into the target node
+        styles: Vec<&'a Style>,
+    },
+    /// Remove the style properties that the old node had that the new node doesn't
+    RemoveStyles {
+        /// the names of the style properties to be removed from the target node
+        names: Vec<&'a str>,
+    },
+    /// Add the class names that the new node has that the old node does not,
+    /// the other class names of the target node are left as is
+    AddClasses {
+        /// the class names to be added into the target node
+        classes: Vec<String>,
+    },
+    /// Remove the class names that the old node had that the new node doesn't
+    RemoveClasses {
+        /// the class names to be removed from the target node
+        classes: Vec<String>,
+    },
 }
 
 impl<'a, MSG> Patch<'a, MSG> {
@@ -255,6 +287,15 @@ impl<'a, MSG> Patch<'a, MSG> {
         }
     }
 
+    /// create a patch where the content of the target text or comment node is changed
+    pub fn set_text(patch_path: TreePath, text: &'a str) -> Patch<'a, MSG> {
+        Patch {
+            tag: None,
+            patch_path,
+            patch_type: PatchType::SetText { text },
+        }
+    }
+
     /// create a patch where a new attribute is added to the target element
     pub fn add_attributes(
         tag: &'a Tag,
@@ -284,6 +325,67 @@ impl<'a, MSG> Patch<'a, MSG> {
         }
     }
 
+    /// create a patch where the style properties are set into the target element
+    pub fn set_styles(
+        tag: &'a Tag,
+        patch_path: TreePath,
+        styles: impl IntoIterator<Item = &'a Style>,
+    ) -> Patch<'a, MSG> {
+        Patch {
+            tag: Some(tag),
+            patch_path,
+            patch_type: PatchType::SetStyles {
+                styles: styles.into_iter().collect(),
+            },
+        }
+    }
+
+    /// create a patch where the style properties with these names are removed from the target
+    /// element
+    pub fn remove_styles(
+        tag: &'a Tag,
+        patch_path: TreePath,
+        names: impl IntoIterator<Item = &'a str>,
+    ) -> Patch<'a, MSG> {
+        Patch {
+            tag: Some(tag),
+            patch_path,
+            patch_type: PatchType::RemoveStyles {
+                names: names.into_iter().collect(),
+            },
+        }
+    }
+
+    /// create a patch where the class names are added into the target element
+    pub fn add_classes(
+        tag: &'a Tag,
+        patch_path: TreePath,
+        classes: impl IntoIterator<Item = impl ToString>,
+    ) -> Patch<'a, MSG> {
+        Patch {
+            tag: Some(tag),
+            patch_path,
+            patch_type: PatchType::AddClasses {
+                classes: classes.into_iter().map(|c| c.to_string()).collect(),
+            },
+        }
+    }
+
+    /// create a patch where the class names are removed from the target element
+    pub fn remove_classes(
+        tag: &'a Tag,
+        patch_path: TreePath,
+        classes: impl IntoIterator<Item = impl ToString>,
+    ) -> Patch<'a, MSG> {
+        Patch {
+            tag: Some(tag),
+            patch_path,
+            patch_type: PatchType::RemoveClasses {
+                classes: classes.into_iter().map(|c| c.to_string()).collect(),
+            },
+        }
+    }
+
     /// map the msg of this patch such that `Patch<MSG>` becomes `Patch<MSG2>`
     pub fn map_msg<F, MSG2>(self, cb: F) -> Patch<'a, MSG2>
     where
@@ -300,6 +402,27 @@ impl<'a, MSG> Patch<'a, MSG> {
 }
 
 impl<'a, MSG> PatchType<'a, MSG> {
+    /// the name of this patch type, ie: `AddAttributes`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::InsertBeforeNode { .. } => "InsertBeforeNode",
+            Self::InsertAfterNode { .. } => "InsertAfterNode",
+            Self::AppendChildren { .. } => "AppendChildren",
+            Self::ClearChildren => "ClearChildren",
+            Self::RemoveNode => "RemoveNode",
+            Self::MoveBeforeNode { .. } => "MoveBeforeNode",
+            Self::MoveAfterNode { .. } => "MoveAfterNode",
+            Self::ReplaceNode { .. } => "ReplaceNode",
+            Self::SetText { .. } => "SetText",
+            Self::AddAttributes { .. } => "AddAttributes",
+            Self::RemoveAttributes { .. } => "RemoveAttributes",
+            Self::SetStyles { .. } => "SetStyles",
+            Self::RemoveStyles { .. } => "RemoveStyles",
+            Self::AddClasses { .. } => "AddClasses",
+            Self::RemoveClasses { .. } => "RemoveClasses",
+        }
+    }
+
     /// map the msg of this patch_type such that `PatchType<MSG>` becomes `PatchType<MSG2>`
     pub fn map_msg<F, MSG2>(self, cb: F) -> PatchType<'a, MSG2>
     where
@@ -0,0 +1,232 @@
+//! Summary of a set of patches, to find out why an update produces more patches than expected
+use crate::vdom::selector::{attribute_string, materialized};
+use crate::vdom::{find_by_path, Attribute, Leaf, Node, Patch, PatchType, Tag, TreePath};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A summary of the patches produced by diffing an old node against a new one
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PatchReport {
+    /// the number of patches of each patch type
+    pub per_type: BTreeMap<&'static str, usize>,
+    /// the number of nodes created by inserting, appending and replacing nodes
+    pub nodes_created: usize,
+    /// the number of nodes removed by removing, replacing and clearing nodes
+    pub nodes_removed: usize,
+    /// the number of nodes moved
+    pub nodes_moved: usize,
+    /// the number of attributes added or removed in the elements of each tag,
+    /// where the styles and the classes of an element count as one attribute each
+    pub attributes_per_tag: BTreeMap<Tag, usize>,
+    /// what each patch does, in the same order as the patches
+    pub patches: Vec<PatchExplanation>,
+}
+
+/// What a patch does to its target node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatchExplanation {
+    /// the target node as a css-like path, ie: `div.app > ul > li:nth-child(3)`
+    pub target: String,
+    /// what is changed in the target node
+    pub description: String,
+}
+
+/// Summarize the `patches` which are the result of diffing the `old` node against a new node.
+///
+/// # Examples
+/// ```rust
+/// use sauron::{vdom::explain_patches, html::attributes::*, *};
+///
+/// let old: Node<()> = div([class("app")], [ul([], [li([], [text("one")]), li([], [text("two")])])]);
+/// let new: Node<()> = div([class("app")], [ul([], [li([], [text("one")]), li([class("done")], [text("two")])])]);
+/// let report = explain_patches(&old, &diff(&old, &new));
+/// assert_eq!(report.patches[0].target, "div.app > ul > li:nth-child(2)");
+/// assert_eq!(report.patches[0].description, "add attributes: class");
+/// ```
+pub fn explain_patches<MSG>(old: &Node<MSG>, patches: &[Patch<MSG>]) -> PatchReport {
+    let mut report = PatchReport::default();
+    for patch in patches.iter() {
+        *report.per_type.entry(patch.patch_type.name()).or_default() += 1;
+        let target = find_by_path(old, &patch.patch_path);
+        let description = match &patch.patch_type {
+            PatchType::InsertBeforeNode { nodes } => {
+                report.nodes_created += nodes.iter().map(|n| n.node_count()).sum::<usize>();
+                format!("insert {} node(s) before", nodes.len())
+            }
+            PatchType::InsertAfterNode { nodes } => {
+                report.nodes_created += nodes.iter().map(|n| n.node_count()).sum::<usize>();
+                format!("insert {} node(s) after", nodes.len())
+            }
+            PatchType::AppendChildren { children } => {
+                report.nodes_created += children.iter().map(|n| n.node_count()).sum::<usize>();
+                format!("append {} child node(s)", children.len())
+            }
+            PatchType::ClearChildren => {
+                report.nodes_removed += target.map_or(0, |n| n.descendant_node_count());
+                "clear the child nodes".to_string()
+            }
+            PatchType::RemoveNode => {
+                report.nodes_removed += target.map_or(0, |n| n.node_count());
+                "remove the node".to_string()
+            }
+            PatchType::MoveBeforeNode { nodes_path } => {
+                report.nodes_moved += nodes_path.len();
+                format!("move {} node(s) before", nodes_path.len())
+            }
+            PatchType::MoveAfterNode { nodes_path } => {
+                report.nodes_moved += nodes_path.len();
+                format!("move {} node(s) after", nodes_path.len())
+            }
+            PatchType::ReplaceNode { replacement } => {
+                report.nodes_removed += target.map_or(0, |n| n.node_count());
+                report.nodes_created += replacement.iter().map(|n| n.node_count()).sum::<usize>();
+                format!("replace with {} node(s)", replacement.len())
+            }
+            PatchType::SetText { text } => format!("set the text to {text:?}"),
+            PatchType::AddAttributes { attrs } => {
+                report.touch_attributes(patch, target, attrs.len());
+                format!("add attributes: {}", attribute_names(attrs))
+            }
+            PatchType::RemoveAttributes { attrs } => {
+                report.touch_attributes(patch, target, attrs.len());
+                format!("remove attributes: {}", attribute_names(attrs))
+            }
+            PatchType::SetStyles { styles } => {
+                report.touch_attributes(patch, target, 1);
+                let styles = styles.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+                format!("set styles: {}", styles.join("; "))
+            }
+            PatchType::RemoveStyles { names } => {
+                report.touch_attributes(patch, target, 1);
+                format!("remove styles: {}", names.join(", "))
+            }
+            PatchType::AddClasses { classes } => {
+                report.touch_attributes(patch, target, 1);
+                format!("add classes: {}", classes.join(" "))
+            }
+            PatchType::RemoveClasses { classes } => {
+                report.touch_attributes(patch, target, 1);
+                format!("remove classes: {}", classes.join(" "))
+            }
+        };
+        report.patches.push(PatchExplanation {
+            target: css_path(old, &patch.patch_path),
+            description,
+        });
+    }
+    report
+}
+
+impl PatchReport {
+    /// the total number of patches
+    pub fn total(&self) -> usize {
+        self.per_type.values().sum()
+    }
+
+    fn touch_attributes<MSG>(
+        &mut self,
+        patch: &Patch<MSG>,
+        target: Option<&Node<MSG>>,
+        count: usize,
+    ) {
+        let tag = patch
+            .tag
+            .copied()
+            .or_else(|| target.and_then(|n| n.element_ref()).map(|e| *e.tag()));
+        if let Some(tag) = tag {
+            *self.attributes_per_tag.entry(tag).or_default() += count;
+        }
+    }
+}
+
+fn attribute_names<MSG>(attrs: &[&Attribute<MSG>]) -> String {
+    attrs
+        .iter()
+        .map(|attr| *attr.name())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn child_nodes<MSG>(node: &Node<MSG>) -> &[Node<MSG>] {
+    match node {
+        Node::Element(element) => element.children(),
+        Node::Leaf(Leaf::Fragment(nodes) | Leaf::NodeList(nodes)) => nodes,
+        Node::Leaf(_) => &[],
+    }
+}
+
+/// the css-like path of the node at `path`, ie: `div.app > ul > li:nth-child(3)`.
+/// The `:nth-child` is only added when there are other element siblings.
+/// When the path does not exist in the `old` node, the indexes of the rest of the path are used.
+fn css_path<MSG>(old: &Node<MSG>, path: &TreePath) -> String {
+    let mut node = materialized(old);
+    let mut segments = vec![css_segment(node)];
+    for (depth, index) in path.path.iter().enumerate() {
+        let siblings = child_nodes(node);
+        let Some(child) = siblings.get(*index) else {
+            segments.push(format!("{:?}", &path.path[depth..]));
+            break;
+        };
+        let child = materialized(child);
+        let mut segment = css_segment(child);
+        let is_element = |n: &&Node<MSG>| materialized(n).is_element();
+        if child.is_element() && siblings.iter().filter(is_element).count() > 1 {
+            let nth = siblings[..*index].iter().filter(is_element).count() + 1;
+            segment += &format!(":nth-child({nth})");
+        }
+        segments.push(segment);
+        node = child;
+    }
+    segments.join(" > ")
+}
+
+fn css_segment<MSG>(node: &Node<MSG>) -> String {
+    match node {
+        Node::Element(element) => {
+            let mut segment = element.tag().to_string();
+            if let Some(id) = attribute_string(element, "id") {
+                segment += &format!("#{id}");
+            }
+            if let Some(class) = attribute_string(element, "class") {
+                for class in class.split_whitespace() {
+                    segment += &format!(".{class}");
+                }
+            }
+            segment
+        }
+        Node::Leaf(Leaf::Text(_)) => "#text".to_string(),
+        Node::Leaf(Leaf::Comment(_)) => "#comment".to_string(),
+        Node::Leaf(Leaf::Fragment(_) | Leaf::NodeList(_)) => "#fragment".to_string(),
+        Node::Leaf(_) => "#leaf".to_string(),
+    }
+}
+
+impl fmt::Display for PatchReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} patches", self.total())?;
+        for (name, count) in self.per_type.iter() {
+            writeln!(f, "  {name}: {count}")?;
+        }
+        writeln!(
+            f,
+            "nodes created: {}, removed: {}, moved: {}",
+            self.nodes_created, self.nodes_removed, self.nodes_moved
+        )?;
+        if !self.attributes_per_tag.is_empty() {
+            writeln!(f, "attributes touched:")?;
+            for (tag, count) in self.attributes_per_tag.iter() {
+                writeln!(f, "  {tag}: {count}")?;
+            }
+        }
+        for explanation in self.patches.iter() {
+            writeln!(f, "{explanation}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for PatchExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.target, self.description)
+    }
+}
@@ -0,0 +1,288 @@
+//! Merge and cancel the patches which would otherwise be applied as separate DOM calls.
+//!
+//! All the nodes targeted by the patches are looked up before any patch is applied,
+//! so the patch paths always point to the nodes of the original tree, and the optimized
+//! patches use only the paths of the original patches.
+use crate::vdom::{Node, Patch, PatchType, TreePath};
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// Merge and cancel the patches such that applying the optimized patches gives the same result
+/// as applying the original `patches`.
+///
+/// - patches on the same element which add or remove attributes, styles or classes are merged into one,
+///   and the attributes, styles or classes which are added and then removed are not added
+/// - consecutive insertions, appends or moves at the same node are merged into one
+/// - a node which is removed and has nodes inserted in its place is replaced instead
+/// - moving nodes to where they already are is removed
+///
+/// # Examples
+/// ```rust
+/// use sauron::{vdom::{optimize_patches, Patch}, html::attributes::*, *};
+///
+/// let attrs: [Attribute<()>; 2] = [class("a"), id("b")];
+/// let patches = vec![
+///     Patch::add_attributes(&"div", TreePath::new([1]), [&attrs[0]]),
+///     Patch::add_attributes(&"div", TreePath::new([1]), [&attrs[1]]),
+/// ];
+/// assert_eq!(
+///     optimize_patches(patches),
+///     [Patch::add_attributes(&"div", TreePath::new([1]), [&attrs[0], &attrs[1]])]
+/// );
+/// ```
+pub fn optimize_patches<'a, MSG>(patches: Vec<Patch<'a, MSG>>) -> Vec<Patch<'a, MSG>> {
+    let mut references: HashMap<TreePath, usize> = HashMap::new();
+    for patch in patches.iter() {
+        for path in std::iter::once(patch.path()).chain(patch.node_paths()) {
+            *references.entry(path.clone()).or_default() += 1;
+        }
+    }
+    let is_only_referred_by_itself = |path: &TreePath| references.get(path) == Some(&1);
+
+    let mut optimized: Vec<Patch<'a, MSG>> = Vec::with_capacity(patches.len());
+    for patch in patches {
+        if is_redundant_move(&patch, is_only_referred_by_itself) {
+            continue;
+        }
+        let patch = if is_attribute_patch(&patch) {
+            merge_attribute_patch(&mut optimized, patch)
+        } else {
+            merge_adjacent(&mut optimized, patch, is_only_referred_by_itself)
+        };
+        if let Some(patch) = patch {
+            optimized.push(patch);
+        }
+    }
+    optimized
+}
+
+fn is_attribute_patch<MSG>(patch: &Patch<MSG>) -> bool {
+    matches!(
+        patch.patch_type,
+        PatchType::AddAttributes { .. }
+            | PatchType::RemoveAttributes { .. }
+            | PatchType::SetStyles { .. }
+            | PatchType::RemoveStyles { .. }
+            | PatchType::AddClasses { .. }
+            | PatchType::RemoveClasses { .. }
+    )
+}
+
+/// Merge the patch into the last patch on the same element, when that patch changes the same kind of attributes.
+/// The attributes of an element are not affected by the patches on the other nodes,
+/// so the patch can be merged into a patch before them.
+///
+/// Returns the patch back if it is still to be added.
+fn merge_attribute_patch<'a, MSG>(
+    optimized: &mut Vec<Patch<'a, MSG>>,
+    mut patch: Patch<'a, MSG>,
+) -> Option<Patch<'a, MSG>> {
+    let path = patch.path();
+    let Some(position) = optimized
+        .iter()
+        .rposition(|p| p.path() == path || p.node_paths().contains(path))
+    else {
+        return Some(patch);
+    };
+    let last = &mut optimized[position];
+    if last.tag != patch.tag {
+        return Some(patch);
+    }
+    match (&mut last.patch_type, &mut patch.patch_type) {
+        (PatchType::AddAttributes { attrs: last }, PatchType::AddAttributes { attrs }) => {
+            // the later attributes replace the earlier ones of the same name
+            last.retain(|a| !attrs.iter().any(|attr| attr.name == a.name));
+            last.append(attrs);
+            None
+        }
+        (PatchType::RemoveAttributes { attrs: last }, PatchType::RemoveAttributes { attrs }) => {
+            attrs.retain(|attr| !last.iter().any(|a| a.name == attr.name));
+            last.append(attrs);
+            None
+        }
+        (PatchType::SetStyles { styles: last }, PatchType::SetStyles { styles }) => {
+            last.retain(|s| !styles.iter().any(|style| style.name == s.name));
+            last.append(styles);
+            None
+        }
+        (PatchType::RemoveStyles { names: last }, PatchType::RemoveStyles { names }) => {
+            names.retain(|name| !last.contains(name));
+            last.append(names);
+            None
+        }
+        (PatchType::AddClasses { classes: last }, PatchType::AddClasses { classes })
+        | (PatchType::RemoveClasses { classes: last }, PatchType::RemoveClasses { classes }) => {
+            classes.retain(|class| !last.contains(class));
+            last.append(classes);
+            None
+        }
+        // the attributes, styles or classes which are removed afterwards need not be added
+        (PatchType::AddAttributes { attrs: last }, PatchType::RemoveAttributes { attrs }) => {
+            last.retain(|a| !attrs.iter().any(|attr| attr.name == a.name));
+            let is_empty = last.is_empty();
+            remove_if(optimized, position, is_empty);
+            Some(patch)
+        }
+        (PatchType::SetStyles { styles: last }, PatchType::RemoveStyles { names }) => {
+            last.retain(|s| !names.contains(&s.name.as_ref()));
+            let is_empty = last.is_empty();
+            remove_if(optimized, position, is_empty);
+            Some(patch)
+        }
+        (PatchType::AddClasses { classes: last }, PatchType::RemoveClasses { classes }) => {
+            last.retain(|class| !classes.contains(class));
+            let is_empty = last.is_empty();
+            remove_if(optimized, position, is_empty);
+            Some(patch)
+        }
+        _ => Some(patch),
+    }
+}
+
+fn remove_if<MSG>(optimized: &mut Vec<Patch<MSG>>, position: usize, condition: bool) {
+    if condition {
+        optimized.remove(position);
+    }
+}
+
+/// Merge the patch into the patch right before it.
+///
+/// Returns the patch back if it is still to be added.
+fn merge_adjacent<'a, MSG>(
+    optimized: &mut [Patch<'a, MSG>],
+    mut patch: Patch<'a, MSG>,
+    is_only_referred_by_itself: impl Fn(&TreePath) -> bool,
+) -> Option<Patch<'a, MSG>> {
+    let Some(last) = optimized.last_mut() else {
+        return Some(patch);
+    };
+    let same_target = last.patch_path == patch.patch_path && last.tag == patch.tag;
+    let replacement = match (&mut last.patch_type, &mut patch.patch_type) {
+        (
+            PatchType::InsertBeforeNode { nodes: last_nodes },
+            PatchType::InsertBeforeNode { nodes },
+        ) if same_target => {
+            last_nodes.append(nodes);
+            return None;
+        }
+        // the nodes inserted later are right after the target, before the nodes inserted earlier
+        (
+            PatchType::InsertAfterNode { nodes: last_nodes },
+            PatchType::InsertAfterNode { nodes },
+        ) if same_target => {
+            last_nodes.splice(0..0, nodes.drain(..));
+            return None;
+        }
+        (
+            PatchType::AppendChildren {
+                children: last_children,
+            },
+            PatchType::AppendChildren { children },
+        ) if same_target => {
+            last_children.append(children);
+            return None;
+        }
+        (
+            PatchType::MoveBeforeNode {
+                nodes_path: last_paths,
+            },
+            PatchType::MoveBeforeNode { nodes_path },
+        ) if same_target && is_disjoint(last_paths, nodes_path) => {
+            last_paths.append(nodes_path);
+            return None;
+        }
+        (
+            PatchType::MoveAfterNode {
+                nodes_path: last_paths,
+            },
+            PatchType::MoveAfterNode { nodes_path },
+        ) if same_target && is_disjoint(last_paths, nodes_path) => {
+            last_paths.splice(0..0, nodes_path.drain(..));
+            return None;
+        }
+        // inserting beside a node then removing it is replacing it
+        (PatchType::InsertBeforeNode { nodes }, PatchType::RemoveNode) if same_target => {
+            match borrowed_nodes(nodes) {
+                Some(replacement) => replacement,
+                None => return Some(patch),
+            }
+        }
+        (PatchType::InsertAfterNode { nodes }, PatchType::RemoveNode) if same_target => {
+            std::mem::take(nodes)
+        }
+        // removing a node then inserting beside its sibling is replacing it,
+        // when no other patch inserts or moves nodes beside both of them
+        (PatchType::RemoveNode, PatchType::InsertBeforeNode { nodes })
+            if is_sibling(&last.patch_path, &patch.patch_path, 1)
+                && is_only_referred_by_itself(&last.patch_path)
+                && is_only_referred_by_itself(&patch.patch_path) =>
+        {
+            match borrowed_nodes(nodes) {
+                Some(replacement) => replacement,
+                None => return Some(patch),
+            }
+        }
+        (PatchType::RemoveNode, PatchType::InsertAfterNode { nodes })
+            if is_sibling(&patch.patch_path, &last.patch_path, 1)
+                && is_only_referred_by_itself(&last.patch_path)
+                && is_only_referred_by_itself(&patch.patch_path) =>
+        {
+            std::mem::take(nodes)
+        }
+        _ => return Some(patch),
+    };
+    *last = Patch::replace_node(last.tag, last.patch_path.clone(), replacement);
+    None
+}
+
+/// moving the nodes before or after the target when they are already there,
+/// and no other patch inserts or moves nodes beside them
+fn is_redundant_move<MSG>(
+    patch: &Patch<MSG>,
+    is_only_referred_by_itself: impl Fn(&TreePath) -> bool,
+) -> bool {
+    let target = patch.path();
+    let (nodes_path, after) = match &patch.patch_type {
+        PatchType::MoveBeforeNode { nodes_path } => (nodes_path, false),
+        PatchType::MoveAfterNode { nodes_path } => (nodes_path, true),
+        _ => return false,
+    };
+    let count = nodes_path.len();
+    let in_place = nodes_path.iter().enumerate().all(|(i, path)| {
+        if after {
+            is_sibling(target, path, i + 1)
+        } else {
+            is_sibling(path, target, count - i)
+        }
+    });
+    in_place
+        && !nodes_path.is_empty()
+        && std::iter::once(target)
+            .chain(nodes_path.iter())
+            .all(is_only_referred_by_itself)
+}
+
+/// returns true if `next` is the `n`th sibling after `prev`
+fn is_sibling(prev: &TreePath, next: &TreePath, n: usize) -> bool {
+    match (prev.path.split_last(), next.path.split_last()) {
+        (Some((prev_idx, prev_parent)), Some((next_idx, next_parent))) => {
+            prev_parent == next_parent && prev_idx + n == *next_idx
+        }
+        _ => false,
+    }
+}
+
+fn is_disjoint(paths: &[TreePath], other: &[TreePath]) -> bool {
+    !paths.iter().any(|path| other.contains(path))
+}
+
+/// the nodes, if all of them are borrowed
+fn borrowed_nodes<'a, MSG>(nodes: &[Cow<'a, Node<MSG>>]) -> Option<Vec<&'a Node<MSG>>> {
+    nodes
+        .iter()
+        .map(|node| match node {
+            Cow::Borrowed(node) => Some(*node),
+            Cow::Owned(_) => None,
+        })
+        .collect()
+}
@@ -58,6 +58,7 @@ use std::fmt::Debug;
 ///    7 = [1,2]
 /// ```
 #[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "with-serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TreePath {
     /// An array of child index at each level of the dom tree.
     /// The children of the nodes at each child index is traverse
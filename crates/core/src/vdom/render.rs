@@ -1,6 +1,10 @@
 //! This contains a trait to be able to render
 //! virtual dom into a writable buffer
 //!
+//! Text, attribute values, comments and doctype are escaped accordingly to where they are
+//! placed in the document, so user supplied strings can not inject html into the rendered output.
+//! Only `Leaf::Symbol` is written as is, since it is meant for html entities such as `&nbsp;`.
+//!
 use crate::vdom::Style;
 use crate::vdom::Value;
 use crate::{
@@ -9,6 +13,11 @@ use crate::{
 };
 use std::fmt;
 
+pub use stream::{FlushPolicy, RenderChunks};
+
+pub(crate) mod escape;
+mod stream;
+
 const DEFAULT_INDENT_SIZE: usize = 2;
 
 /// add an indent if applicable
@@ -48,10 +57,7 @@ impl<MSG> Node<MSG> {
         indent: usize,
         compressed: bool,
     ) -> fmt::Result {
-        match self {
-            Node::Element(element) => element.render_with_indent(buffer, indent, compressed),
-            Node::Leaf(leaf) => leaf.render_with_indent(buffer, indent, compressed),
-        }
+        Renderer::from_node(self, indent, compressed).render_all(buffer)
     }
 
     /// render the node to a writable buffer
@@ -87,37 +93,7 @@ impl<MSG> Leaf<MSG> {
         indent: usize,
         compressed: bool,
     ) -> fmt::Result {
-        match self {
-            Leaf::Text(text) => {
-                write!(buffer, "{text}")
-            }
-            Leaf::Symbol(symbol) => {
-                write!(buffer, "{symbol}")
-            }
-            Leaf::Comment(comment) => {
-                write!(buffer, "<!--{comment}-->")
-            }
-            Leaf::DocType(doctype) => {
-                write!(buffer, "<!doctype {doctype}>")
-            }
-            Leaf::Fragment(nodes) => {
-                for node in nodes {
-                    node.render_with_indent(buffer, indent, compressed)?;
-                }
-                Ok(())
-            }
-            Leaf::NodeList(node_list) => {
-                for node in node_list {
-                    node.render_with_indent(buffer, indent, compressed)?;
-                }
-                Ok(())
-            }
-            Leaf::StatefulComponent(_comp) => {
-                write!(buffer, "<!-- stateful component -->")
-            }
-            Leaf::StatelessComponent(comp) => comp.view.render(buffer),
-            Leaf::TemplatedView(view) => view.view.render(buffer),
-        }
+        Renderer::from_leaf(self, indent, compressed).render_all(buffer)
     }
 }
 
@@ -129,6 +105,11 @@ impl<MSG> Element<MSG> {
         indent: usize,
         compressed: bool,
     ) -> fmt::Result {
+        Renderer::from_element(self, indent, compressed).render_all(buffer)
+    }
+
+    /// write the opening tag of this element together with its attributes
+    fn render_start_tag(&self, buffer: &mut dyn fmt::Write) -> fmt::Result {
         write!(buffer, "<{}", self.tag())?;
 
         let merged_attributes: Vec<Attribute<MSG>> =
@@ -140,37 +121,252 @@ impl<MSG> Element<MSG> {
         }
 
         if self.self_closing {
-            write!(buffer, "/>")?;
+            write!(buffer, "/>")
         } else {
-            write!(buffer, ">")?;
+            write!(buffer, ">")
         }
+    }
 
-        let children = self.children();
-        let first_child = children.first();
-        let is_first_child_text_node = first_child.map(|node| node.is_text()).unwrap_or(false);
+    /// write the closing tag of this element, if it has one
+    fn render_end_tag(&self, buffer: &mut dyn fmt::Write) -> fmt::Result {
+        if !self.self_closing {
+            write!(buffer, "</{}>", self.tag())?;
+        }
+        Ok(())
+    }
+}
 
-        let is_lone_child_text_node = children.len() == 1 && is_first_child_text_node;
+/// A unit of work in rendering a node tree
+enum Work<'a, MSG> {
+    Node {
+        node: &'a Node<MSG>,
+        indent: usize,
+        compressed: bool,
+    },
+    Leaf {
+        leaf: &'a Leaf<MSG>,
+        indent: usize,
+        compressed: bool,
+    },
+    Element {
+        element: &'a Element<MSG>,
+        indent: usize,
+        compressed: bool,
+    },
+    Indent {
+        indent: usize,
+        compressed: bool,
+    },
+    EndTag {
+        element: &'a Element<MSG>,
+    },
+}
 
-        // do not indent if it is only text child node
-        if is_lone_child_text_node {
-            first_child
-                .unwrap()
-                .render_with_indent(buffer, indent, compressed)?;
-        } else {
-            // otherwise print all child nodes with each line and indented
-            for child in self.children() {
-                maybe_indent(buffer, indent + 1, compressed)?;
-                child.render_with_indent(buffer, indent + 1, compressed)?;
+/// Renders a node tree one piece at a time.
+///
+/// The work that is yet to be done is kept in a stack instead of the call stack,
+/// so the rendering can be paused in between pieces, which is used for streaming
+/// the rendered html.
+pub(crate) struct Renderer<'a, MSG> {
+    stack: Vec<Work<'a, MSG>>,
+}
+
+impl<'a, MSG> Renderer<'a, MSG> {
+    pub(crate) fn from_node(node: &'a Node<MSG>, indent: usize, compressed: bool) -> Self {
+        Self {
+            stack: vec![Work::Node {
+                node,
+                indent,
+                compressed,
+            }],
+        }
+    }
+
+    fn from_leaf(leaf: &'a Leaf<MSG>, indent: usize, compressed: bool) -> Self {
+        Self {
+            stack: vec![Work::Leaf {
+                leaf,
+                indent,
+                compressed,
+            }],
+        }
+    }
+
+    fn from_element(element: &'a Element<MSG>, indent: usize, compressed: bool) -> Self {
+        Self {
+            stack: vec![Work::Element {
+                element,
+                indent,
+                compressed,
+            }],
+        }
+    }
+
+    /// returns true if there is nothing left to render
+    pub(crate) fn is_done(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    /// render everything that is left into the buffer
+    pub(crate) fn render_all(mut self, buffer: &mut dyn fmt::Write) -> fmt::Result {
+        while !self.is_done() {
+            self.render_next(buffer)?;
+        }
+        Ok(())
+    }
+
+    /// render the next piece of the node tree into the buffer.
+    /// Returns the element if this piece is the closing of that element.
+    pub(crate) fn render_next(
+        &mut self,
+        buffer: &mut dyn fmt::Write,
+    ) -> Result<Option<&'a Element<MSG>>, fmt::Error> {
+        let Some(work) = self.stack.pop() else {
+            return Ok(None);
+        };
+        match work {
+            Work::Node {
+                node,
+                indent,
+                compressed,
+            } => match node {
+                Node::Element(element) => self.stack.push(Work::Element {
+                    element,
+                    indent,
+                    compressed,
+                }),
+                Node::Leaf(leaf) => self.stack.push(Work::Leaf {
+                    leaf,
+                    indent,
+                    compressed,
+                }),
+            },
+            Work::Leaf {
+                leaf,
+                indent,
+                compressed,
+            } => self.render_leaf(buffer, leaf, indent, compressed)?,
+            Work::Element {
+                element,
+                indent,
+                compressed,
+            } => self.render_element(buffer, element, indent, compressed)?,
+            Work::Indent { indent, compressed } => maybe_indent(buffer, indent, compressed)?,
+            Work::EndTag { element } => {
+                element.render_end_tag(buffer)?;
+                return Ok(Some(element));
             }
         }
+        Ok(None)
+    }
 
-        // do not make a new line it if is only a text child node or it has no child nodes
-        if !is_lone_child_text_node && !children.is_empty() {
-            maybe_indent(buffer, indent, compressed)?;
+    fn render_leaf(
+        &mut self,
+        buffer: &mut dyn fmt::Write,
+        leaf: &'a Leaf<MSG>,
+        indent: usize,
+        compressed: bool,
+    ) -> fmt::Result {
+        match leaf {
+            Leaf::Text(text) => escape::write_text(buffer, text),
+            Leaf::Symbol(symbol) => {
+                write!(buffer, "{symbol}")
+            }
+            Leaf::Comment(comment) => {
+                write!(buffer, "<!--")?;
+                escape::write_comment(buffer, comment)?;
+                write!(buffer, "-->")
+            }
+            Leaf::DocType(doctype) => {
+                write!(buffer, "<!doctype ")?;
+                escape::write_doctype(buffer, doctype)?;
+                write!(buffer, ">")
+            }
+            Leaf::Fragment(nodes) | Leaf::NodeList(nodes) => {
+                self.stack.extend(nodes.iter().rev().map(|node| Work::Node {
+                    node,
+                    indent,
+                    compressed,
+                }));
+                Ok(())
+            }
+            Leaf::StatefulComponent(_comp) => {
+                write!(buffer, "<!-- stateful component -->")
+            }
+            Leaf::StatelessComponent(comp) => {
+                self.push_view(&comp.view);
+                Ok(())
+            }
+            Leaf::TemplatedView(view) => {
+                self.push_view(&view.view);
+                Ok(())
+            }
+            // the lazy view is rendered in place, the same as when it is not lazy
+            Leaf::Lazy(lazy) => {
+                self.stack.push(Work::Node {
+                    node: lazy.view(),
+                    indent,
+                    compressed,
+                });
+                Ok(())
+            }
         }
+    }
 
-        if !self.self_closing {
-            write!(buffer, "</{}>", self.tag())?;
+    /// views of components are rendered on their own, starting with no indent
+    fn push_view(&mut self, view: &'a Node<MSG>) {
+        self.stack.push(Work::Node {
+            node: view,
+            indent: 0,
+            compressed: false,
+        });
+    }
+
+    fn render_element(
+        &mut self,
+        buffer: &mut dyn fmt::Write,
+        element: &'a Element<MSG>,
+        indent: usize,
+        compressed: bool,
+    ) -> fmt::Result {
+        element.render_start_tag(buffer)?;
+
+        let children = element.children();
+        let first_child = children.first();
+        let is_first_child_text_node = first_child.map(|node| node.is_text()).unwrap_or(false);
+
+        let is_lone_child_text_node = children.len() == 1 && is_first_child_text_node;
+
+        self.stack.push(Work::EndTag { element });
+
+        if escape::is_raw_text_element(element.tag()) {
+            // the content of script and style is taken literally by the browser,
+            // so only the text is written and without indentation
+            for text in children.iter().filter_map(|child| child.as_text()) {
+                escape::write_raw_text(buffer, text)?;
+            }
+        } else if is_lone_child_text_node {
+            // do not indent if it is only text child node
+            self.stack.push(Work::Node {
+                node: first_child.unwrap(),
+                indent,
+                compressed,
+            });
+        } else if !children.is_empty() {
+            // do not make a new line before the closing tag if it has no child nodes
+            self.stack.push(Work::Indent { indent, compressed });
+            // otherwise print all child nodes with each line and indented
+            for child in children.iter().rev() {
+                self.stack.push(Work::Node {
+                    node: child,
+                    indent: indent + 1,
+                    compressed,
+                });
+                self.stack.push(Work::Indent {
+                    indent: indent + 1,
+                    compressed,
+                });
+            }
         }
         Ok(())
     }
@@ -204,10 +400,14 @@ impl<MSG> Attribute<MSG> {
 
         if !should_skip_attribute {
             if let Some(merged_plain_values) = Value::merge_to_string(plain_values) {
-                write!(buffer, "{}=\"{}\"", self.name(), merged_plain_values)?;
+                write!(buffer, "{}=\"", self.name())?;
+                escape::write_attribute_value(buffer, &merged_plain_values)?;
+                write!(buffer, "\"")?;
             }
             if let Some(merged_styles) = Style::merge_to_string(styles) {
-                write!(buffer, "{}=\"{}\"", self.name(), merged_styles)?;
+                write!(buffer, "{}=\"", self.name())?;
+                escape::write_attribute_value(buffer, &merged_styles)?;
+                write!(buffer, "\"")?;
             }
         }
         Ok(())
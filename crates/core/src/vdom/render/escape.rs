@@ -0,0 +1,167 @@
+//! Escaping rules used when rendering the virtual dom into html.
+//!
+//! Each position in an html document has its own set of characters that can break out of it,
+//! so there is a dedicated function for each of them.
+//! <https://html.spec.whatwg.org/multipage/syntax.html#writing>
+use std::fmt;
+
+/// Elements whose text content is not parsed for character references by the browser.
+/// The text content of these elements are written as is, except for sequences that would
+/// close the element prematurely.
+///
+/// <https://html.spec.whatwg.org/multipage/syntax.html#raw-text-elements>
+const RAW_TEXT_ELEMENTS: [&str; 2] = ["script", "style"];
+
+/// returns true if the text content of this tag is not escaped with character references
+pub(crate) fn is_raw_text_element(tag: &str) -> bool {
+    RAW_TEXT_ELEMENTS
+        .iter()
+        .any(|raw| raw.eq_ignore_ascii_case(tag))
+}
+
+/// escape the text content of a normal element
+pub(crate) fn write_text(buffer: &mut dyn fmt::Write, text: &str) -> fmt::Result {
+    write_replaced(buffer, text, |ch| match ch {
+        '&' => Some("&amp;"),
+        '<' => Some("&lt;"),
+        '>' => Some("&gt;"),
+        _ => None,
+    })
+}
+
+/// escape the value of an attribute, the value is assumed to be enclosed in double quotes
+pub(crate) fn write_attribute_value(buffer: &mut dyn fmt::Write, value: &str) -> fmt::Result {
+    write_replaced(buffer, value, |ch| match ch {
+        '&' => Some("&amp;"),
+        '"' => Some("&quot;"),
+        '<' => Some("&lt;"),
+        '>' => Some("&gt;"),
+        _ => None,
+    })
+}
+
+/// Write the text content of a raw text element such as `script` and `style`.
+///
+/// Character references are not decoded inside these elements, so the text is written as is.
+/// The only thing that can break out of it is an end tag or the start of a comment, so
+/// `</` is written as `<\/` and `<!--` as `<\!--`, which are equivalent in both javascript strings and css.
+pub(crate) fn write_raw_text(buffer: &mut dyn fmt::Write, text: &str) -> fmt::Result {
+    let mut rest = text;
+    while let Some(pos) = rest.find('<') {
+        buffer.write_str(&rest[..pos])?;
+        let after = &rest[pos + 1..];
+        if after.starts_with('/') || after.starts_with("!--") {
+            buffer.write_str("<\\")?;
+        } else {
+            buffer.write_str("<")?;
+        }
+        rest = after;
+    }
+    buffer.write_str(rest)
+}
+
+/// Write the content of a comment.
+///
+/// A comment can not contain `--` since it could close the comment with `-->` or `--!>`,
+/// and it can not start with `>` or `->`.
+/// Consecutive dashes are separated with a space and a leading `>` is prefixed with a space.
+///
+/// <https://html.spec.whatwg.org/multipage/syntax.html#comments>
+pub(crate) fn write_comment(buffer: &mut dyn fmt::Write, comment: &str) -> fmt::Result {
+    if comment.starts_with('>') || comment.starts_with("->") {
+        buffer.write_char(' ')?;
+    }
+    let mut prev_dash = false;
+    for ch in comment.chars() {
+        if ch == '-' && prev_dash {
+            buffer.write_char(' ')?;
+        }
+        buffer.write_char(ch)?;
+        prev_dash = ch == '-';
+    }
+    // a trailing dash would be merged with the closing `-->`
+    if prev_dash {
+        buffer.write_char(' ')?;
+    }
+    Ok(())
+}
+
+/// Write the content of a doctype, `<` and `>` is not allowed in here and are removed.
+pub(crate) fn write_doctype(buffer: &mut dyn fmt::Write, doctype: &str) -> fmt::Result {
+    for ch in doctype.chars().filter(|ch| *ch != '<' && *ch != '>') {
+        buffer.write_char(ch)?;
+    }
+    Ok(())
+}
+
+/// write the text replacing the characters that has a replacement
+fn write_replaced(
+    buffer: &mut dyn fmt::Write,
+    text: &str,
+    replacement: impl Fn(char) -> Option<&'static str>,
+) -> fmt::Result {
+    let mut last = 0;
+    for (i, ch) in text.char_indices() {
+        if let Some(replace) = replacement(ch) {
+            buffer.write_str(&text[last..i])?;
+            buffer.write_str(replace)?;
+            last = i + ch.len_utf8();
+        }
+    }
+    buffer.write_str(&text[last..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn escaped(f: fn(&mut dyn fmt::Write, &str) -> fmt::Result, s: &str) -> String {
+        let mut buffer = String::new();
+        f(&mut buffer, s).expect("must write");
+        buffer
+    }
+
+    #[test]
+    fn text_is_escaped() {
+        assert_eq!(
+            escaped(write_text, "<b>Tom & \"Jerry\"</b>"),
+            "&lt;b&gt;Tom &amp; \"Jerry\"&lt;/b&gt;"
+        );
+    }
+
+    #[test]
+    fn attribute_value_is_escaped() {
+        assert_eq!(
+            escaped(write_attribute_value, r#"x" onload="alert(1)"#),
+            "x&quot; onload=&quot;alert(1)"
+        );
+    }
+
+    #[test]
+    fn raw_text_can_not_be_closed() {
+        assert_eq!(
+            escaped(
+                write_raw_text,
+                "let a = '</script><script>alert(1)</script>';"
+            ),
+            r#"let a = '<\/script><script>alert(1)<\/script>';"#
+        );
+        assert_eq!(escaped(write_raw_text, "if (a < b) {}"), "if (a < b) {}");
+        assert_eq!(escaped(write_raw_text, "<!-- x"), r#"<\!-- x"#);
+    }
+
+    #[test]
+    fn comment_can_not_be_closed() {
+        assert_eq!(escaped(write_comment, "--><script>"), "- -><script>");
+        assert_eq!(escaped(write_comment, "a---b"), "a- - -b");
+        assert_eq!(escaped(write_comment, ">x"), " >x");
+        assert_eq!(escaped(write_comment, "->x"), " ->x");
+        assert_eq!(escaped(write_comment, "x-"), "x- ");
+        assert_eq!(escaped(write_comment, "separator"), "separator");
+    }
+
+    #[test]
+    fn doctype_can_not_be_closed() {
+        assert_eq!(escaped(write_doctype, "html><script>"), "htmlscript");
+    }
+}
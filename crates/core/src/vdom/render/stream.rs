@@ -0,0 +1,136 @@
+//! Streaming of the rendered html in chunks, so the top part of the document can be sent
+//! to the client while the rest of the node tree is still being rendered.
+//!
+//! The chunks are produced by the same renderer as `Node::render_to_string`,
+//! so the output is exactly the same when the chunks are joined together.
+use super::Renderer;
+use crate::vdom::{Node, Tag};
+use futures::stream::{self, Stream};
+use std::io;
+use std::mem;
+
+/// Specifies the points where the rendered html is flushed as a chunk.
+///
+/// The remaining html is always flushed at the end of the render.
+#[derive(Debug, Clone, Default)]
+pub struct FlushPolicy {
+    /// flush right after the closing tag of these elements
+    after_tags: Vec<Tag>,
+    /// flush as soon as the rendered html reaches this number of bytes
+    chunk_size: Option<usize>,
+}
+
+impl FlushPolicy {
+    /// a policy with no flush points, the whole html is rendered in 1 chunk
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// flush right after the closing tag of element with this `tag`, ie: `head`
+    pub fn flush_after(mut self, tag: Tag) -> Self {
+        self.after_tags.push(tag);
+        self
+    }
+
+    /// flush once the rendered html reaches at least `chunk_size` bytes
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = Some(chunk_size);
+        self
+    }
+
+    fn should_flush_after(&self, tag: &str) -> bool {
+        self.after_tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    fn is_chunk_full(&self, buffer: &str) -> bool {
+        self.chunk_size
+            .map(|chunk_size| buffer.len() >= chunk_size)
+            .unwrap_or(false)
+    }
+}
+
+/// The rendered html of a node tree, produced in chunks at the flush points of the [`FlushPolicy`].
+///
+/// Each chunk is only rendered when it is requested.
+pub struct RenderChunks<'a, MSG> {
+    renderer: Renderer<'a, MSG>,
+    policy: FlushPolicy,
+    buffer: String,
+}
+
+impl<'a, MSG> Iterator for RenderChunks<'a, MSG> {
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.renderer.is_done() {
+            let closed = self
+                .renderer
+                .render_next(&mut self.buffer)
+                .expect("must render");
+            let is_flush_point = closed
+                .map(|element| self.policy.should_flush_after(element.tag()))
+                .unwrap_or(false);
+            if is_flush_point || self.policy.is_chunk_full(&self.buffer) {
+                break;
+            }
+        }
+        if self.buffer.is_empty() {
+            None
+        } else {
+            Some(mem::take(&mut self.buffer).into_bytes())
+        }
+    }
+}
+
+impl<MSG> Node<MSG> {
+    /// render compressed html in chunks which are cut at the flush points of the `policy`
+    pub fn render_chunks(&self, policy: FlushPolicy) -> RenderChunks<'_, MSG> {
+        RenderChunks {
+            renderer: Renderer::from_node(self, 0, true),
+            policy,
+            buffer: String::new(),
+        }
+    }
+
+    /// render compressed html as a stream of chunks which are cut at the flush points of the `policy`
+    pub fn render_stream(&self, policy: FlushPolicy) -> impl Stream<Item = Vec<u8>> + '_ {
+        stream::iter(self.render_chunks(policy))
+    }
+
+    /// render compressed html into the `writer`, the writer is flushed at each of the flush points
+    /// of the `policy`
+    pub fn render_to_writer(
+        &self,
+        writer: &mut dyn io::Write,
+        policy: FlushPolicy,
+    ) -> io::Result<()> {
+        for chunk in self.render_chunks(policy) {
+            writer.write_all(&chunk)?;
+            writer.flush()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::html::{attributes::*, *};
+    use futures::StreamExt;
+
+    #[test]
+    fn stream_produces_the_same_chunks_as_iterator() {
+        let view: Node<()> = html(
+            [],
+            [
+                head([], [tags::title([], [text("list")])]),
+                body([], [ul([class("list")], (0..3).map(|i| li([], [text(i)])))]),
+            ],
+        );
+        let policy = FlushPolicy::new().flush_after("head").chunk_size(8);
+        let chunks: Vec<Vec<u8>> =
+            futures::executor::block_on(view.render_stream(policy.clone()).collect());
+        assert_eq!(chunks, view.render_chunks(policy).collect::<Vec<_>>());
+        assert_eq!(chunks.concat(), view.render_to_string().into_bytes());
+    }
+}
@@ -0,0 +1,587 @@
+//! Find nodes in a virtual node tree using css selectors, the same way `querySelector` finds elements in the DOM.
+//!
+//! The supported selectors are the tag, `*`, `#id`, `.class`, the attribute selectors
+//! `[name]`, `[name=value]`, `[name~=value]`, `[name^=value]`, `[name$=value]`, `[name*=value]`,
+//! `:nth-child(an+b)`, and the descendant and child (`>`) combinators.
+//! Selectors can be grouped with `,`.
+use crate::vdom::{AttributeValue, Element, Leaf, Node, TreePath};
+use std::str::FromStr;
+
+/// An error while parsing a css selector
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SelectorError {
+    /// the selector or one of the grouped selectors is empty
+    #[error("empty selector")]
+    Empty,
+    /// an unexpected character at this position of the selector
+    #[error("unexpected character: {found:?} at position: {position}")]
+    Unexpected {
+        /// the unexpected character
+        found: char,
+        /// the char position in the selector
+        position: usize,
+    },
+    /// the selector ended before an attribute selector or pseudo class is closed
+    #[error("unexpected end of selector")]
+    UnexpectedEnd,
+    /// the argument of `:nth-child` is not in the form of `an+b`, `odd` or `even`
+    #[error("invalid :nth-child argument: {0:?}")]
+    InvalidNth(String),
+    /// only `:nth-child` is supported
+    #[error("unsupported pseudo class: {0:?}")]
+    UnsupportedPseudoClass(String),
+}
+
+/// A parsed css selector, which can be grouped selectors separated by `,`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector {
+    complexes: Vec<Complex>,
+}
+
+/// compound selectors joined with combinators, ie: `ul.todo > li`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Complex {
+    compounds: Vec<Compound>,
+    /// the combinator between `compounds[i]` and `compounds[i + 1]`
+    combinators: Vec<Combinator>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Combinator {
+    Descendant,
+    Child,
+}
+
+/// the simple selectors that must all match the same element, ie: `li.done[data-id]`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+struct Compound {
+    tag: Option<String>,
+    simples: Vec<Simple>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Simple {
+    Id(String),
+    Class(String),
+    Attribute {
+        name: String,
+        matcher: Option<(Operator, String)>,
+    },
+    NthChild {
+        a: i64,
+        b: i64,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    /// `=`
+    Equal,
+    /// `~=`
+    Includes,
+    /// `^=`
+    Prefix,
+    /// `$=`
+    Suffix,
+    /// `*=`
+    Substring,
+}
+
+/// an element being matched, with its position among the sibling elements
+struct Candidate<'a, MSG> {
+    element: &'a Element<MSG>,
+    /// 1 based index of this element among the sibling elements
+    nth: usize,
+}
+
+impl FromStr for Selector {
+    type Err = SelectorError;
+
+    fn from_str(selector: &str) -> Result<Self, Self::Err> {
+        Parser::new(selector).parse()
+    }
+}
+
+impl Selector {
+    /// parse the css selector
+    pub fn parse(selector: &str) -> Result<Self, SelectorError> {
+        selector.parse()
+    }
+
+    fn matches<MSG>(&self, target: &Candidate<MSG>, ancestors: &[Candidate<MSG>]) -> bool {
+        self.complexes
+            .iter()
+            .any(|complex| complex.matches(target, ancestors))
+    }
+
+    /// return the nodes matching this selector and their paths in depth first order,
+    /// the `root` node is included
+    pub fn select_all<'a, MSG>(&self, root: &'a Node<MSG>) -> Vec<(&'a Node<MSG>, TreePath)> {
+        let mut found = vec![];
+        let mut ancestors = vec![];
+        self.select_in(root, TreePath::root(), 1, &mut ancestors, &mut found, false);
+        found
+    }
+
+    /// return the first node matching this selector and its path
+    pub fn select<'a, MSG>(&self, root: &'a Node<MSG>) -> Option<(&'a Node<MSG>, TreePath)> {
+        let mut found = vec![];
+        let mut ancestors = vec![];
+        self.select_in(root, TreePath::root(), 1, &mut ancestors, &mut found, true);
+        found.pop()
+    }
+
+    /// returns true when only the first node is needed and it is found
+    fn select_in<'a, MSG>(
+        &self,
+        node: &'a Node<MSG>,
+        path: TreePath,
+        nth: usize,
+        ancestors: &mut Vec<Candidate<'a, MSG>>,
+        found: &mut Vec<(&'a Node<MSG>, TreePath)>,
+        first_only: bool,
+    ) -> bool {
+        let node = materialized(node);
+        match node {
+            Node::Element(element) => {
+                let candidate = Candidate { element, nth };
+                if self.matches(&candidate, ancestors) {
+                    found.push((node, path.clone()));
+                    if first_only {
+                        return true;
+                    }
+                }
+                ancestors.push(candidate);
+                let done = self.select_children(
+                    element.children(),
+                    &path,
+                    &mut 0,
+                    ancestors,
+                    found,
+                    first_only,
+                );
+                ancestors.pop();
+                done
+            }
+            Node::Leaf(Leaf::Fragment(nodes) | Leaf::NodeList(nodes)) => {
+                self.select_children(nodes, &path, &mut 0, ancestors, found, first_only)
+            }
+            Node::Leaf(_) => false,
+        }
+    }
+
+    /// `nth` is the count of the sibling elements before these children
+    fn select_children<'a, MSG>(
+        &self,
+        children: &'a [Node<MSG>],
+        path: &TreePath,
+        nth: &mut usize,
+        ancestors: &mut Vec<Candidate<'a, MSG>>,
+        found: &mut Vec<(&'a Node<MSG>, TreePath)>,
+        first_only: bool,
+    ) -> bool {
+        for (index, child) in children.iter().enumerate() {
+            let child_path = path.traverse(index);
+            let done = match materialized(child) {
+                // the children of fragments and node lists are inlined into the parent in the DOM,
+                // so they are counted along with the siblings of the fragment
+                Node::Leaf(Leaf::Fragment(nodes) | Leaf::NodeList(nodes)) => {
+                    self.select_children(nodes, &child_path, nth, ancestors, found, first_only)
+                }
+                child => {
+                    if child.is_element() {
+                        *nth += 1;
+                    }
+                    self.select_in(child, child_path, *nth, ancestors, found, first_only)
+                }
+            };
+            if done {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// the node which is in place of this node in the DOM, ie: the view of a component
+pub(crate) fn materialized<MSG>(node: &Node<MSG>) -> &Node<MSG> {
+    match node {
+        Node::Leaf(Leaf::StatelessComponent(comp)) => materialized(&comp.view),
+        Node::Leaf(Leaf::TemplatedView(view)) => materialized(&view.view),
+        Node::Leaf(Leaf::Lazy(lazy)) => materialized(lazy.view()),
+        _ => node,
+    }
+}
+
+/// find the node at the `path` the same way the paths of the selected nodes are formed,
+/// where the views of components are in place of the components
+pub(crate) fn find_by_path<'a, MSG>(root: &'a Node<MSG>, path: &TreePath) -> Option<&'a Node<MSG>> {
+    let mut node = materialized(root);
+    for index in path.path.iter() {
+        let children = match node {
+            Node::Element(element) => element.children(),
+            Node::Leaf(Leaf::Fragment(nodes) | Leaf::NodeList(nodes)) => nodes,
+            Node::Leaf(_) => return None,
+        };
+        node = materialized(children.get(*index)?);
+    }
+    Some(node)
+}
+
+impl Complex {
+    fn matches<MSG>(&self, target: &Candidate<MSG>, ancestors: &[Candidate<MSG>]) -> bool {
+        let last = self.compounds.len() - 1;
+        self.compounds[last].matches(target) && self.matches_ancestors(last, ancestors)
+    }
+
+    /// the compounds before the `n`th compound must match the ancestors
+    fn matches_ancestors<MSG>(&self, n: usize, ancestors: &[Candidate<MSG>]) -> bool {
+        if n == 0 {
+            return true;
+        }
+        let compound = &self.compounds[n - 1];
+        match self.combinators[n - 1] {
+            Combinator::Child => match ancestors.split_last() {
+                Some((parent, rest)) => {
+                    compound.matches(parent) && self.matches_ancestors(n - 1, rest)
+                }
+                None => false,
+            },
+            Combinator::Descendant => (0..ancestors.len()).rev().any(|i| {
+                compound.matches(&ancestors[i]) && self.matches_ancestors(n - 1, &ancestors[..i])
+            }),
+        }
+    }
+}
+
+impl Compound {
+    fn matches<MSG>(&self, candidate: &Candidate<MSG>) -> bool {
+        let element = candidate.element;
+        if let Some(tag) = &self.tag {
+            if !element.tag().eq_ignore_ascii_case(tag) {
+                return false;
+            }
+        }
+        self.simples.iter().all(|simple| match simple {
+            Simple::Id(id) => attribute_string(element, "id").as_deref() == Some(id.as_str()),
+            Simple::Class(class) => attribute_string(element, "class")
+                .is_some_and(|classes| classes.split_whitespace().any(|c| c == class)),
+            Simple::Attribute { name, matcher } => {
+                let Some(value) = attribute_string(element, name) else {
+                    return false;
+                };
+                match matcher {
+                    None => true,
+                    Some((Operator::Equal, expected)) => value == *expected,
+                    Some((Operator::Includes, expected)) => {
+                        value.split_whitespace().any(|v| v == expected)
+                    }
+                    Some((Operator::Prefix, expected)) => {
+                        !expected.is_empty() && value.starts_with(expected.as_str())
+                    }
+                    Some((Operator::Suffix, expected)) => {
+                        !expected.is_empty() && value.ends_with(expected.as_str())
+                    }
+                    Some((Operator::Substring, expected)) => {
+                        !expected.is_empty() && value.contains(expected.as_str())
+                    }
+                }
+            }
+            Simple::NthChild { a, b } => {
+                let offset = candidate.nth as i64 - b;
+                if *a == 0 {
+                    offset == 0
+                } else {
+                    offset % a == 0 && offset / a >= 0
+                }
+            }
+        })
+    }
+}
+
+/// the plain values of the attribute merged into a string, the same as rendered
+pub(crate) fn attribute_string<MSG>(element: &Element<MSG>, name: &str) -> Option<String> {
+    let values = element
+        .attributes()
+        .iter()
+        .filter(|attr| attr.name == name)
+        .flat_map(|attr| attr.value.iter())
+        .filter_map(|v| match v {
+            AttributeValue::Simple(v) => Some(v.to_string()),
+            AttributeValue::Style(styles) => {
+                Some(styles.iter().map(|s| format!("{s};")).collect::<String>())
+            }
+            AttributeValue::Empty => Some(String::new()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.join(" "))
+    }
+}
+
+struct Parser<'s> {
+    chars: Vec<char>,
+    position: usize,
+    selector: &'s str,
+}
+
+impl<'s> Parser<'s> {
+    fn new(selector: &'s str) -> Self {
+        Self {
+            chars: selector.chars().collect(),
+            position: 0,
+            selector,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn unexpected(&self) -> SelectorError {
+        match self.peek() {
+            Some(found) => SelectorError::Unexpected {
+                found,
+                position: self.position,
+            },
+            None => SelectorError::UnexpectedEnd,
+        }
+    }
+
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.position;
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+        self.position > start
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), SelectorError> {
+        if self.peek() == Some(expected) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn parse(mut self) -> Result<Selector, SelectorError> {
+        if self.selector.trim().is_empty() {
+            return Err(SelectorError::Empty);
+        }
+        let mut complexes = vec![self.parse_complex()?];
+        while self.peek() == Some(',') {
+            self.position += 1;
+            complexes.push(self.parse_complex()?);
+        }
+        match self.peek() {
+            None => Ok(Selector { complexes }),
+            Some(_) => Err(self.unexpected()),
+        }
+    }
+
+    fn parse_complex(&mut self) -> Result<Complex, SelectorError> {
+        self.skip_whitespace();
+        let mut compounds = vec![self.parse_compound()?];
+        let mut combinators = vec![];
+        loop {
+            let had_whitespace = self.skip_whitespace();
+            let combinator = match self.peek() {
+                Some('>') => {
+                    self.position += 1;
+                    self.skip_whitespace();
+                    Combinator::Child
+                }
+                Some(',') | None => break,
+                Some(_) if had_whitespace => Combinator::Descendant,
+                Some(_) => return Err(self.unexpected()),
+            };
+            combinators.push(combinator);
+            compounds.push(self.parse_compound()?);
+        }
+        Ok(Complex {
+            compounds,
+            combinators,
+        })
+    }
+
+    fn parse_compound(&mut self) -> Result<Compound, SelectorError> {
+        let mut compound = Compound::default();
+        let start = self.position;
+        if self.peek() == Some('*') {
+            self.position += 1;
+        } else if self.peek().is_some_and(is_ident_char) {
+            compound.tag = Some(self.parse_ident()?);
+        }
+        loop {
+            match self.peek() {
+                Some('#') => {
+                    self.position += 1;
+                    compound.simples.push(Simple::Id(self.parse_ident()?));
+                }
+                Some('.') => {
+                    self.position += 1;
+                    compound.simples.push(Simple::Class(self.parse_ident()?));
+                }
+                Some('[') => {
+                    self.position += 1;
+                    compound.simples.push(self.parse_attribute()?);
+                }
+                Some(':') => {
+                    self.position += 1;
+                    compound.simples.push(self.parse_pseudo_class()?);
+                }
+                _ => break,
+            }
+        }
+        if self.position == start {
+            match self.peek() {
+                Some(',') | None => Err(SelectorError::Empty),
+                Some(_) => Err(self.unexpected()),
+            }
+        } else {
+            Ok(compound)
+        }
+    }
+
+    fn parse_ident(&mut self) -> Result<String, SelectorError> {
+        let start = self.position;
+        while self.peek().is_some_and(is_ident_char) {
+            self.position += 1;
+        }
+        if self.position == start {
+            Err(self.unexpected())
+        } else {
+            Ok(self.chars[start..self.position].iter().collect())
+        }
+    }
+
+    fn parse_attribute(&mut self) -> Result<Simple, SelectorError> {
+        self.skip_whitespace();
+        let name = self.parse_ident()?;
+        self.skip_whitespace();
+        let operator = match self.peek() {
+            Some(']') => {
+                self.position += 1;
+                return Ok(Simple::Attribute {
+                    name,
+                    matcher: None,
+                });
+            }
+            Some('=') => Operator::Equal,
+            Some('~') => Operator::Includes,
+            Some('^') => Operator::Prefix,
+            Some('$') => Operator::Suffix,
+            Some('*') => Operator::Substring,
+            _ => return Err(self.unexpected()),
+        };
+        self.position += 1;
+        if operator != Operator::Equal {
+            self.expect('=')?;
+        }
+        self.skip_whitespace();
+        let value = match self.peek() {
+            Some(quote @ ('"' | '\'')) => {
+                self.position += 1;
+                let start = self.position;
+                while self.peek().is_some_and(|c| c != quote) {
+                    self.position += 1;
+                }
+                let value = self.chars[start..self.position].iter().collect();
+                self.expect(quote)?;
+                value
+            }
+            _ => self.parse_ident()?,
+        };
+        self.skip_whitespace();
+        self.expect(']')?;
+        Ok(Simple::Attribute {
+            name,
+            matcher: Some((operator, value)),
+        })
+    }
+
+    fn parse_pseudo_class(&mut self) -> Result<Simple, SelectorError> {
+        let name = self.parse_ident()?;
+        if name != "nth-child" {
+            return Err(SelectorError::UnsupportedPseudoClass(name));
+        }
+        self.expect('(')?;
+        let start = self.position;
+        while self.peek().is_some_and(|c| c != ')') {
+            self.position += 1;
+        }
+        let argument: String = self.chars[start..self.position].iter().collect();
+        self.expect(')')?;
+        let (a, b) = parse_nth(&argument).ok_or(SelectorError::InvalidNth(argument))?;
+        Ok(Simple::NthChild { a, b })
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_'
+}
+
+/// parse the `an+b` argument of `:nth-child`
+fn parse_nth(argument: &str) -> Option<(i64, i64)> {
+    let argument: String = argument
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase();
+    match argument.as_str() {
+        "odd" => return Some((2, 1)),
+        "even" => return Some((2, 0)),
+        _ => (),
+    }
+    match argument.split_once('n') {
+        Some((a, b)) => {
+            let a = match a {
+                "" | "+" => 1,
+                "-" => -1,
+                a => a.parse().ok()?,
+            };
+            let b = match b {
+                "" => 0,
+                b if b.starts_with('+') || b.starts_with('-') => {
+                    b.trim_start_matches('+').parse().ok()?
+                }
+                _ => return None,
+            };
+            Some((a, b))
+        }
+        None => Some((0, argument.parse().ok()?)),
+    }
+}
+
+impl<MSG> Node<MSG> {
+    /// return the first node matching the css `selector` and its path, searching in depth first order.
+    /// This node is also matched against the selector.
+    /// # Examples
+    /// ```rust
+    /// use sauron::{*, vdom::TreePath};
+    ///
+    /// let view: Node<()> = ul([], [li([], [text("one")]), li([class("done")], [text("two")])]);
+    /// let (node, path) = view.query_selector("ul > li.done").unwrap().unwrap();
+    /// assert_eq!(node, &li([class("done")], [text("two")]));
+    /// assert_eq!(path, TreePath::new([1]));
+    /// ```
+    pub fn query_selector(
+        &self,
+        selector: &str,
+    ) -> Result<Option<(&Node<MSG>, TreePath)>, SelectorError> {
+        Ok(Selector::parse(selector)?.select(self))
+    }
+
+    /// return all the nodes matching the css `selector` and their paths, in depth first order.
+    /// This node is also matched against the selector.
+    pub fn query_selector_all(
+        &self,
+        selector: &str,
+    ) -> Result<Vec<(&Node<MSG>, TreePath)>, SelectorError> {
+        Ok(Selector::parse(selector)?.select_all(self))
+    }
+}
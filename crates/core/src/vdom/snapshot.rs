@@ -0,0 +1,136 @@
+//! Snapshot testing of views, used by [`assert_view_snapshot!`](crate::assert_view_snapshot).
+//!
+//! The view is rendered as pretty html and compared to the stored snapshot file.
+//! On mismatch, the new rendering is written beside it with a `.new` extension to be reviewed,
+//! which is then renamed into the snapshot if it is correct.
+//! Set the environment variable `SAURON_BLESS` to overwrite all the snapshots with the new renderings.
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The environment variable which, when set, overwrites the snapshots with the new renderings
+pub const BLESS_ENV: &str = "SAURON_BLESS";
+
+/// An error when the rendered view does not match the snapshot
+#[derive(Debug, thiserror::Error)]
+pub enum SnapshotError {
+    /// there is no stored snapshot yet
+    #[error("snapshot {path:?} does not exist, review the new snapshot: {new_path:?} or set {BLESS_ENV}=1 to accept it")]
+    Missing {
+        /// the path of the snapshot
+        path: PathBuf,
+        /// the path where the new rendering is written
+        new_path: PathBuf,
+    },
+    /// the rendered view is different from the stored snapshot
+    #[error("snapshot {path:?} does not match at line {line}\nexpected: {expected}\n   found: {found}\nreview the new snapshot: {new_path:?} or set {BLESS_ENV}=1 to accept it")]
+    Mismatch {
+        /// the path of the snapshot
+        path: PathBuf,
+        /// the path where the new rendering is written
+        new_path: PathBuf,
+        /// the first line which is different, starting at 1
+        line: usize,
+        /// the line in the snapshot
+        expected: String,
+        /// the line in the new rendering
+        found: String,
+    },
+    /// reading or writing the snapshot files failed
+    #[error("snapshot io error: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// returns true if the snapshots are to be overwritten, when [`BLESS_ENV`] is set
+pub fn bless_snapshots() -> bool {
+    std::env::var_os(BLESS_ENV).is_some_and(|value| !value.is_empty() && value != "0")
+}
+
+/// Compare the `rendered` view with the snapshot `<dir>/<name>.html`.
+///
+/// When they are different, the `rendered` view is written into `<dir>/<name>.html.new`,
+/// unless `bless` is true, where the snapshot is overwritten instead.
+pub fn check_snapshot(
+    dir: impl AsRef<Path>,
+    name: &str,
+    rendered: &str,
+    bless: bool,
+) -> Result<(), SnapshotError> {
+    let dir = dir.as_ref();
+    let path = dir.join(format!("{name}.html"));
+    let new_path = dir.join(format!("{name}.html.new"));
+    // the stored files always end with a new line
+    let rendered = format!("{}\n", rendered.trim_end());
+    let stored = match fs::read_to_string(&path) {
+        Ok(stored) => Some(stored),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e.into()),
+    };
+    if stored.as_deref() == Some(rendered.as_str()) {
+        remove_if_exists(&new_path)?;
+        return Ok(());
+    }
+    fs::create_dir_all(dir)?;
+    if bless {
+        fs::write(&path, &rendered)?;
+        remove_if_exists(&new_path)?;
+        return Ok(());
+    }
+    fs::write(&new_path, &rendered)?;
+    let Some(stored) = stored else {
+        return Err(SnapshotError::Missing { path, new_path });
+    };
+    let mut stored_lines = stored.lines();
+    let mut rendered_lines = rendered.lines();
+    let mut line = 1;
+    loop {
+        match (stored_lines.next(), rendered_lines.next()) {
+            (Some(expected), Some(found)) if expected == found => line += 1,
+            (expected, found) => {
+                return Err(SnapshotError::Mismatch {
+                    path,
+                    new_path,
+                    line,
+                    expected: expected.unwrap_or("<end of file>").to_string(),
+                    found: found.unwrap_or("<end of file>").to_string(),
+                })
+            }
+        }
+    }
+}
+
+fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Assert that the view rendered as pretty html matches the snapshot in `tests/snapshots/<name>.html`
+/// of the crate being tested.
+///
+/// On mismatch, the new rendering is written into `tests/snapshots/<name>.html.new` to be reviewed.
+/// Set the environment variable `SAURON_BLESS=1` to overwrite the snapshots with the new renderings.
+///
+/// # Examples
+/// ```rust,no_run
+/// use sauron::{assert_view_snapshot, *};
+///
+/// let view: Node<()> = ul([], [li([], [text("one")]), li([], [text("two")])]);
+/// assert_view_snapshot!("list", view);
+/// ```
+#[macro_export]
+macro_rules! assert_view_snapshot {
+    ($name:expr, $node:expr $(,)?) => {
+        if let Err(err) = $crate::vdom::snapshot::check_snapshot(
+            ::std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests")
+                .join("snapshots"),
+            $name,
+            &($node).render_to_string_pretty(),
+            $crate::vdom::snapshot::bless_snapshots(),
+        ) {
+            panic!("{err}");
+        }
+    };
+}
@@ -0,0 +1,219 @@
+//! Find the problems in a node tree which would make the DOM go out of sync with it
+use crate::vdom::visit::{walk_element, walk_leaf};
+use crate::vdom::{Element, Leaf, Node, Tag, TreePath, Visitor, KEY};
+use std::collections::HashMap;
+use std::fmt;
+
+/// The elements which close an open `<p>`, when they are parsed inside it
+///
+/// <https://html.spec.whatwg.org/multipage/grouping-content.html#the-p-element>
+const CLOSES_PARAGRAPH: [&str; 29] = [
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "details",
+    "div",
+    "dl",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hgroup",
+    "hr",
+    "main",
+    "menu",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+];
+
+/// The elements where an open `<p>` is no longer looked up
+const PARAGRAPH_SCOPE: [&str; 9] = [
+    "button", "applet", "caption", "html", "table", "td", "th", "marquee", "object",
+];
+
+/// A problem found at a node of the tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// the path of the node
+    pub path: TreePath,
+    /// what is wrong with the node
+    pub reason: InvalidReason,
+}
+
+/// The reason a node is invalid
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum InvalidReason {
+    /// the node has the same key as a sibling before it, which confuses the diffing of keyed nodes
+    #[error("the key: {key:?} is the same as the key of the sibling at: {first:?}")]
+    DuplicateKey {
+        /// the key of the node
+        key: String,
+        /// the path of the sibling which has the same key
+        first: TreePath,
+    },
+    /// the self closing element has child nodes, which are not rendered
+    #[error("the self closing element <{tag}> has child nodes")]
+    ChildrenInSelfClosing {
+        /// the tag of the element
+        tag: Tag,
+    },
+    /// the element can not be inside the parent element,
+    /// the browser moves it elsewhere when the html is parsed
+    #[error("<{tag}> can not be inside <{parent}>")]
+    InvalidNesting {
+        /// the tag of the element
+        tag: Tag,
+        /// the tag of the parent element which it can not be in
+        parent: Tag,
+    },
+}
+
+impl<MSG> Node<MSG> {
+    /// Check for the problems in this node tree which make the DOM go out of sync with it:
+    /// siblings sharing the same key, child nodes in self closing elements,
+    /// and html elements which the browser moves elsewhere when parsed, such as a `<p>` inside a `<p>`
+    /// or a `<tr>` directly under a `<table>`.
+    ///
+    /// Returns the problems found in depth first order, which is empty when the node is valid.
+    /// The lazy nodes whose view is not yet built are not checked.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use sauron::{vdom::InvalidReason, html::attributes::*, *};
+    ///
+    /// let view: Node<()> = ul([], [li([key(1)], []), li([key(1)], [])]);
+    /// let errors = view.validate();
+    /// assert_eq!(errors[0].path, TreePath::new([1]));
+    /// assert!(matches!(errors[0].reason, InvalidReason::DuplicateKey { .. }));
+    /// ```
+    pub fn validate(&self) -> Vec<ValidationError> {
+        let mut validator = Validator {
+            ancestors: vec![],
+            errors: vec![],
+        };
+        validator.visit_node(self, &TreePath::root());
+        validator.errors
+    }
+}
+
+struct Validator {
+    /// the tags of the html elements enclosing the current node
+    ancestors: Vec<Tag>,
+    errors: Vec<ValidationError>,
+}
+
+impl Validator {
+    fn error(&mut self, path: TreePath, reason: InvalidReason) {
+        self.errors.push(ValidationError { path, reason });
+    }
+
+    fn check_keys<MSG>(&mut self, siblings: &[Node<MSG>], path: &TreePath) {
+        let mut keys: HashMap<String, usize> = HashMap::new();
+        for (index, sibling) in siblings.iter().enumerate() {
+            let Some(values) = sibling.attribute_value(KEY) else {
+                continue;
+            };
+            let key = values
+                .iter()
+                .filter_map(|v| v.get_simple())
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join(" ");
+            if let Some(first) = keys.get(&key) {
+                let first = path.traverse(*first);
+                self.error(
+                    path.traverse(index),
+                    InvalidReason::DuplicateKey { key, first },
+                );
+            } else {
+                keys.insert(key, index);
+            }
+        }
+    }
+
+    /// the parent element which the browser would not keep this html element in
+    fn invalid_parent(&self, tag: &Tag) -> Option<Tag> {
+        let parent = *self.ancestors.last()?;
+        let allowed: &[&str] = match parent {
+            "table" => &["caption", "colgroup", "thead", "tbody", "tfoot"],
+            "thead" | "tbody" | "tfoot" => &["tr"],
+            "tr" => &["td", "th"],
+            _ => {
+                let closes = |ancestor: &str| match *tag {
+                    "a" => ancestor == "a",
+                    tag if CLOSES_PARAGRAPH.contains(&tag) => ancestor == "p",
+                    _ => false,
+                };
+                return self
+                    .ancestors
+                    .iter()
+                    .rev()
+                    .take_while(|ancestor| !PARAGRAPH_SCOPE.contains(ancestor))
+                    .find(|ancestor| closes(ancestor))
+                    .copied();
+            }
+        };
+        let always_allowed = ["script", "template", "style"];
+        (!allowed.contains(tag) && !always_allowed.contains(tag)).then_some(parent)
+    }
+}
+
+impl<MSG> Visitor<MSG> for Validator {
+    fn visit_element(&mut self, element: &Element<MSG>, path: &TreePath) {
+        let tag = *element.tag();
+        if element.self_closing && !element.children().is_empty() {
+            self.error(path.clone(), InvalidReason::ChildrenInSelfClosing { tag });
+        }
+        // only the html elements are moved by the browser
+        let is_html = element.namespace().is_none();
+        if is_html {
+            if let Some(parent) = self.invalid_parent(&tag) {
+                self.error(path.clone(), InvalidReason::InvalidNesting { tag, parent });
+            }
+        }
+        self.check_keys(element.children(), path);
+        if is_html {
+            self.ancestors.push(tag);
+            walk_element(self, element, path);
+            self.ancestors.pop();
+        } else {
+            // the elements of other namespaces are not checked for nesting
+            let ancestors = std::mem::take(&mut self.ancestors);
+            walk_element(self, element, path);
+            self.ancestors = ancestors;
+        }
+    }
+
+    fn visit_leaf(&mut self, leaf: &Leaf<MSG>, path: &TreePath) {
+        match leaf {
+            Leaf::Fragment(nodes) | Leaf::NodeList(nodes) => self.check_keys(nodes, path),
+            // validating must not build the view of a lazy node
+            Leaf::Lazy(lazy) if !lazy.is_built() => return,
+            _ => (),
+        }
+        walk_leaf(self, leaf, path);
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid node at path: {:?}, {}",
+            self.path.path, self.reason
+        )
+    }
+}
@@ -0,0 +1,242 @@
+//! Traversal of the virtual node tree, with a [`Visitor`] for inspecting the nodes
+//! and a [`Fold`] for transforming them.
+//!
+//! The default traversal goes into the children of elements, fragments, node lists
+//! and stateful components, and into the views of stateless components, templated views and lazy nodes.
+//! The path passed along is the [`TreePath`] of the node in the DOM, where the nodes of fragments
+//! and node lists are indexed as children and the views of components are in place of the components,
+//! the same as the paths returned by the [`Selector`](crate::vdom::Selector).
+//!
+//! The traversal of each kind of node can be overridden, calling the `walk_*` or `fold_*` functions
+//! of this module to continue into the children.
+use crate::vdom::{Attribute, Element, Leaf, Node, TreePath};
+
+/// Inspect the nodes of a virtual node tree.
+///
+/// # Examples
+/// ```rust
+/// use sauron::{vdom::{Attribute, Visitor}, html::attributes::*, *};
+///
+/// #[derive(Default)]
+/// struct Links(Vec<String>);
+///
+/// impl<MSG> Visitor<MSG> for Links {
+///     fn visit_attribute(&mut self, attr: &Attribute<MSG>, _path: &TreePath) {
+///         if *attr.name() == "href" {
+///             self.0.extend(attr.value().iter().filter_map(|v| v.get_simple()).map(|v| v.to_string()));
+///         }
+///     }
+/// }
+///
+/// let view: Node<()> = ul([], [li([], [a([href("/one")], [text("one")])])]);
+/// let mut links = Links::default();
+/// links.visit_node(&view, &TreePath::root());
+/// assert_eq!(links.0, ["/one"]);
+/// ```
+pub trait Visitor<MSG> {
+    /// visit the node at `path`
+    fn visit_node(&mut self, node: &Node<MSG>, path: &TreePath) {
+        walk_node(self, node, path)
+    }
+
+    /// visit the element at `path`
+    fn visit_element(&mut self, element: &Element<MSG>, path: &TreePath) {
+        walk_element(self, element, path)
+    }
+
+    /// visit an attribute of the element at `path`
+    fn visit_attribute(&mut self, _attr: &Attribute<MSG>, _path: &TreePath) {}
+
+    /// visit the leaf at `path`
+    fn visit_leaf(&mut self, leaf: &Leaf<MSG>, path: &TreePath) {
+        walk_leaf(self, leaf, path)
+    }
+}
+
+/// visit the node as an element or a leaf
+pub fn walk_node<MSG, V>(visitor: &mut V, node: &Node<MSG>, path: &TreePath)
+where
+    V: Visitor<MSG> + ?Sized,
+{
+    match node {
+        Node::Element(element) => visitor.visit_element(element, path),
+        Node::Leaf(leaf) => visitor.visit_leaf(leaf, path),
+    }
+}
+
+/// visit the attributes then the children of the element
+pub fn walk_element<MSG, V>(visitor: &mut V, element: &Element<MSG>, path: &TreePath)
+where
+    V: Visitor<MSG> + ?Sized,
+{
+    for attr in element.attributes().iter() {
+        visitor.visit_attribute(attr, path);
+    }
+    walk_children(visitor, element.children(), path);
+}
+
+/// visit the nodes of fragments and node lists, the attributes and children of stateful components,
+/// and the views of stateless components, templated views and lazy nodes.
+///
+/// Note: the view of a lazy node is built if it is not yet built.
+pub fn walk_leaf<MSG, V>(visitor: &mut V, leaf: &Leaf<MSG>, path: &TreePath)
+where
+    V: Visitor<MSG> + ?Sized,
+{
+    match leaf {
+        Leaf::Fragment(nodes) | Leaf::NodeList(nodes) => walk_children(visitor, nodes, path),
+        #[cfg(feature = "with-dom")]
+        Leaf::StatefulComponent(comp) => {
+            for attr in comp.attrs.iter() {
+                visitor.visit_attribute(attr, path);
+            }
+            walk_children(visitor, &comp.children, path);
+        }
+        Leaf::StatelessComponent(comp) => visitor.visit_node(&comp.view, path),
+        Leaf::TemplatedView(view) => visitor.visit_node(&view.view, path),
+        Leaf::Lazy(lazy) => visitor.visit_node(lazy.view(), path),
+        Leaf::Text(_) | Leaf::Symbol(_) | Leaf::Comment(_) | Leaf::DocType(_) => (),
+    }
+}
+
+fn walk_children<MSG, V>(visitor: &mut V, children: &[Node<MSG>], path: &TreePath)
+where
+    V: Visitor<MSG> + ?Sized,
+{
+    for (index, child) in children.iter().enumerate() {
+        visitor.visit_node(child, &path.traverse(index));
+    }
+}
+
+/// Transform the nodes of a virtual node tree, taking them by value.
+///
+/// The templated views and lazy nodes are replaced by their folded views,
+/// since their templates and view functions would still produce the unfolded views.
+///
+/// # Examples
+/// ```rust
+/// use sauron::{vdom::{Attribute, Fold}, html::{attributes::*, events::*}, *};
+///
+/// struct StripListeners;
+///
+/// impl<MSG> Fold<MSG> for StripListeners {
+///     fn fold_attribute(&mut self, mut attr: Attribute<MSG>, _path: &TreePath) -> Option<Attribute<MSG>> {
+///         attr.value.retain(|v| !v.is_event_listener());
+///         (!attr.value.is_empty()).then_some(attr)
+///     }
+/// }
+///
+/// let view: Node<()> = button([class("ok"), on_click(|_| ())], [text("ok")]);
+/// let view = StripListeners.fold_node(view, &TreePath::root());
+/// assert_eq!(view.render_to_string(), r#"<button class="ok">ok</button>"#);
+/// ```
+pub trait Fold<MSG> {
+    /// transform the node at `path`
+    fn fold_node(&mut self, node: Node<MSG>, path: &TreePath) -> Node<MSG> {
+        fold_node(self, node, path)
+    }
+
+    /// transform the element at `path`
+    fn fold_element(&mut self, element: Element<MSG>, path: &TreePath) -> Element<MSG> {
+        fold_element(self, element, path)
+    }
+
+    /// transform an attribute of the element at `path`, returning None removes the attribute
+    fn fold_attribute(&mut self, attr: Attribute<MSG>, _path: &TreePath) -> Option<Attribute<MSG>> {
+        Some(attr)
+    }
+
+    /// transform the leaf at `path`, which can be replaced with any node
+    fn fold_leaf(&mut self, leaf: Leaf<MSG>, path: &TreePath) -> Node<MSG> {
+        fold_leaf(self, leaf, path)
+    }
+}
+
+/// fold the node as an element or a leaf
+pub fn fold_node<MSG, F>(folder: &mut F, node: Node<MSG>, path: &TreePath) -> Node<MSG>
+where
+    F: Fold<MSG> + ?Sized,
+{
+    match node {
+        Node::Element(element) => Node::Element(folder.fold_element(element, path)),
+        Node::Leaf(leaf) => folder.fold_leaf(leaf, path),
+    }
+}
+
+/// fold the attributes then the children of the element
+pub fn fold_element<MSG, F>(folder: &mut F, element: Element<MSG>, path: &TreePath) -> Element<MSG>
+where
+    F: Fold<MSG> + ?Sized,
+{
+    let Element {
+        namespace,
+        tag,
+        attrs,
+        children,
+        self_closing,
+    } = element;
+    Element {
+        namespace,
+        tag,
+        attrs: fold_attributes(folder, attrs, path),
+        children: fold_children(folder, children, path),
+        self_closing,
+    }
+}
+
+/// fold the nodes of fragments and node lists, the attributes and children of stateful components,
+/// and the views of stateless components.
+///
+/// The templated views and lazy nodes are replaced by their folded views.
+pub fn fold_leaf<MSG, F>(folder: &mut F, leaf: Leaf<MSG>, path: &TreePath) -> Node<MSG>
+where
+    F: Fold<MSG> + ?Sized,
+{
+    let leaf = match leaf {
+        Leaf::Fragment(nodes) => Leaf::Fragment(fold_children(folder, nodes, path)),
+        Leaf::NodeList(nodes) => Leaf::NodeList(fold_children(folder, nodes, path)),
+        #[cfg(feature = "with-dom")]
+        Leaf::StatefulComponent(mut comp) => {
+            comp.attrs = fold_attributes(folder, comp.attrs, path);
+            comp.children = fold_children(folder, comp.children, path);
+            Leaf::StatefulComponent(comp)
+        }
+        Leaf::StatelessComponent(mut comp) => {
+            comp.view = Box::new(folder.fold_node(*comp.view, path));
+            Leaf::StatelessComponent(comp)
+        }
+        Leaf::TemplatedView(view) => return folder.fold_node(*view.view, path),
+        Leaf::Lazy(lazy) => return folder.fold_node(lazy.view().clone(), path),
+        leaf @ (Leaf::Text(_) | Leaf::Symbol(_) | Leaf::Comment(_) | Leaf::DocType(_)) => leaf,
+    };
+    Node::Leaf(leaf)
+}
+
+fn fold_attributes<MSG, F>(
+    folder: &mut F,
+    attrs: Vec<Attribute<MSG>>,
+    path: &TreePath,
+) -> Vec<Attribute<MSG>>
+where
+    F: Fold<MSG> + ?Sized,
+{
+    attrs
+        .into_iter()
+        .filter_map(|attr| folder.fold_attribute(attr, path))
+        .collect()
+}
+
+fn fold_children<MSG, F>(
+    folder: &mut F,
+    children: Vec<Node<MSG>>,
+    path: &TreePath,
+) -> Vec<Node<MSG>>
+where
+    F: Fold<MSG> + ?Sized,
+{
+    children
+        .into_iter()
+        .enumerate()
+        .map(|(index, child)| folder.fold_node(child, &path.traverse(index)))
+        .collect()
+}
@@ -0,0 +1,653 @@
+//! An owned and serializable representation of patches and nodes, so a diff computed in one
+//! process can be shipped to another process and applied there.
+//!
+//! Event listeners can not leave the process, they are registered into a [`HandlerRegistry`]
+//! and only their [`HandlerId`] is sent. The receiving side supplies the listener for each
+//! handler id when converting back into patches and nodes.
+//!
+//! The wire types can be encoded as json with [`to_json`] or as a compact binary with [`to_binary`].
+use crate::vdom::{
+    Attribute, AttributeValue, Element, EventCallback, Leaf, ListenerOptions, Node, Patch,
+    PatchType, Style, Tag, TreePath, Value, KEY, REPLACE, SKIP, SKIP_CRITERIA,
+};
+use derive_where::derive_where;
+use indexmap::IndexMap;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+
+/// The id of an event listener which was registered in the [`HandlerRegistry`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct HandlerId(pub u32);
+
+/// Keeps the event listeners of the nodes which were converted into wire nodes.
+///
+/// A listener keeps its id for as long as it is registered, the same listener or its clones,
+/// such as a listener in the view of a lazy node which is not rebuilt, is given the same id
+/// each time it is registered. The listener is removed when each of its registrations is released.
+/// Ids are assigned in the order the listeners are encountered and are never reused.
+pub struct HandlerRegistry<MSG> {
+    /// the listeners and the number of times they are registered
+    handlers: IndexMap<HandlerId, (EventCallback<MSG>, usize)>,
+    /// the handler ids by the address of the listener function
+    ids: HashMap<usize, HandlerId>,
+    next_id: u32,
+}
+
+impl<MSG> Default for HandlerRegistry<MSG> {
+    fn default() -> Self {
+        Self {
+            handlers: IndexMap::new(),
+            ids: HashMap::new(),
+            next_id: 0,
+        }
+    }
+}
+
+impl<MSG> HandlerRegistry<MSG> {
+    /// create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// register the listener and return its id,
+    /// which is the same id if the listener is already registered
+    pub fn register(&mut self, listener: &EventCallback<MSG>) -> HandlerId {
+        if let Some(id) = self.ids.get(&listener.func_addr()) {
+            self.handlers[id].1 += 1;
+            return *id;
+        }
+        let id = HandlerId(self.next_id);
+        self.next_id += 1;
+        self.ids.insert(listener.func_addr(), id);
+        self.handlers.insert(id, (listener.clone(), 1));
+        id
+    }
+
+    /// release one registration of the listener with this handler id,
+    /// the listener is removed when all of its registrations are released
+    pub fn release(&mut self, id: HandlerId) {
+        let Some((listener, count)) = self.handlers.get_mut(&id) else {
+            return;
+        };
+        *count -= 1;
+        if *count == 0 {
+            self.ids.remove(&listener.func_addr());
+            self.handlers.swap_remove(&id);
+        }
+    }
+
+    /// return the listener with this handler id
+    pub fn get(&self, id: HandlerId) -> Option<&EventCallback<MSG>> {
+        self.handlers.get(&id).map(|(listener, _)| listener)
+    }
+
+    /// the number of registered listeners
+    pub fn len(&self) -> usize {
+        self.handlers.len()
+    }
+
+    /// returns true if there are no registered listeners
+    pub fn is_empty(&self) -> bool {
+        self.handlers.is_empty()
+    }
+}
+
+/// An owned and serializable [`Patch`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WirePatch {
+    /// the tag of the node at patch_path
+    pub tag: Option<String>,
+    /// the path to traverse to get to the target element
+    pub patch_path: TreePath,
+    /// the type of patch we are going to apply
+    pub patch_type: WirePatchType,
+}
+
+/// An owned and serializable [`PatchType`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum WirePatchType {
+    /// insert the nodes before the node at patch_path
+    InsertBeforeNode {
+        /// the nodes to be inserted before patch_path
+        nodes: Vec<WireNode>,
+    },
+    /// insert the nodes after the node at patch_path
+    InsertAfterNode {
+        /// the nodes to be inserted after the patch_path
+        nodes: Vec<WireNode>,
+    },
+    /// Append a vector of child nodes to a parent node id at patch_path
+    AppendChildren {
+        /// children nodes to be appended
+        children: Vec<WireNode>,
+    },
+    /// clear the chilren of this node,
+    ClearChildren,
+    /// remove the target node
+    RemoveNode,
+    /// move the nodes at `nodes_path` before the node at patch_path
+    MoveBeforeNode {
+        /// the path of the nodes to be moved
+        nodes_path: Vec<TreePath>,
+    },
+    /// move the nodes at `nodes_path` after the node at patch_path
+    MoveAfterNode {
+        /// the path of the nodes to be moved
+        nodes_path: Vec<TreePath>,
+    },
+    /// replace the node at patch_path with these nodes
+    ReplaceNode {
+        /// the nodes that will replace the target node
+        replacement: Vec<WireNode>,
+    },
+    /// Add these attributes to the node at patch_path
+    AddAttributes {
+        /// the attributes to be patched into the target node
+        attrs: Vec<WireAttribute>,
+    },
+    /// Remove these attributes from the node at patch_path
+    RemoveAttributes {
+        /// attributes that are to be removed from this target node
+        attrs: Vec<WireAttribute>,
+    },
+    /// Set these style properties of the node at patch_path
+    SetStyles {
+        /// the style properties to be set
+        styles: Vec<Style>,
+    },
+    /// Remove the style properties with these names from the node at patch_path
+    RemoveStyles {
+        /// the names of the style properties to be removed
+        names: Vec<String>,
+    },
+    /// Set the content of the text or comment node at patch_path
+    SetText {
+        /// the new content
+        text: String,
+    },
+    /// Add these class names into the node at patch_path
+    AddClasses {
+        /// the class names to be added
+        classes: Vec<String>,
+    },
+    /// Remove these class names from the node at patch_path
+    RemoveClasses {
+        /// the class names to be removed
+        classes: Vec<String>,
+    },
+}
+
+/// An owned and serializable [`Node`], where the event listeners are replaced with handler ids
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum WireNode {
+    /// an element
+    Element(WireElement),
+    /// a text node
+    Text(String),
+    /// html entities such as &nbsp; &gt;
+    Symbol(String),
+    /// a comment node
+    Comment(String),
+    /// doctype: html, math, svg
+    DocType(String),
+    /// a node list, which is unrolled into the children of the parent element
+    NodeList(Vec<WireNode>),
+    /// a document fragment
+    Fragment(Vec<WireNode>),
+}
+
+/// An owned and serializable [`Element`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WireElement {
+    /// namespace of this element
+    pub namespace: Option<String>,
+    /// the element tag, such as div, a, button
+    pub tag: String,
+    /// attributes of this element
+    pub attrs: Vec<WireAttribute>,
+    /// children of this element
+    pub children: Vec<WireNode>,
+    /// is the element has a self closing tag
+    pub self_closing: bool,
+}
+
+/// An owned and serializable [`Attribute`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WireAttribute {
+    /// namespace of the attribute
+    pub namespace: Option<String>,
+    /// the attribute name
+    pub name: String,
+    /// the attribute values
+    pub value: Vec<WireAttributeValue>,
+}
+
+/// An owned and serializable [`AttributeValue`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum WireAttributeValue {
+    /// a simple value
+    Simple(Value),
+    /// style values
+    Style(Vec<Style>),
+    /// the id of the event listener in the [`HandlerRegistry`], and the options of the listener
+    EventListener(HandlerId, ListenerOptions),
+    /// no value
+    Empty,
+}
+
+/// An error while converting the wire types back into nodes and patches
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum WireError {
+    /// the name is not a known tag, attribute or namespace and too many unknown names were received
+    #[error("too many unknown names are received, the name {0:?} is not kept")]
+    TooManyNames(String),
+}
+
+/// the most unknown names which are kept, the names received after that are errors
+const MAX_UNKNOWN_NAMES: usize = 1024;
+
+thread_local! {
+    static INTERNED: RefCell<HashSet<&'static str>> = RefCell::new(HashSet::new());
+}
+
+/// the static name of the known tags, attributes and namespaces
+fn known_name(name: &str) -> Option<&'static str> {
+    let special = [
+        crate::svg::SVG_NAMESPACE,
+        crate::svg::attributes::XLINK_NAMESPACE,
+        *KEY,
+        *SKIP,
+        *SKIP_CRITERIA,
+        *REPLACE,
+    ];
+    if let Some(special) = special.into_iter().find(|special| *special == name) {
+        return Some(special);
+    }
+    #[cfg(feature = "with-lookup")]
+    {
+        crate::html::lookup::match_tag(name).or_else(|| crate::html::lookup::match_attribute(name))
+    }
+    #[cfg(not(feature = "with-lookup"))]
+    {
+        None
+    }
+}
+
+/// Tags and attribute names are `&'static str` in the vdom.
+/// The known names are found in the lookup tables, the other names, such as custom elements
+/// and event names, are leaked once and reused afterwards up to `MAX_UNKNOWN_NAMES`.
+fn intern(name: String) -> Result<&'static str, WireError> {
+    if let Some(known) = known_name(&name) {
+        return Ok(known);
+    }
+    INTERNED.with(|interned| {
+        let mut interned = interned.borrow_mut();
+        if let Some(existing) = interned.get(name.as_str()) {
+            Ok(*existing)
+        } else if interned.len() >= MAX_UNKNOWN_NAMES {
+            Err(WireError::TooManyNames(name))
+        } else {
+            let leaked: &'static str = Box::leak(name.into_boxed_str());
+            interned.insert(leaked);
+            Ok(leaked)
+        }
+    })
+}
+
+impl<MSG> Node<MSG> {
+    /// convert into a wire node, the event listeners are registered into the `registry`
+    ///
+    /// Stateful components can not be sent, they are converted into a placeholder comment
+    /// the same way as in `render_to_string`.
+    pub fn to_wire(&self, registry: &mut HandlerRegistry<MSG>) -> WireNode {
+        match self {
+            Node::Element(elm) => WireNode::Element(elm.to_wire(registry)),
+            Node::Leaf(leaf) => match leaf {
+                Leaf::Text(txt) => WireNode::Text(txt.to_string()),
+                Leaf::Symbol(symbol) => WireNode::Symbol(symbol.to_string()),
+                Leaf::Comment(comment) => WireNode::Comment(comment.to_string()),
+                Leaf::DocType(doctype) => WireNode::DocType(doctype.to_string()),
+                Leaf::NodeList(nodes) => {
+                    WireNode::NodeList(nodes.iter().map(|n| n.to_wire(registry)).collect())
+                }
+                Leaf::Fragment(nodes) => {
+                    WireNode::Fragment(nodes.iter().map(|n| n.to_wire(registry)).collect())
+                }
+                #[cfg(feature = "with-dom")]
+                Leaf::StatefulComponent(_comp) => {
+                    WireNode::Comment("stateful component".to_string())
+                }
+                Leaf::StatelessComponent(comp) => comp.view.to_wire(registry),
+                Leaf::TemplatedView(view) => view.view.to_wire(registry),
+                Leaf::Lazy(lazy) => lazy.view().to_wire(registry),
+            },
+        }
+    }
+}
+
+impl<MSG> Element<MSG> {
+    fn to_wire(&self, registry: &mut HandlerRegistry<MSG>) -> WireElement {
+        WireElement {
+            namespace: self.namespace.map(str::to_string),
+            tag: self.tag.to_string(),
+            attrs: self.attrs.iter().map(|a| a.to_wire(registry)).collect(),
+            children: self.children.iter().map(|c| c.to_wire(registry)).collect(),
+            self_closing: self.self_closing,
+        }
+    }
+}
+
+impl<MSG> Attribute<MSG> {
+    /// convert into a wire attribute, the event listeners are registered into the `registry`
+    pub fn to_wire(&self, registry: &mut HandlerRegistry<MSG>) -> WireAttribute {
+        WireAttribute {
+            namespace: self.namespace.map(str::to_string),
+            name: self.name.to_string(),
+            value: self
+                .value
+                .iter()
+                .map(|v| match v {
+                    AttributeValue::Simple(v) => WireAttributeValue::Simple(v.clone()),
+                    AttributeValue::Style(styles) => WireAttributeValue::Style(styles.clone()),
+                    AttributeValue::EventListener(cb) => {
+                        WireAttributeValue::EventListener(registry.register(cb), cb.options())
+                    }
+                    AttributeValue::Empty => WireAttributeValue::Empty,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<'a, MSG> Patch<'a, MSG> {
+    /// convert into a wire patch, the event listeners are registered into the `registry`
+    pub fn to_wire(&self, registry: &mut HandlerRegistry<MSG>) -> WirePatch {
+        let nodes = |nodes: &[&Node<MSG>], registry: &mut HandlerRegistry<MSG>| {
+            nodes.iter().map(|n| n.to_wire(registry)).collect()
+        };
+        let attrs = |attrs: &[&Attribute<MSG>], registry: &mut HandlerRegistry<MSG>| {
+            attrs.iter().map(|a| a.to_wire(registry)).collect()
+        };
+        let patch_type = match &self.patch_type {
+            PatchType::InsertBeforeNode { nodes } => WirePatchType::InsertBeforeNode {
+                nodes: nodes.iter().map(|n| n.to_wire(registry)).collect(),
+            },
+            PatchType::InsertAfterNode { nodes: n } => WirePatchType::InsertAfterNode {
+                nodes: nodes(n, registry),
+            },
+            PatchType::AppendChildren { children } => WirePatchType::AppendChildren {
+                children: nodes(children, registry),
+            },
+            PatchType::ClearChildren => WirePatchType::ClearChildren,
+            PatchType::RemoveNode => WirePatchType::RemoveNode,
+            PatchType::MoveBeforeNode { nodes_path } => WirePatchType::MoveBeforeNode {
+                nodes_path: nodes_path.clone(),
+            },
+            PatchType::MoveAfterNode { nodes_path } => WirePatchType::MoveAfterNode {
+                nodes_path: nodes_path.clone(),
+            },
+            PatchType::ReplaceNode { replacement } => WirePatchType::ReplaceNode {
+                replacement: nodes(replacement, registry),
+            },
+            PatchType::AddAttributes { attrs: a } => WirePatchType::AddAttributes {
+                attrs: attrs(a, registry),
+            },
+            PatchType::RemoveAttributes { attrs: a } => WirePatchType::RemoveAttributes {
+                attrs: attrs(a, registry),
+            },
+            PatchType::SetStyles { styles } => WirePatchType::SetStyles {
+                styles: styles.iter().map(|s| (*s).clone()).collect(),
+            },
+            PatchType::RemoveStyles { names } => WirePatchType::RemoveStyles {
+                names: names.iter().map(|n| n.to_string()).collect(),
+            },
+            PatchType::SetText { text } => WirePatchType::SetText {
+                text: text.to_string(),
+            },
+            PatchType::AddClasses { classes } => WirePatchType::AddClasses {
+                classes: classes.clone(),
+            },
+            PatchType::RemoveClasses { classes } => WirePatchType::RemoveClasses {
+                classes: classes.clone(),
+            },
+        };
+        WirePatch {
+            tag: self.tag.map(|tag| tag.to_string()),
+            patch_path: self.patch_path.clone(),
+            patch_type,
+        }
+    }
+}
+
+impl WireNode {
+    /// convert back into a node, `listener` supplies the event listener of each handler id
+    pub fn into_node<MSG>(
+        self,
+        listener: &dyn Fn(HandlerId) -> EventCallback<MSG>,
+    ) -> Result<Node<MSG>, WireError> {
+        let node = match self {
+            WireNode::Element(elm) => Node::Element(elm.into_element(listener)?),
+            WireNode::Text(txt) => Node::Leaf(Leaf::Text(Cow::Owned(txt))),
+            WireNode::Symbol(symbol) => Node::Leaf(Leaf::Symbol(Cow::Owned(symbol))),
+            WireNode::Comment(comment) => Node::Leaf(Leaf::Comment(Cow::Owned(comment))),
+            WireNode::DocType(doctype) => Node::Leaf(Leaf::DocType(Cow::Owned(doctype))),
+            WireNode::NodeList(nodes) => Node::Leaf(Leaf::NodeList(
+                nodes
+                    .into_iter()
+                    .map(|n| n.into_node(listener))
+                    .collect::<Result<_, _>>()?,
+            )),
+            WireNode::Fragment(nodes) => Node::Leaf(Leaf::Fragment(
+                nodes
+                    .into_iter()
+                    .map(|n| n.into_node(listener))
+                    .collect::<Result<_, _>>()?,
+            )),
+        };
+        Ok(node)
+    }
+}
+
+impl WireElement {
+    fn into_element<MSG>(
+        self,
+        listener: &dyn Fn(HandlerId) -> EventCallback<MSG>,
+    ) -> Result<Element<MSG>, WireError> {
+        let attrs: Vec<Attribute<MSG>> = self
+            .attrs
+            .into_iter()
+            .map(|a| a.into_attribute(listener))
+            .collect::<Result<_, _>>()?;
+        let children: Vec<Node<MSG>> = self
+            .children
+            .into_iter()
+            .map(|c| c.into_node(listener))
+            .collect::<Result<_, _>>()?;
+        Ok(Element::new(
+            self.namespace.map(intern).transpose()?,
+            intern(self.tag)?,
+            attrs,
+            children,
+            self.self_closing,
+        ))
+    }
+}
+
+impl WireAttribute {
+    /// convert back into an attribute, `listener` supplies the event listener of each handler id
+    pub fn into_attribute<MSG>(
+        self,
+        listener: &dyn Fn(HandlerId) -> EventCallback<MSG>,
+    ) -> Result<Attribute<MSG>, WireError> {
+        Ok(Attribute::with_multiple_values(
+            self.namespace.map(intern).transpose()?,
+            intern(self.name)?,
+            self.value.into_iter().map(|v| match v {
+                WireAttributeValue::Simple(v) => AttributeValue::Simple(v),
+                WireAttributeValue::Style(styles) => AttributeValue::Style(styles),
+                WireAttributeValue::EventListener(id, options) => {
+                    AttributeValue::EventListener(listener(id).with_options(options))
+                }
+                WireAttributeValue::Empty => AttributeValue::Empty,
+            }),
+        ))
+    }
+}
+
+/// A patch which owns its nodes and attributes, converted from a [`WirePatch`].
+///
+/// Use [`OwnedPatch::as_patch`] to get the [`Patch`] to be applied.
+#[derive_where(Clone, Debug)]
+pub struct OwnedPatch<MSG> {
+    tag: Option<Tag>,
+    patch_path: TreePath,
+    patch_type: OwnedPatchType<MSG>,
+}
+
+#[derive_where(Clone, Debug)]
+enum OwnedPatchType<MSG> {
+    InsertBeforeNode(Vec<Node<MSG>>),
+    InsertAfterNode(Vec<Node<MSG>>),
+    AppendChildren(Vec<Node<MSG>>),
+    ClearChildren,
+    RemoveNode,
+    MoveBeforeNode(Vec<TreePath>),
+    MoveAfterNode(Vec<TreePath>),
+    ReplaceNode(Vec<Node<MSG>>),
+    AddAttributes(Vec<Attribute<MSG>>),
+    RemoveAttributes(Vec<Attribute<MSG>>),
+    SetStyles(Vec<Style>),
+    RemoveStyles(Vec<String>),
+    SetText(String),
+    AddClasses(Vec<String>),
+    RemoveClasses(Vec<String>),
+}
+
+impl WirePatch {
+    /// convert back into a patch, `listener` supplies the event listener of each handler id
+    pub fn into_patch<MSG>(
+        self,
+        listener: &dyn Fn(HandlerId) -> EventCallback<MSG>,
+    ) -> Result<OwnedPatch<MSG>, WireError> {
+        let nodes = |nodes: Vec<WireNode>| -> Result<Vec<Node<MSG>>, WireError> {
+            nodes.into_iter().map(|n| n.into_node(listener)).collect()
+        };
+        let attrs = |attrs: Vec<WireAttribute>| -> Result<Vec<Attribute<MSG>>, WireError> {
+            attrs
+                .into_iter()
+                .map(|a| a.into_attribute(listener))
+                .collect()
+        };
+        let patch_type = match self.patch_type {
+            WirePatchType::InsertBeforeNode { nodes: n } => {
+                OwnedPatchType::InsertBeforeNode(nodes(n)?)
+            }
+            WirePatchType::InsertAfterNode { nodes: n } => {
+                OwnedPatchType::InsertAfterNode(nodes(n)?)
+            }
+            WirePatchType::AppendChildren { children } => {
+                OwnedPatchType::AppendChildren(nodes(children)?)
+            }
+            WirePatchType::ClearChildren => OwnedPatchType::ClearChildren,
+            WirePatchType::RemoveNode => OwnedPatchType::RemoveNode,
+            WirePatchType::MoveBeforeNode { nodes_path } => {
+                OwnedPatchType::MoveBeforeNode(nodes_path)
+            }
+            WirePatchType::MoveAfterNode { nodes_path } => {
+                OwnedPatchType::MoveAfterNode(nodes_path)
+            }
+            WirePatchType::ReplaceNode { replacement } => {
+                OwnedPatchType::ReplaceNode(nodes(replacement)?)
+            }
+            WirePatchType::AddAttributes { attrs: a } => OwnedPatchType::AddAttributes(attrs(a)?),
+            WirePatchType::RemoveAttributes { attrs: a } => {
+                OwnedPatchType::RemoveAttributes(attrs(a)?)
+            }
+            WirePatchType::SetStyles { styles } => OwnedPatchType::SetStyles(styles),
+            WirePatchType::RemoveStyles { names } => OwnedPatchType::RemoveStyles(names),
+            WirePatchType::SetText { text } => OwnedPatchType::SetText(text),
+            WirePatchType::AddClasses { classes } => OwnedPatchType::AddClasses(classes),
+            WirePatchType::RemoveClasses { classes } => OwnedPatchType::RemoveClasses(classes),
+        };
+        Ok(OwnedPatch {
+            tag: self.tag.map(intern).transpose()?,
+            patch_path: self.patch_path,
+            patch_type,
+        })
+    }
+}
+
+impl<MSG> OwnedPatch<MSG> {
+    /// the patch borrowing the nodes and attributes of this owned patch
+    pub fn as_patch(&self) -> Patch<'_, MSG> {
+        let patch_type = match &self.patch_type {
+            OwnedPatchType::InsertBeforeNode(nodes) => PatchType::InsertBeforeNode {
+                nodes: nodes.iter().map(Cow::Borrowed).collect(),
+            },
+            OwnedPatchType::InsertAfterNode(nodes) => PatchType::InsertAfterNode {
+                nodes: nodes.iter().collect(),
+            },
+            OwnedPatchType::AppendChildren(children) => PatchType::AppendChildren {
+                children: children.iter().collect(),
+            },
+            OwnedPatchType::ClearChildren => PatchType::ClearChildren,
+            OwnedPatchType::RemoveNode => PatchType::RemoveNode,
+            OwnedPatchType::MoveBeforeNode(nodes_path) => PatchType::MoveBeforeNode {
+                nodes_path: nodes_path.clone(),
+            },
+            OwnedPatchType::MoveAfterNode(nodes_path) => PatchType::MoveAfterNode {
+                nodes_path: nodes_path.clone(),
+            },
+            OwnedPatchType::ReplaceNode(replacement) => PatchType::ReplaceNode {
+                replacement: replacement.iter().collect(),
+            },
+            OwnedPatchType::AddAttributes(attrs) => PatchType::AddAttributes {
+                attrs: attrs.iter().collect(),
+            },
+            OwnedPatchType::RemoveAttributes(attrs) => PatchType::RemoveAttributes {
+                attrs: attrs.iter().collect(),
+            },
+            OwnedPatchType::SetStyles(styles) => PatchType::SetStyles {
+                styles: styles.iter().collect(),
+            },
+            OwnedPatchType::RemoveStyles(names) => PatchType::RemoveStyles {
+                names: names.iter().map(String::as_str).collect(),
+            },
+            OwnedPatchType::SetText(text) => PatchType::SetText { text },
+            OwnedPatchType::AddClasses(classes) => PatchType::AddClasses {
+                classes: classes.clone(),
+            },
+            OwnedPatchType::RemoveClasses(classes) => PatchType::RemoveClasses {
+                classes: classes.clone(),
+            },
+        };
+        Patch {
+            tag: self.tag.as_ref(),
+            patch_path: self.patch_path.clone(),
+            patch_type,
+        }
+    }
+}
+
+/// encode the wire patches or nodes as json
+pub fn to_json<T: Serialize + ?Sized>(value: &T) -> Result<String, serde_json::Error> {
+    serde_json::to_string(value)
+}
+
+/// decode the wire patches or nodes from json
+pub fn from_json<T: DeserializeOwned>(json: &str) -> Result<T, serde_json::Error> {
+    serde_json::from_str(json)
+}
+
+/// encode the wire patches or nodes into a compact binary
+pub fn to_binary<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, bincode::Error> {
+    bincode::serialize(value)
+}
+
+/// decode the wire patches or nodes from a compact binary
+pub fn from_binary<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, bincode::Error> {
+    bincode::deserialize(bytes)
+}
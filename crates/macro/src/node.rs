@@ -2,7 +2,7 @@ use proc_macro2::TokenStream;
 use quote::quote;
 use rstml::node::{KeyedAttributeValue, Node, NodeAttribute, NodeBlock};
 use sauron_core::html::lookup;
-use syn::{Expr, ExprForLoop, ExprIf, Stmt};
+use syn::{Expr, ExprBlock, ExprForLoop, ExprIf, ExprLit, ExprParen, ExprPath, Lit, Stmt};
 
 pub fn to_token_stream(input: proc_macro::TokenStream) -> TokenStream {
     match rstml::parse(input) {
@@ -166,6 +166,12 @@ fn attribute_to_tokens(attribute: NodeAttribute) -> TokenStream {
                             #[allow(unused_braces)]
                             sauron::html::events::#event(#value)
                         }
+                    } else if let Some(function) = aria_function(&attr, &value) {
+                        let function = quote::format_ident!("{function}");
+                        quote! {
+                            #[allow(unused_braces)]
+                            sauron::html::attributes::aria::#function(#value)
+                        }
                     } else {
                         quote! {
                             #[allow(unused_braces)]
@@ -183,6 +189,42 @@ fn attribute_to_tokens(attribute: NodeAttribute) -> TokenStream {
     }
 }
 
+/// The typed builder of an aria attribute, so the value is type checked.
+/// Only the values which are typed tokens use the builder, other values such as strings and variables
+/// are set with `attr` as they are, since they may not convert into the type of the builder.
+fn aria_function(attr: &str, value: &Expr) -> Option<&'static str> {
+    let is_aria = attr == "role" || attr.starts_with("aria-");
+    if is_aria && is_typed_token(value) {
+        lookup::attribute_function(attr)
+    } else {
+        None
+    }
+}
+
+/// returns true if the value is a literal bool or number, an array, or a variant of an enum such as `Role::Dialog`
+fn is_typed_token(value: &Expr) -> bool {
+    match value {
+        Expr::Block(ExprBlock { block, .. }) if block.stmts.len() == 1 => {
+            matches!(&block.stmts[0], Stmt::Expr(expr, None) if is_typed_token(expr))
+        }
+        Expr::Paren(ExprParen { expr, .. }) => is_typed_token(expr),
+        Expr::Lit(ExprLit {
+            lit: Lit::Bool(_) | Lit::Int(_) | Lit::Float(_),
+            ..
+        }) => true,
+        Expr::Array(_) => true,
+        Expr::Path(ExprPath { path, .. }) => {
+            let segments: Vec<_> = path.segments.iter().collect();
+            segments.len() >= 2
+                && segments[segments.len() - 2]
+                    .ident
+                    .to_string()
+                    .starts_with(|c: char| c.is_ascii_uppercase())
+        }
+        _ => false,
+    }
+}
+
 fn braced_for_loop(block: &syn::Block) -> Option<&ExprForLoop> {
     let len = block.stmts.len();
     if len != 1 {
@@ -188,32 +188,32 @@ impl App {
     }
 }
 
-/// The serialized_state is supplied by the generated page from the webserver.
-/// The generated page in index function has a main function which is supplied by a json text
-/// serialized state. This json text is deserialized and used here as our `App` value which
+/// The serialized state is embedded by the webserver in the generated page with `embed_state`.
+/// This json is deserialized and used here as our `App` value which
 /// will then be injected into the view
 #[wasm_bindgen]
-pub fn main(serialized_state: String) {
+pub fn main() {
     console_log::init_with_level(log::Level::Trace).unwrap();
     console_error_panic_hook::set_once();
 
-    /* Deserialize starting app data from the argument, which is passed in the generated index page
+    /* Deserialize starting app data from the state embedded in the generated index page
      * (but generated in server/src/main.rs) */
-    let app =
-        if let Ok(app_state) = serde_json::from_str::<App>(&serialized_state) {
-            app_state
-        } else {
+    let app = match sauron::dom::embedded_state::<App>() {
+        Ok(app_state) => app_state,
+        Err(e) => {
+            warn!("{e}");
             App::default()
-        };
+        }
+    };
 
     /* If there's a window (i.e., if this is running in the browser)
-     * then mount the app by swapping out the <main> tag */
+     * then mount the app by adopting the server rendered <main> tag */
     match web_sys::window() {
         Some(window) => {
-            trace!("found window, will try to replace <main>");
+            trace!("found window, will try to hydrate <main>");
             let document =
                 window.document().expect("should have a document on window");
-            Program::replace_mount(
+            Program::hydrate_mount(
                 app,
                 &document.query_selector_all("main").unwrap().get(0).unwrap(),
             );
@@ -3,9 +3,9 @@ use sauron::{html::units::*, html::*, *};
 
 /// We are creating an index page.
 /// From the `App` supplied, we can derive the view by calling `App.view` function.
-/// we extract the state and serialize it.
+/// we extract the state and embed it in the page.
 pub fn index(app: &App) -> Node<Msg> {
-    let serialized_state = serde_json::to_string(&app).unwrap();
+    let embedded_state = embed_state(app).unwrap();
     let view = app.view();
     let style = style();
     node! {
@@ -15,15 +15,16 @@ pub fn index(app: &App) -> Node<Msg> {
             <meta http-equiv="Content-type" content="text/html; charset=utf-8"/>
             <title>"Progressive Rendering with Sauron"</title>
             <style>{text(style)}</style>
+            {embedded_state}
             <script type="module">
-                {text!("
-                      import init, {{ main }} from '/pkg/client.js';
-                      async function start() {{
+                "
+                      import init, { main } from '/pkg/client.js';
+                      async function start() {
                         await init();
-                        main(`{}`);
-                      }}
+                        main();
+                      }
                       await start();
-                ",serialized_state)}
+                "
             </script>
             <meta name="viewport" content="viewport-fit=cover, width=device-width, initial-scale=1.0"/>
           </head>
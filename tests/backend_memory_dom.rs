@@ -0,0 +1,156 @@
+#![deny(warnings)]
+use sauron::{
+    dom::{events::RemoteEvent, Event, MemoryDom, MemoryNode, RenderBackend, Renderer},
+    html::{
+        attributes::{class, id, key, styles},
+        events::on,
+        *,
+    },
+    vdom::fragment,
+    Node,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn mount<MSG: 'static>(view: Node<MSG>) -> (Renderer<MemoryDom, MSG>, MemoryNode) {
+    let body = MemoryDom::create_element("body", None);
+    let renderer = Renderer::mount(&body, view, |_| {});
+    (renderer, body)
+}
+
+fn keyed_list(keys: &[usize]) -> Node<()> {
+    main(
+        [class("reordered")],
+        [ul(
+            [class("todo")],
+            keys.iter()
+                .map(|k| li([key(*k)], [text(format!("item{k}"))])),
+        )],
+    )
+}
+
+#[test]
+fn mount_the_view() {
+    let (_renderer, body) = mount::<()>(div(
+        [id("app"), class("a b")],
+        [
+            text("hello"),
+            input([class("name")], []),
+            comment("note"),
+            span([styles([("color", "red")])], [text("<world>")]),
+        ],
+    ));
+    assert_eq!(
+        body.inner_html(),
+        r#"<div id="app" class="a b">hello<input class="name"><!--note--><span style="color:red;">&lt;world&gt;</span></div>"#
+    );
+}
+
+#[test]
+fn update_the_view() {
+    let (mut renderer, body) = mount::<()>(div(
+        [
+            class("a b"),
+            styles([("display", "block"), ("color", "red")]),
+        ],
+        [text("hello"), span([id("x")], [])],
+    ));
+    renderer
+        .update(div(
+            [
+                class("b c"),
+                styles([("display", "none"), ("color", "red")]),
+            ],
+            [text("world"), span([], [])],
+        ))
+        .unwrap();
+    assert_eq!(
+        body.inner_html(),
+        r#"<div class="b c" style="display:none;color:red;">world<span></span></div>"#
+    );
+}
+
+#[test]
+fn keyed_reordering() {
+    let cases: &[(&[usize], &[usize])] = &[
+        (&[1, 2, 3], &[3, 2, 1]),
+        (&[1, 2, 3], &[2, 3]),
+        (&[1, 2, 3], &[1, 4, 2, 3]),
+        (&[1, 2, 3, 4, 5], &[5, 1, 2, 3, 4]),
+        (&[1, 2, 3], &[]),
+        (&[], &[1, 2]),
+        (&[1, 2, 3, 4, 5], &[2, 1, 4, 3, 6, 5]),
+    ];
+    for (old, new) in cases {
+        let (mut renderer, body) = mount(keyed_list(old));
+        renderer.update(keyed_list(new)).unwrap();
+        assert_eq!(
+            body.inner_html(),
+            keyed_list(new).render_to_string(),
+            "{old:?} -> {new:?}"
+        );
+    }
+}
+
+#[test]
+fn replace_the_root() {
+    let (mut renderer, body) = mount::<()>(div([], [text("div")]));
+    renderer.update(p([], [text("p")])).unwrap();
+    assert_eq!(body.inner_html(), "<p>p</p>");
+    assert_eq!(renderer.root_node().tag().as_deref(), Some("p"));
+    renderer
+        .update(fragment([span([], []), p([], [])]))
+        .unwrap();
+    assert_eq!(body.inner_html(), "<span></span><p></p>");
+}
+
+#[test]
+fn event_listeners_dispatch_msgs() {
+    let view = |n: i32, clickable: bool| {
+        let listener = clickable.then(|| on("click", |_| 1));
+        div(
+            [],
+            [button([id("inc")].into_iter().chain(listener), [text(n)])],
+        )
+    };
+    let msgs = Rc::new(RefCell::new(vec![]));
+    let body = MemoryDom::create_element("body", None);
+    let dispatched = Rc::clone(&msgs);
+    let mut renderer = Renderer::<MemoryDom, _>::mount(&body, view(0, true), move |msg| {
+        dispatched.borrow_mut().push(msg)
+    });
+    let click = || Event::RemoteEvent(RemoteEvent::new("click"));
+    let button = renderer.root_node().children()[0].clone();
+    assert!(button.dispatch_event("click", click()));
+    assert!(!button.dispatch_event("input", click()));
+    assert_eq!(*msgs.borrow(), [1]);
+
+    renderer.update(view(1, true)).unwrap();
+    assert!(button.dispatch_event("click", click()));
+    assert_eq!(*msgs.borrow(), [1, 1]);
+    assert_eq!(
+        body.inner_html(),
+        r#"<div><button id="inc">1</button></div>"#
+    );
+
+    renderer.update(view(2, false)).unwrap();
+    assert!(!button.dispatch_event("click", click()));
+}
+
+#[test]
+fn removed_nodes_drop_their_listeners() {
+    let view = |with_button: bool| -> Node<i32> {
+        let button =
+            with_button.then(|| button([on("click", |_| 1)], [span([on("click", |_| 2)], [])]));
+        div([], button)
+    };
+    let (mut renderer, _body) = mount(view(true));
+    let button = renderer.root_node().children()[0].clone();
+    let inner = button.children()[0].clone();
+    let click = || Event::RemoteEvent(RemoteEvent::new("click"));
+
+    renderer.update(view(false)).unwrap();
+    // the handles of the removed nodes are still alive, but their listeners are dropped
+    assert!(!button.dispatch_event("click", click()));
+    assert!(!inner.dispatch_event("click", click()));
+}
@@ -0,0 +1,50 @@
+#![deny(warnings)]
+use sauron::{
+    dom::{embedded_state, EmbeddedStateError},
+    html::embed_state,
+    *,
+};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct App {
+    name: String,
+}
+
+impl Application for App {
+    type MSG = ();
+
+    fn update(&mut self, _msg: ()) -> Cmd<()> {
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        p([], [text(&self.name)])
+    }
+}
+
+#[wasm_bindgen_test]
+fn program_starts_with_the_embedded_state() {
+    assert!(matches!(
+        embedded_state::<App>(),
+        Err(EmbeddedStateError::Missing(_))
+    ));
+
+    let server_app = App {
+        name: "</script>from the server".to_string(),
+    };
+    let state: Node<()> = embed_state(&server_app).expect("must serialize");
+    let container = document().create_element("div").unwrap();
+    container.set_inner_html(&state.render_to_string());
+    document().body().unwrap().append_child(&container).unwrap();
+
+    let program = Program::<App>::from_embedded_state().expect("must have a state");
+    assert_eq!(*program.app(), server_app);
+    assert_eq!(
+        program.app().view().render_to_string(),
+        "<p>&lt;/script&gt;from the server</p>"
+    );
+}
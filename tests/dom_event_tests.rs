@@ -164,3 +164,174 @@ fn remove_event() {
     //the `new` vdom which has no attached event
     assert_eq!(&*text.borrow(), "Start Text");
 }
+
+#[wasm_bindgen_test]
+fn pointer_and_drag_events() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let events = Rc::new(RefCell::new(vec![]));
+    let (pointer_events, drag_events) = (Rc::clone(&events), Rc::clone(&events));
+
+    let elem_id = "pointer-drag-event-test";
+
+    let view: Node<()> = div(
+        vec![
+            id(elem_id),
+            on_pointerdown(move |event: PointerEvent| {
+                pointer_events.borrow_mut().push(event.type_());
+            }),
+            on_drop(move |event: DragEvent| {
+                assert_eq!(get_drag_data(&event, "text/plain"), None);
+                drag_events.borrow_mut().push(event.type_());
+            }),
+        ],
+        vec![],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view)
+        .expect("must not error");
+
+    let element = sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap();
+    let target = web_sys::EventTarget::from(element);
+    target
+        .dispatch_event(&PointerEvent::new("pointerdown").unwrap())
+        .unwrap();
+    target
+        .dispatch_event(&DragEvent::new("drop").unwrap())
+        .unwrap();
+
+    assert_eq!(&*events.borrow(), &["pointerdown", "drop"]);
+}
+
+#[wasm_bindgen_test]
+fn pointer_capture_errors() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    // an event which is not being dispatched has no element listening to it
+    let event = PointerEvent::new("pointerdown").unwrap();
+    assert!(set_pointer_capture(&event).is_err());
+    assert!(release_pointer_capture(&event).is_err());
+    assert!(!has_pointer_capture(&event));
+    // a synthetic drag event has no data transfer
+    let event = DragEvent::new("dragstart").unwrap();
+    assert!(set_drag_data(&event, "text/plain", "data").is_err());
+
+    let captured = Rc::new(RefCell::new(None));
+    let captured_clone = Rc::clone(&captured);
+    let elem_id = "pointer-capture-test";
+    let view: Node<()> = div(
+        vec![
+            id(elem_id),
+            on_pointerdown(move |event: PointerEvent| {
+                // the synthetic pointer is not active, so it can not be captured
+                *captured_clone.borrow_mut() = Some(set_pointer_capture(&event).is_ok());
+            }),
+        ],
+        vec![],
+    );
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view)
+        .expect("must not error");
+
+    let element = sauron_core::dom::document()
+        .get_element_by_id(elem_id)
+        .unwrap();
+    web_sys::EventTarget::from(element)
+        .dispatch_event(&PointerEvent::new("pointerdown").unwrap())
+        .unwrap();
+    assert_eq!(*captured.borrow(), Some(false));
+}
+
+#[wasm_bindgen_test]
+fn change_listener_options() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let clicks = Rc::new(RefCell::new(0));
+
+    let elem_id = "listener-options-test";
+    let view = |capture: bool, listening: bool| -> Node<()> {
+        let clicks = Rc::clone(&clicks);
+        let listener = on_click(move |_| *clicks.borrow_mut() += 1);
+        let listener = if capture {
+            listener.capture()
+        } else {
+            listener
+        };
+        div(
+            vec![id(elem_id)]
+                .into_iter()
+                .chain(listening.then_some(listener)),
+            vec![],
+        )
+    };
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view(false, true))
+        .expect("must update dom");
+    // only the options of the listener are changed
+    simple_program
+        .update_dom_with_vdom(view(true, true))
+        .expect("must update dom");
+
+    let target = web_sys::EventTarget::from(
+        sauron_core::dom::document()
+            .get_element_by_id(elem_id)
+            .unwrap(),
+    );
+    let click = || web_sys::MouseEvent::new("click").unwrap();
+    target.dispatch_event(&click()).unwrap();
+    // the replaced listener is detached, so it is not called anymore
+    assert_eq!(*clicks.borrow(), 1);
+
+    // the capture listener is detached with the capture flag
+    simple_program
+        .update_dom_with_vdom(view(true, false))
+        .expect("must update dom");
+    target.dispatch_event(&click()).unwrap();
+    assert_eq!(*clicks.borrow(), 1);
+}
+
+#[wasm_bindgen_test]
+fn remove_all_listeners_of_an_event() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let clicks = Rc::new(RefCell::new(0));
+
+    let elem_id = "remove-all-listeners-test";
+    let view = |listening: bool| -> Node<()> {
+        let listeners = (0..2).map(|_| {
+            let clicks = Rc::clone(&clicks);
+            on_click(move |_| *clicks.borrow_mut() += 1)
+        });
+        div(
+            vec![id(elem_id)]
+                .into_iter()
+                .chain(listeners.filter(|_| listening)),
+            vec![],
+        )
+    };
+
+    let mut simple_program = simple_program();
+    simple_program
+        .update_dom_with_vdom(view(true))
+        .expect("must update dom");
+
+    let target = web_sys::EventTarget::from(
+        sauron_core::dom::document()
+            .get_element_by_id(elem_id)
+            .unwrap(),
+    );
+    let click = || web_sys::MouseEvent::new("click").unwrap();
+    target.dispatch_event(&click()).unwrap();
+    assert_eq!(*clicks.borrow(), 2);
+
+    // both of the listeners of the click event are detached
+    simple_program
+        .update_dom_with_vdom(view(false))
+        .expect("must update dom");
+    target.dispatch_event(&click()).unwrap();
+    assert_eq!(*clicks.borrow(), 2);
+}
@@ -0,0 +1,181 @@
+#![deny(warnings)]
+use sauron::{
+    dom::HydrationMismatch,
+    html::{attributes::*, events::*, fragment, node_list, symbol, *},
+    *,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Default)]
+struct Counter {
+    count: i32,
+}
+
+impl Application for Counter {
+    type MSG = i32;
+
+    fn update(&mut self, msg: i32) -> Cmd<i32> {
+        self.count += msg;
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<i32> {
+        main(
+            [class("counter")],
+            [
+                h1([], [text("Counter")]),
+                button([id("hydrate-inc"), on_click(|_| 1)], [text("+")]),
+                span([id("hydrate-count")], [text(self.count)]),
+            ],
+        )
+    }
+}
+
+/// put the server rendered html in the document and return its root node
+fn server_rendered(html: &str) -> web_sys::Node {
+    let container = document().create_element("div").unwrap();
+    container.set_inner_html(html);
+    document().body().unwrap().append_child(&container).unwrap();
+    container.first_child().unwrap()
+}
+
+#[wasm_bindgen_test]
+fn hydrate_adopts_the_server_rendered_dom() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let server_html = Counter::default().view().render_to_string();
+    let mount_node = server_rendered(&server_html);
+    let server_button = document().get_element_by_id("hydrate-inc").unwrap();
+
+    let mut program = Program::new(Counter::default());
+    let mismatches = program.hydrate(&mount_node);
+    assert_eq!(mismatches, vec![]);
+
+    // the existing elements are used as is
+    let root_node = program.root_node.borrow().as_ref().unwrap().as_node();
+    assert_eq!(root_node, mount_node);
+    assert_eq!(
+        document().get_element_by_id("hydrate-inc").unwrap(),
+        server_button
+    );
+
+    // the event listeners are attached to the existing elements
+    let button: web_sys::HtmlElement = server_button.unchecked_into();
+    button.click();
+    assert_eq!(program.app().count, 1);
+}
+
+#[wasm_bindgen_test]
+fn hydrate_ignores_the_whitespace_of_pretty_render() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let server_html = Counter::default().view().render_to_string_pretty();
+    let mount_node = server_rendered(&server_html);
+
+    let mut program = Program::new(Counter::default());
+    let mismatches = program.hydrate(&mount_node);
+    assert_eq!(mismatches, vec![]);
+}
+
+#[wasm_bindgen_test]
+fn hydrate_reports_and_replaces_mismatches() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let mount_node = server_rendered(
+        r#"<main class="counter"><h2>Counter</h2><button id="hydrate-mismatch">+</button><span>5</span><p>extra</p></main>"#,
+    );
+
+    let mut program = Program::new(Counter::default());
+    let mismatches = program.hydrate(&mount_node);
+    assert_eq!(
+        mismatches,
+        vec![
+            HydrationMismatch {
+                path: TreePath::new([0]),
+                reason: "expecting <h1>, found <h2>".to_string(),
+            },
+            HydrationMismatch {
+                path: TreePath::new([2, 0]),
+                reason: r#"expecting text "0", found text "5""#.to_string(),
+            },
+            HydrationMismatch {
+                path: TreePath::new([]),
+                reason: "found extra <p>".to_string(),
+            },
+        ]
+    );
+    let main: web_sys::Element = mount_node.unchecked_into();
+    assert_eq!(
+        main.outer_html(),
+        r#"<main class="counter"><h1>Counter</h1><button id="hydrate-mismatch">+</button><span>0</span></main>"#
+    );
+}
+
+#[derive(Default)]
+struct Symbols;
+
+impl Application for Symbols {
+    type MSG = ();
+
+    fn update(&mut self, _msg: ()) -> Cmd<()> {
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        div(
+            [class("symbols")],
+            [
+                text("a"),
+                fragment([symbol("&nbsp;"), text("b")]),
+                node_list([span([], [symbol("&amp;")])]),
+            ],
+        )
+    }
+}
+
+#[wasm_bindgen_test]
+fn hydrate_symbols_nested_in_fragments() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let server_html = Symbols.view().render_to_string();
+    let mount_node = server_rendered(&server_html);
+
+    let mut program = Program::new(Symbols);
+    // the children with symbols are created, since the browser merges them with the text
+    let mismatches = program.hydrate(&mount_node);
+    assert_eq!(mismatches, vec![]);
+    let root: web_sys::Element = program
+        .root_node
+        .borrow()
+        .as_ref()
+        .unwrap()
+        .as_node()
+        .unchecked_into();
+    assert_eq!(root.outer_html(), server_html);
+}
+
+#[wasm_bindgen_test]
+fn hydrate_symbol_at_the_root() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    struct RootSymbol;
+    impl Application for RootSymbol {
+        type MSG = ();
+
+        fn update(&mut self, _msg: ()) -> Cmd<()> {
+            Cmd::none()
+        }
+
+        fn view(&self) -> Node<()> {
+            symbol("&hellip;")
+        }
+    }
+    let mount_node = server_rendered("<span>loading</span>");
+
+    let mut program = Program::new(RootSymbol);
+    let mismatches = program.hydrate(&mount_node);
+    assert_eq!(
+        mismatches,
+        vec![HydrationMismatch {
+            path: TreePath::new([]),
+            reason: r#"symbol "&hellip;" can not be adopted, it is created"#.to_string(),
+        }]
+    );
+}
@@ -0,0 +1,89 @@
+#![deny(warnings)]
+use sauron::{
+    dom::{ChannelTransport, RemoteClient, ServerMessage, Transport},
+    html::{attributes::id, *},
+    vdom::{
+        wire::{self, HandlerRegistry, WirePatch, WirePatchType},
+        TreePath,
+    },
+    *,
+};
+use std::io;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn send(server: &mut ChannelTransport, message: &ServerMessage) {
+    server
+        .send(wire::to_binary(message).expect("must encode"))
+        .expect("must send");
+}
+
+fn mounted_client() -> (
+    ChannelTransport,
+    RemoteClient<ChannelTransport>,
+    web_sys::Element,
+) {
+    let (mut server, client_end) = ChannelTransport::pair();
+    let container = document().create_element("div").unwrap();
+    document().body().unwrap().append_child(&container).unwrap();
+    let mut client = RemoteClient::new(&container, client_end);
+
+    let view: Node<()> = div([id("remote")], [p([], [text("hello")])]);
+    let mut registry = HandlerRegistry::new();
+    send(
+        &mut server,
+        &ServerMessage::Mount(view.to_wire(&mut registry)),
+    );
+    assert_eq!(client.process_incoming().expect("must mount"), 1);
+    (server, client, container)
+}
+
+#[wasm_bindgen_test]
+fn apply_the_patches_from_the_server() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let (mut server, mut client, container) = mounted_client();
+    send(
+        &mut server,
+        &ServerMessage::Patches(vec![WirePatch {
+            tag: None,
+            patch_path: TreePath::new([0, 0]),
+            patch_type: WirePatchType::SetText {
+                text: "world".to_string(),
+            },
+        }]),
+    );
+    assert_eq!(client.process_incoming().expect("must patch"), 1);
+    assert_eq!(
+        container.inner_html(),
+        r#"<div id="remote"><p>world</p></div>"#
+    );
+}
+
+#[wasm_bindgen_test]
+fn malformed_messages_are_errors() {
+    console_log::init_with_level(log::Level::Trace).ok();
+    let (mut server, mut client, container) = mounted_client();
+
+    server.send(vec![0xff, 0xff, 0xff]).expect("must send");
+    let err = client.process_incoming().expect_err("must not decode");
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    // the patch is for a node which is not in the client
+    send(
+        &mut server,
+        &ServerMessage::Patches(vec![WirePatch {
+            tag: Some("span".to_string()),
+            patch_path: TreePath::new([5, 2]),
+            patch_type: WirePatchType::RemoveNode,
+        }]),
+    );
+    let err = client.process_incoming().expect_err("must not convert");
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    // the client keeps its view
+    assert_eq!(
+        container.inner_html(),
+        r#"<div id="remote"><p>hello</p></div>"#
+    );
+}
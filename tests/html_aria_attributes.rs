@@ -0,0 +1,109 @@
+#![deny(warnings)]
+use sauron::{
+    html::{attributes::aria::*, lookup},
+    *,
+};
+use sauron_html_parser::parse_html;
+
+#[test]
+fn render_typed_aria_attributes() {
+    let view: Node<()> = button(
+        [
+            role(Role::Switch),
+            aria_checked(Tristate::Mixed),
+            aria_expanded(false),
+            aria_haspopup(HasPopup::Menu),
+            aria_controls(["menu", "panel"]),
+            aria_live(Politeness::Polite),
+            aria_relevant([Relevant::Additions, Relevant::Text]),
+            aria_level(2),
+            aria_valuenow(0.5),
+        ],
+        [text("toggle")],
+    );
+    assert_eq!(
+        view.render_to_string(),
+        "<button role=\"switch\" aria-checked=\"mixed\" aria-expanded=\"false\" aria-haspopup=\"menu\" \
+        aria-controls=\"menu panel\" aria-live=\"polite\" aria-relevant=\"additions text\" aria-level=\"2\" \
+        aria-valuenow=\"0.5\">toggle</button>"
+    );
+}
+
+#[test]
+fn bool_conversions() {
+    let view: Node<()> = div(
+        [
+            aria_pressed(true),
+            aria_current(false),
+            aria_invalid(true),
+            aria_label(String::from("label")),
+        ],
+        [],
+    );
+    assert_eq!(
+        view.render_to_string(),
+        "<div aria-pressed=\"true\" aria-current=\"false\" aria-invalid=\"true\" aria-label=\"label\"></div>"
+    );
+}
+
+#[test]
+fn lookup_aria_attributes() {
+    assert_eq!(
+        lookup::match_attribute("aria-expanded"),
+        Some("aria-expanded")
+    );
+    assert_eq!(
+        lookup::attribute_function("aria-labelledby"),
+        Some("aria_labelledby")
+    );
+    assert_eq!(lookup::attribute_function("role"), Some("role"));
+    assert_eq!(lookup::match_attribute("aria-unknown"), None);
+}
+
+#[test]
+fn typed_aria_in_node_macro() {
+    let is_open = true;
+    let view: Node<()> = node! {
+        <div role={Role::Dialog} aria-modal={is_open} aria-labelledby={["title"]} aria-describedby="text">
+            <h1 id="title">"Settings"</h1>
+        </div>
+    };
+    let expected: Node<()> = div(
+        [
+            role(Role::Dialog),
+            aria_modal(true),
+            aria_labelledby(["title"]),
+            attr("aria-describedby", "text"),
+        ],
+        [h1([id("title")], [text("Settings")])],
+    );
+    assert_eq!(view, expected);
+}
+
+struct Labelled {
+    label: String,
+    role: String,
+}
+
+#[test]
+fn dynamic_aria_in_node_macro() {
+    let labelled = Labelled {
+        label: "close".to_string(),
+        role: "button".to_string(),
+    };
+    let id = String::from("hint");
+    let view: Node<()> = node! {
+        <span aria-label={&labelled.label} aria-describedby={id} role={labelled.role.clone()}></span>
+    };
+    assert_eq!(
+        view.render_to_string(),
+        r#"<span aria-label="close" aria-describedby="hint" role="button"></span>"#
+    );
+}
+
+#[test]
+fn parse_aria_attributes() {
+    let html = r#"<nav role="navigation" aria-label="main"><a href="/" aria-current="page">home</a></nav>"#;
+    let node: Node<()> = parse_html(html).ok().flatten().expect("must parse");
+    assert_eq!(node.render_to_string(), html);
+}
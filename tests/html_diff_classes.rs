@@ -8,12 +8,11 @@ fn class_with_bool_value() {
     let new = div(vec![class(true)], vec![]);
     assert_eq!(
         diff(&old, &new),
-        vec![Patch::add_attributes(
-            &"div",
-            TreePath::new(vec![]),
-            vec![&class(true)]
-        )],
-        "Should add the new attributes"
+        vec![
+            Patch::add_classes(&"div", TreePath::new(vec![]), ["true"]),
+            Patch::remove_classes(&"div", TreePath::new(vec![]), ["false"]),
+        ],
+        "Should add the new class and remove the old one"
     );
 }
 
@@ -38,11 +37,10 @@ fn parent_of_matching_keyed_are_ignored() {
     );
     assert_eq!(
         diff(&old, &new),
-        vec![Patch::add_attributes(
-            &"ul",
-            TreePath::new(vec![]),
-            vec![&class("changed")]
-        )],
-        "Should add the new attributes"
+        vec![
+            Patch::add_classes(&"ul", TreePath::new(vec![]), ["changed"]),
+            Patch::remove_classes(&"ul", TreePath::new(vec![]), ["original"]),
+        ],
+        "Should add the new class and remove the old one"
     );
 }
@@ -48,19 +48,28 @@ fn change_class_attribute() {
     let new = div(vec![classes(["class1", "difference_class"])], vec![]);
     assert_eq!(
         diff(&old, &new),
-        vec![Patch::add_attributes(
-            &"div",
-            TreePath::new(vec![]),
-            vec![&Attribute::with_multiple_values(
-                None,
-                "class",
-                vec![
-                    AttributeValue::from("class1".to_string()),
-                    AttributeValue::from("difference_class".to_string())
-                ]
-            )]
-        )],
-        "Should add the new attributes"
+        vec![
+            Patch::add_classes(&"div", TreePath::new(vec![]), ["difference_class"]),
+            Patch::remove_classes(&"div", TreePath::new(vec![]), ["class2"]),
+        ],
+        "Should only patch the class names that changed"
+    );
+}
+
+#[test]
+fn reordered_class_names_are_not_patched() {
+    let old: Node<()> = div(vec![class("a b"), classes(["c"])], vec![]);
+    let new = div(vec![classes(["c", "b"]), class("a")], vec![]);
+    assert_eq!(diff(&old, &new), vec![]);
+}
+
+#[test]
+fn class_names_added_with_classes_flag() {
+    let old: Node<()> = div(vec![classes_flag([("active", false), ("big", true)])], vec![]);
+    let new = div(vec![classes_flag([("active", true), ("big", true)])], vec![]);
+    assert_eq!(
+        diff(&old, &new),
+        vec![Patch::add_classes(&"div", TreePath::new(vec![]), ["active"])]
     );
 }
 
@@ -129,9 +138,12 @@ fn truncate_children_different_attributes() {
     assert_eq!(
         patch,
         vec![
-            Patch::add_attributes(&"div", TreePath::new(vec![0]), vec![&class("class5")]),
-            Patch::add_attributes(&"div", TreePath::new(vec![1]), vec![&class("class6")]),
-            Patch::add_attributes(&"div", TreePath::new(vec![2]), vec![&class("class7")]),
+            Patch::add_classes(&"div", TreePath::new(vec![0]), ["class5"]),
+            Patch::remove_classes(&"div", TreePath::new(vec![0]), ["class1"]),
+            Patch::add_classes(&"div", TreePath::new(vec![1]), ["class6"]),
+            Patch::remove_classes(&"div", TreePath::new(vec![1]), ["class2"]),
+            Patch::add_classes(&"div", TreePath::new(vec![2]), ["class7"]),
+            Patch::remove_classes(&"div", TreePath::new(vec![2]), ["class3"]),
             Patch::remove_node(Some(&"div"), TreePath::new(vec![3]),),
             Patch::remove_node(Some(&"div"), TreePath::new(vec![4]),),
             Patch::remove_node(Some(&"div"), TreePath::new(vec![5]),),
@@ -261,12 +273,12 @@ fn add_style_attributes() {
     let new = div(vec![style!("display": "none")], vec![]);
     assert_eq!(
         diff(&old, &new),
-        vec![Patch::add_attributes(
+        vec![Patch::set_styles(
             &"div",
             TreePath::new(vec![]),
-            vec![&style!("display": "none")]
+            vec![&Style::new("display", "none")]
         )],
-        "Add attributes",
+        "Set styles",
     );
 }
 
@@ -282,17 +294,10 @@ fn add_style_attributes_1_change() {
     );
     assert_eq!(
         diff(&old, &new),
-        vec![Patch::add_attributes(
+        vec![Patch::set_styles(
             &"div",
             TreePath::new(vec![]),
-            vec![&Attribute::with_multiple_values(
-                None,
-                "style",
-                vec![AttributeValue::Style(vec![
-                    Style::new("display", "none"),
-                    Style::new("position", "absolute")
-                ])]
-            )]
+            vec![&Style::new("display", "none")]
         )],
     );
 }
@@ -310,6 +315,40 @@ fn add_style_attributes_no_changes() {
     assert_eq!(diff(&old, &new), vec![],);
 }
 
+#[test]
+fn set_and_remove_style_properties() {
+    let old: Node<()> = div(
+        vec![styles([("display", "block"), ("position", "absolute")])],
+        vec![],
+    );
+    let new = div(vec![styles([("position", "relative")])], vec![]);
+    assert_eq!(
+        diff(&old, &new),
+        vec![
+            Patch::set_styles(
+                &"div",
+                TreePath::new(vec![]),
+                vec![&Style::new("position", "relative")]
+            ),
+            Patch::remove_styles(&"div", TreePath::new(vec![]), vec!["display"]),
+        ],
+    );
+}
+
+#[test]
+fn style_changed_into_plain_value() {
+    let old: Node<()> = div(vec![styles([("display", "block")])], vec![]);
+    let new = div(vec![attr("style", "display:none;")], vec![]);
+    assert_eq!(
+        diff(&old, &new),
+        vec![Patch::add_attributes(
+            &"div",
+            TreePath::new(vec![]),
+            vec![&attr("style", "display:none;")]
+        )],
+    );
+}
+
 #[test]
 fn remove_style_attributes() {
     let old: Node<()> = div(vec![style!("display": "block")], vec![]);
@@ -372,11 +411,7 @@ fn text_changed_in_keyed_elements() {
     assert_eq!(
         patch,
         vec![
-            Patch::replace_node(
-                None,
-                TreePath::new(vec![0, 2, 0]),
-                vec![&text("item3 with changes")]
-            ),
+            Patch::set_text(TreePath::new(vec![0, 2, 0]), "item3 with changes"),
             Patch::remove_node(Some(&"article"), TreePath::new(vec![0, 0]),),
         ]
     );
@@ -405,13 +440,10 @@ fn multiple_style_calls() {
     println!("patches: {:#?}", patches);
     assert_eq!(
         patches,
-        vec![Patch::add_attributes(
+        vec![Patch::set_styles(
             &"div",
             TreePath::new(vec![]),
-            vec![
-                &style!("font-family": "monospace1"),
-                &style!("display": "flex")
-            ]
+            vec![&Style::new("font-family", "monospace1")]
         )]
     );
 }
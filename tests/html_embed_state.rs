@@ -0,0 +1,47 @@
+#![deny(warnings)]
+use sauron::{html::embed_state, Node};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct App {
+    name: String,
+    items: Vec<String>,
+}
+
+fn hostile_app() -> App {
+    App {
+        name: "</script><script>alert(1)</script>".to_string(),
+        items: vec!["<!--".to_string(), "a < b && c > d".to_string()],
+    }
+}
+
+/// extract the json in the rendered script element
+fn script_content(html: &str) -> &str {
+    let start = html.find('>').expect("must have a start tag") + 1;
+    let end = html.rfind("</script>").expect("must have an end tag");
+    &html[start..end]
+}
+
+#[test]
+fn embedded_state_is_in_a_json_script() {
+    let node: Node<()> = embed_state(&hostile_app()).expect("must serialize");
+    let html = node.render_to_string();
+    assert!(html.starts_with(r#"<script type="application/json" id="sauron-embedded-state">"#));
+    assert!(html.ends_with("</script>"));
+}
+
+#[test]
+fn embedded_state_can_not_close_the_script() {
+    let node: Node<()> = embed_state(&hostile_app()).expect("must serialize");
+    let html = node.render_to_string();
+    let content = script_content(&html);
+    assert!(!content.contains('<'));
+}
+
+#[test]
+fn embedded_state_deserializes_to_the_same_state() {
+    let node: Node<()> = embed_state(&hostile_app()).expect("must serialize");
+    let html = node.render_to_string();
+    let app: App = serde_json::from_str(script_content(&html)).expect("must deserialize");
+    assert_eq!(app, hostile_app());
+}
@@ -16,11 +16,7 @@ fn comments_next_to_each_other() {
     assert_eq!(
         patch,
         vec![
-            Patch::replace_node(
-                None,
-                TreePath::new(vec![1]),
-                vec![&comment("world".to_string())]
-            ),
+            Patch::set_text(TreePath::new(vec![1]), "world"),
             Patch::remove_node(None, TreePath::new(vec![2]),)
         ]
     );
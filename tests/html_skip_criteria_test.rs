@@ -33,7 +33,7 @@ fn must_diff() {
         patch,
         vec![
             Patch::add_attributes(&"div", TreePath::new([]), &[skip_criteria(1001)]),
-            Patch::replace_node(None, TreePath::new([0]), &[text("the difference here")])
+            Patch::set_text(TreePath::new([0]), "the difference here")
         ]
     );
 }
@@ -1,5 +1,5 @@
 #![deny(warnings)]
-use sauron::*;
+use sauron::{html::attributes::Style, *};
 
 #[test]
 fn style_calcd_changed() {
@@ -14,9 +14,9 @@ fn style_calcd_changed() {
     );
 
     let patches: Vec<Patch<&'static str>> = diff(&old, &new);
-    let styl = style! {width: format!("calc(50% + {}", px(200))};
+    let styl = Style::new("width", format!("calc(50% + {}", px(200)));
     let expected: Vec<Patch<&'static str>> =
-        vec![Patch::add_attributes(&"div", TreePath::new([]), [&styl])];
+        vec![Patch::set_styles(&"div", TreePath::new([]), [&styl])];
     assert_eq!(expected, patches);
 }
 
@@ -37,8 +37,8 @@ fn style_calcd_changed_with_event() {
     );
 
     let patches: Vec<Patch<()>> = diff(&old, &new);
-    let styl = style! {width: format!("calc(50% + {}", px(200))};
-    let expected: Vec<Patch<()>> = vec![Patch::add_attributes(&"div", TreePath::new([]), [&styl])];
+    let styl = Style::new("width", format!("calc(50% + {}", px(200)));
+    let expected: Vec<Patch<()>> = vec![Patch::set_styles(&"div", TreePath::new([]), [&styl])];
     assert_eq!(expected, patches);
 }
 
@@ -92,11 +92,11 @@ fn app_editor_width_allocation_bug() {
     );
 
     let patches: Vec<Patch<()>> = diff(&old, &new);
-    let styl_1 = style! {width: format!("calc(50% + {}", px(200))};
-    let styl_2 = style! {width: format!("calc(50% - {}", px(200))};
+    let styl_1 = Style::new("width", format!("calc(50% + {}", px(200)));
+    let styl_2 = Style::new("width", format!("calc(50% - {}", px(200)));
     let expected: Vec<Patch<()>> = vec![
-        Patch::add_attributes(&"div", TreePath::new([0]), [&styl_1]),
-        Patch::add_attributes(&"div", TreePath::new([2]), [&styl_2]),
+        Patch::set_styles(&"div", TreePath::new([0]), [&styl_1]),
+        Patch::set_styles(&"div", TreePath::new([2]), [&styl_2]),
     ];
     assert_eq!(expected, patches);
 }
@@ -239,11 +239,7 @@ fn replace_text_node() {
 
     assert_eq!(
         diff(&old, &new),
-        vec![Patch::replace_node(
-            None,
-            TreePath::new(vec![]),
-            vec![&text("New")]
-        )],
+        vec![Patch::set_text(TreePath::new(vec![]), "New")],
         "ReplaceNode text node",
     );
 }
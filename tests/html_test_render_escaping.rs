@@ -0,0 +1,180 @@
+#![deny(warnings)]
+use sauron::{
+    html::{
+        attributes::{class, href, src, styles, title},
+        *,
+    },
+    Node,
+};
+
+const HOSTILE: &str = r#"</div>"'><script>alert(1)</script>&amp;<!-- -->"#;
+
+#[test]
+fn text_is_escaped() {
+    let view: Node<()> = div([], [text(HOSTILE)]);
+    assert_eq!(
+        view.render_to_string(),
+        r#"<div>&lt;/div&gt;"'&gt;&lt;script&gt;alert(1)&lt;/script&gt;&amp;amp;&lt;!-- --&gt;</div>"#
+    );
+}
+
+#[test]
+fn text_among_siblings_is_escaped() {
+    let view: Node<()> = ul([], [li([], [text("a")]), text("<li>injected</li>")]);
+    assert_eq!(
+        view.render_to_string(),
+        "<ul><li>a</li>&lt;li&gt;injected&lt;/li&gt;</ul>"
+    );
+}
+
+#[test]
+fn attribute_value_can_not_break_out_of_quotes() {
+    let view: Node<()> = img([src(r#"x" onerror="alert(1)"#)], []);
+    assert_eq!(
+        view.render_to_string(),
+        r#"<img src="x&quot; onerror=&quot;alert(1)"/>"#
+    );
+}
+
+#[test]
+fn attribute_value_is_escaped() {
+    let view: Node<()> = a([href(HOSTILE), title("Tom & Jerry")], [text("link")]);
+    assert_eq!(
+        view.render_to_string(),
+        r#"<a href="&lt;/div&gt;&quot;'&gt;&lt;script&gt;alert(1)&lt;/script&gt;&amp;amp;&lt;!-- --&gt;" title="Tom &amp; Jerry">link</a>"#
+    );
+}
+
+#[test]
+fn merged_class_values_are_escaped() {
+    let view: Node<()> = div([class("a"), class(r#"b" onclick="alert(1)"#)], []);
+    assert_eq!(
+        view.render_to_string(),
+        r#"<div class="a b&quot; onclick=&quot;alert(1)"></div>"#
+    );
+}
+
+#[test]
+fn style_value_is_escaped() {
+    let view: Node<()> = div(
+        [styles([("background", r#"url("x") ;" onmouseover="alert(1)"#)])],
+        [],
+    );
+    assert_eq!(
+        view.render_to_string(),
+        r#"<div style="background:url(&quot;x&quot;) ;&quot; onmouseover=&quot;alert(1);"></div>"#
+    );
+}
+
+#[test]
+fn comment_can_not_be_closed_early() {
+    let view: Node<()> = div([], [comment("--><script>alert(1)</script><!--")]);
+    assert_eq!(
+        view.render_to_string(),
+        "<div><!--- -><script>alert(1)</script><!- - --></div>"
+    );
+}
+
+#[test]
+fn comment_with_leading_arrow() {
+    let view: Node<()> = comment(">x");
+    assert_eq!(view.render_to_string(), "<!-- >x-->");
+    let view: Node<()> = comment("->x");
+    assert_eq!(view.render_to_string(), "<!-- ->x-->");
+}
+
+#[test]
+fn doctype_can_not_be_closed_early() {
+    let view: Node<()> = doctype("html><script>alert(1)</script");
+    assert_eq!(
+        view.render_to_string(),
+        "<!doctype htmlscriptalert(1)/script>"
+    );
+}
+
+#[test]
+fn script_text_can_not_close_the_script() {
+    let view: Node<()> = script(
+        [],
+        [text(r#"let s = "</script><script>alert(1)</script>";"#)],
+    );
+    assert_eq!(
+        view.render_to_string(),
+        r#"<script>let s = "<\/script><script>alert(1)<\/script>";</script>"#
+    );
+}
+
+#[test]
+fn script_text_is_not_entity_escaped() {
+    let view: Node<()> = script([], [text("if (a < b && b > c) { x = \"y\"; }")]);
+    assert_eq!(
+        view.render_to_string(),
+        r#"<script>if (a < b && b > c) { x = "y"; }</script>"#
+    );
+}
+
+#[test]
+fn style_text_can_not_close_the_style() {
+    let view: Node<()> = tags::style(
+        [],
+        [text("a > b { content: \"</style><script>alert(1)</script>\" }")],
+    );
+    assert_eq!(
+        view.render_to_string(),
+        r#"<style>a > b { content: "<\/style><script>alert(1)<\/script>" }</style>"#
+    );
+}
+
+#[test]
+fn multiple_text_in_script_are_raw_text() {
+    let view: Node<()> = script([], [text("a < b;"), text("</script>")]);
+    assert_eq!(
+        view.render_to_string(),
+        r#"<script>a < b;<\/script></script>"#
+    );
+}
+
+#[test]
+fn textarea_and_title_are_escaped() {
+    let view: Node<()> = div(
+        [],
+        [
+            textarea([], [text("</textarea><script>alert(1)</script>")]),
+            tags::title([], [text("</title><b>")]),
+        ],
+    );
+    assert_eq!(
+        view.render_to_string(),
+        "<div><textarea>&lt;/textarea&gt;&lt;script&gt;alert(1)&lt;/script&gt;</textarea><title>&lt;/title&gt;&lt;b&gt;</title></div>"
+    );
+}
+
+#[test]
+fn symbol_is_not_escaped() {
+    let view: Node<()> = span([], [symbol("&nbsp;"), text("&nbsp;")]);
+    assert_eq!(view.render_to_string(), "<span>&nbsp;&amp;nbsp;</span>");
+}
+
+#[test]
+fn text_in_fragment_and_node_list_is_escaped() {
+    let view: Node<()> = div(
+        [],
+        [
+            fragment([text("<a>"), node_list([text("<b>"), comment("--")])]),
+            text("&"),
+        ],
+    );
+    assert_eq!(
+        view.render_to_string(),
+        "<div>&lt;a&gt;&lt;b&gt;<!--- - -->&amp;</div>"
+    );
+}
+
+#[test]
+fn pretty_render_is_escaped() {
+    let view: Node<()> = div([class("\"")], [p([], [text("<")]), p([], [text(">")])]);
+    assert_eq!(
+        view.render_to_string_pretty(),
+        "<div class=\"&quot;\">\n  <p>&lt;</p>\n  <p>&gt;</p>\n</div>"
+    );
+}
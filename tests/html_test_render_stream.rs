@@ -0,0 +1,115 @@
+#![deny(warnings)]
+use sauron::{
+    html::{
+        attributes::{class, lang},
+        *,
+    },
+    vdom::FlushPolicy,
+    Node,
+};
+use std::io;
+
+fn page() -> Node<()> {
+    html(
+        [lang("en")],
+        [
+            head([], [tags::title([], [text("<rows>")]), meta([], [])]),
+            body(
+                [],
+                [
+                    header([], [h1([], [text("Rows")])]),
+                    table(
+                        [class("rows")],
+                        (0..5).map(|i| tr([], [td([], [text(i)]), td([], [text("&")])])),
+                    ),
+                ],
+            ),
+        ],
+    )
+}
+
+/// records each chunk that is flushed
+#[derive(Default)]
+struct FlushRecorder {
+    pending: Vec<u8>,
+    flushed: Vec<String>,
+}
+
+impl io::Write for FlushRecorder {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let chunk = std::mem::take(&mut self.pending);
+        self.flushed
+            .push(String::from_utf8(chunk).expect("must be utf8"));
+        Ok(())
+    }
+}
+
+#[test]
+fn joined_chunks_is_the_same_as_render_to_string() {
+    let view = page();
+    let policy = FlushPolicy::new()
+        .flush_after("head")
+        .flush_after("tr")
+        .chunk_size(16);
+    let chunks: Vec<u8> = view.render_chunks(policy).flatten().collect();
+    assert_eq!(String::from_utf8(chunks).unwrap(), view.render_to_string());
+}
+
+#[test]
+fn no_flush_points_is_one_chunk() {
+    let view = page();
+    let chunks: Vec<Vec<u8>> = view.render_chunks(FlushPolicy::new()).collect();
+    assert_eq!(chunks, vec![view.render_to_string().into_bytes()]);
+}
+
+#[test]
+fn flush_after_head_and_header() {
+    let view = page();
+    let mut recorder = FlushRecorder::default();
+    view.render_to_writer(
+        &mut recorder,
+        FlushPolicy::new().flush_after("head").flush_after("header"),
+    )
+    .expect("must write");
+
+    assert_eq!(
+        recorder.flushed,
+        vec![
+            r#"<html lang="en"><head><title>&lt;rows&gt;</title><meta/></head>"#.to_string(),
+            "<body><header><h1>Rows</h1></header>".to_string(),
+            format!(
+                r#"<table class="rows">{}</table></body></html>"#,
+                (0..5)
+                    .map(|i| format!("<tr><td>{i}</td><td>&amp;</td></tr>"))
+                    .collect::<String>()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn flush_by_chunk_size() {
+    let view = page();
+    let chunks: Vec<Vec<u8>> = view
+        .render_chunks(FlushPolicy::new().chunk_size(32))
+        .collect();
+    assert!(chunks.len() > 1);
+    // only the last chunk can be smaller than the chunk size
+    for chunk in &chunks[..chunks.len() - 1] {
+        assert!(chunk.len() >= 32);
+    }
+}
+
+#[test]
+fn pretty_render_is_unaffected() {
+    let view: Node<()> = div([], [p([], [text("a")]), p([], [text("b")])]);
+    assert_eq!(
+        view.render_to_string_pretty(),
+        "<div>\n  <p>a</p>\n  <p>b</p>\n</div>"
+    );
+}
@@ -0,0 +1,83 @@
+#![deny(warnings)]
+use sauron::{
+    assert_view_snapshot,
+    html::attributes::{class, r#type, value},
+    vdom::snapshot::{check_snapshot, SnapshotError},
+    *,
+};
+use std::fs;
+use std::path::PathBuf;
+
+fn todo_list(items: &[(&str, bool)]) -> Node<()> {
+    section(
+        [class("todoapp")],
+        [
+            input([class("new-todo"), r#type("text"), value("")], []),
+            ul(
+                [class("todo-list")],
+                items.iter().map(|(label, completed)| {
+                    li(
+                        [class("todo"), classes_flag([("completed", *completed)])],
+                        [text(label)],
+                    )
+                }),
+            ),
+        ],
+    )
+}
+
+/// a new empty directory for the snapshots of this test
+fn snapshot_dir(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("sauron-snapshots-{}-{test}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn stored_snapshot() {
+    assert_view_snapshot!(
+        "todo_list",
+        todo_list(&[("write", true), ("review", false)])
+    );
+}
+
+#[test]
+fn missing_snapshot_writes_the_new_rendering() {
+    let dir = snapshot_dir("missing");
+    let rendered = todo_list(&[]).render_to_string_pretty();
+    let err = check_snapshot(&dir, "empty", &rendered, false).unwrap_err();
+    assert!(matches!(err, SnapshotError::Missing { .. }), "{err}");
+    assert_eq!(
+        fs::read_to_string(dir.join("empty.html.new")).unwrap(),
+        format!("{rendered}\n")
+    );
+    assert!(!dir.join("empty.html").exists());
+}
+
+#[test]
+fn mismatched_snapshot_reports_the_line() {
+    let dir = snapshot_dir("mismatch");
+    let old = todo_list(&[("write", false)]).render_to_string_pretty();
+    let new = todo_list(&[("write", true)]).render_to_string_pretty();
+    check_snapshot(&dir, "list", &old, true).unwrap();
+    let err = check_snapshot(&dir, "list", &new, false).unwrap_err();
+    match err {
+        SnapshotError::Mismatch {
+            line,
+            expected,
+            found,
+            ..
+        } => {
+            assert_eq!(line, 4);
+            assert!(expected.contains(r#"class="todo""#), "{expected}");
+            assert!(found.contains(r#"class="todo completed""#), "{found}");
+        }
+        err => panic!("expecting a mismatch, found: {err}"),
+    }
+    assert!(dir.join("list.html.new").exists());
+
+    // blessing overwrites the snapshot and removes the new file
+    check_snapshot(&dir, "list", &new, true).unwrap();
+    assert!(!dir.join("list.html.new").exists());
+    check_snapshot(&dir, "list", &new, false).unwrap();
+}
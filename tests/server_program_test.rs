@@ -0,0 +1,362 @@
+#![deny(warnings)]
+use futures::channel::oneshot;
+use sauron::{
+    dom::{
+        events::RemoteEvent, ChannelTransport, ClientMessage, ServerMessage, ServerProgram,
+        Transport,
+    },
+    html::{
+        attributes::{id, key},
+        events::on,
+        *,
+    },
+    vdom::{
+        wire::{self, HandlerId, WireAttributeValue, WireNode, WirePatch, WirePatchType},
+        TreePath,
+    },
+    Application, Attribute, Cmd, Node,
+};
+
+enum Msg {
+    Loaded,
+    Increment,
+    SetName(String),
+}
+
+#[derive(Default)]
+struct Greeter {
+    loaded: bool,
+    count: i32,
+    name: String,
+}
+
+impl Application for Greeter {
+    type MSG = Msg;
+
+    fn init(&mut self) -> Cmd<Msg> {
+        Cmd::once(async { Msg::Loaded })
+    }
+
+    fn update(&mut self, msg: Msg) -> Cmd<Msg> {
+        match msg {
+            Msg::Loaded => self.loaded = true,
+            Msg::Increment => self.count += 1,
+            Msg::SetName(name) => self.name = name,
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div(
+            [],
+            [
+                p([], [text(if self.loaded { "ready" } else { "loading" })]),
+                button(
+                    [id("inc"), on("click", |_| Msg::Increment)],
+                    [text(self.count)],
+                ),
+                input(
+                    [
+                        id("name"),
+                        on("input", |event| {
+                            let remote = event.as_remote().expect("must be a remote event");
+                            Msg::SetName(remote.value.clone().unwrap_or_default())
+                        }),
+                    ],
+                    [],
+                ),
+                span([], [text(format!("hello {}", self.name))]),
+            ],
+        )
+    }
+}
+
+/// return the handler id of the first listener in the element with this id
+fn find_handler(node: &WireNode, element_id: &str) -> Option<HandlerId> {
+    let WireNode::Element(elm) = node else {
+        return None;
+    };
+    let has_id = elm.attrs.iter().any(|attr| {
+        attr.name == "id"
+            && attr.value.iter().any(
+                |v| matches!(v, WireAttributeValue::Simple(v) if v.as_str() == Some(element_id)),
+            )
+    });
+    if has_id {
+        elm.attrs
+            .iter()
+            .flat_map(|attr| &attr.value)
+            .find_map(|v| match v {
+                WireAttributeValue::EventListener(handler, _options) => Some(*handler),
+                _ => None,
+            })
+    } else {
+        elm.children
+            .iter()
+            .find_map(|child| find_handler(child, element_id))
+    }
+}
+
+fn receive(client: &mut ChannelTransport) -> ServerMessage {
+    let bytes = client
+        .try_recv()
+        .expect("must be connected")
+        .expect("must have a message");
+    wire::from_binary(&bytes).expect("must decode")
+}
+
+fn send(client: &mut ChannelTransport, handler: HandlerId, event: RemoteEvent) {
+    let message = ClientMessage { handler, event };
+    client
+        .send(wire::to_binary(&message).expect("must encode"))
+        .expect("must send");
+}
+
+fn set_text(path: &[usize], txt: &str) -> WirePatch {
+    WirePatch {
+        tag: None,
+        patch_path: TreePath::new(path.iter().copied()),
+        patch_type: WirePatchType::SetText {
+            text: txt.to_string(),
+        },
+    }
+}
+
+#[test]
+fn mount_then_patches_from_init() {
+    let (server_end, mut client) = ChannelTransport::pair();
+    let mut program = ServerProgram::new(Greeter::default(), server_end);
+    program.mount().expect("must mount");
+
+    let ServerMessage::Mount(view) = receive(&mut client) else {
+        panic!("expecting the view to be mounted first");
+    };
+    assert!(find_handler(&view, "inc").is_some());
+    assert_eq!(
+        receive(&mut client),
+        ServerMessage::Patches(vec![set_text(&[0, 0], "ready")])
+    );
+    assert!(client.try_recv().unwrap().is_none());
+}
+
+#[test]
+fn client_events_are_dispatched_in_the_server() {
+    let (server_end, mut client) = ChannelTransport::pair();
+    let mut program = ServerProgram::new(Greeter::default(), server_end);
+    program.mount().expect("must mount");
+    let ServerMessage::Mount(view) = receive(&mut client) else {
+        panic!("expecting the view to be mounted first");
+    };
+    let _init_patches = receive(&mut client);
+
+    let inc = find_handler(&view, "inc").unwrap();
+    let name = find_handler(&view, "name").unwrap();
+    send(&mut client, inc, RemoteEvent::new("click"));
+    send(
+        &mut client,
+        name,
+        RemoteEvent::new("input").with_value("world"),
+    );
+    assert_eq!(program.process_incoming().expect("must process"), 2);
+
+    assert_eq!(program.app().count, 1);
+    assert_eq!(program.app().name, "world");
+    assert_eq!(
+        receive(&mut client),
+        ServerMessage::Patches(vec![set_text(&[1, 0], "1")])
+    );
+    assert_eq!(
+        receive(&mut client),
+        ServerMessage::Patches(vec![set_text(&[3, 0], "hello world")])
+    );
+}
+
+#[test]
+fn unknown_handler_is_an_error() {
+    let (server_end, mut client) = ChannelTransport::pair();
+    let mut program = ServerProgram::new(Greeter::default(), server_end);
+    program.mount().expect("must mount");
+    send(&mut client, HandlerId(99), RemoteEvent::new("click"));
+    assert!(program.process_incoming().is_err());
+}
+
+#[derive(Default)]
+struct Notices {
+    dismissed: bool,
+    round: usize,
+}
+
+impl Application for Notices {
+    type MSG = &'static str;
+
+    fn update(&mut self, msg: &'static str) -> Cmd<&'static str> {
+        match msg {
+            "dismiss" => self.dismissed = true,
+            _ => self.round += 1,
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<&'static str> {
+        let notice = (!self.dismissed)
+            .then(|| button([id("dismiss"), on("click", |_| "dismiss")], [text("x")]));
+        div(
+            [],
+            notice.into_iter().chain([
+                // the keyed node is replaced in every round, registering its listener again
+                button(
+                    [id("next"), key(self.round), on("click", |_| "next")],
+                    [text(self.round)],
+                ),
+            ]),
+        )
+    }
+}
+
+#[test]
+fn handlers_of_removed_nodes_are_dropped() {
+    let (server_end, mut client) = ChannelTransport::pair();
+    let mut program = ServerProgram::new(Notices::default(), server_end);
+    program.mount().expect("must mount");
+    let ServerMessage::Mount(view) = receive(&mut client) else {
+        panic!("expecting the view to be mounted first");
+    };
+    let dismiss = find_handler(&view, "dismiss").unwrap();
+
+    send(&mut client, dismiss, RemoteEvent::new("click"));
+    assert_eq!(program.process_incoming().expect("must process"), 1);
+    assert!(program.app().dismissed);
+    let _patches = receive(&mut client);
+
+    // the button is removed from the client, so its handler is no longer valid
+    assert!(program.handlers().get(dismiss).is_none());
+    send(&mut client, dismiss, RemoteEvent::new("click"));
+    assert!(program.process_incoming().is_err());
+}
+
+#[test]
+fn handlers_do_not_grow_with_updates() {
+    let (server_end, _client) = ChannelTransport::pair();
+    let mut program = ServerProgram::new(Notices::default(), server_end);
+    program.mount().expect("must mount");
+    assert_eq!(program.handlers().len(), 2);
+    for _ in 0..10 {
+        program.dispatch("next").expect("must dispatch");
+    }
+    assert_eq!(program.app().round, 10);
+    assert_eq!(program.handlers().len(), 2);
+}
+
+/// the listener is made once, so every view has the same listener
+struct Rounds {
+    round: usize,
+    next: Attribute<&'static str>,
+}
+
+impl Application for Rounds {
+    type MSG = &'static str;
+
+    fn update(&mut self, _msg: &'static str) -> Cmd<&'static str> {
+        self.round += 1;
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<&'static str> {
+        div(
+            [],
+            [button(
+                [id("next"), key(self.round), self.next.clone()],
+                [text(self.round)],
+            )],
+        )
+    }
+}
+
+#[test]
+fn the_same_listener_keeps_its_handler_id() {
+    let (server_end, mut client) = ChannelTransport::pair();
+    let rounds = Rounds {
+        round: 0,
+        next: on("click", |_| "next"),
+    };
+    let mut program = ServerProgram::new(rounds, server_end);
+    program.mount().expect("must mount");
+    let ServerMessage::Mount(view) = receive(&mut client) else {
+        panic!("expecting the view to be mounted first");
+    };
+    let next = find_handler(&view, "next").unwrap();
+
+    for round in 1..=3 {
+        send(&mut client, next, RemoteEvent::new("click"));
+        assert_eq!(program.process_incoming().expect("must process"), 1);
+        assert_eq!(program.app().round, round);
+        let ServerMessage::Patches(patches) = receive(&mut client) else {
+            panic!("expecting patches");
+        };
+        // the keyed button is replaced, but its listener keeps the handler id
+        let sent: Vec<HandlerId> = patches
+            .iter()
+            .flat_map(|patch| match &patch.patch_type {
+                WirePatchType::InsertBeforeNode { nodes }
+                | WirePatchType::InsertAfterNode { nodes } => nodes.clone(),
+                WirePatchType::AppendChildren { children } => children.clone(),
+                WirePatchType::ReplaceNode { replacement } => replacement.clone(),
+                _ => vec![],
+            })
+            .filter_map(|node| find_handler(&node, "next"))
+            .collect();
+        assert_eq!(sent, [next]);
+        assert_eq!(program.handlers().len(), 1);
+    }
+}
+
+struct Waiting {
+    rx: Option<oneshot::Receiver<String>>,
+    status: String,
+}
+
+impl Application for Waiting {
+    type MSG = String;
+
+    fn init(&mut self) -> Cmd<String> {
+        let rx = self.rx.take().expect("must have a receiver");
+        Cmd::once(async move { rx.await.unwrap_or_default() })
+    }
+
+    fn update(&mut self, status: String) -> Cmd<String> {
+        self.status = status;
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<String> {
+        p([], [text(&self.status)])
+    }
+}
+
+#[test]
+fn pending_commands_do_not_block_the_server() {
+    let (tx, rx) = oneshot::channel();
+    let (server_end, mut client) = ChannelTransport::pair();
+    let mut program = ServerProgram::new(
+        Waiting {
+            rx: Some(rx),
+            status: "waiting".to_string(),
+        },
+        server_end,
+    );
+    // the command of init is still waiting, the server carries on
+    program.mount().expect("must mount");
+    let _mount = receive(&mut client);
+    assert_eq!(program.process_incoming().expect("must process"), 0);
+    assert_eq!(program.app().status, "waiting");
+    assert!(client.try_recv().unwrap().is_none());
+
+    // the completed command is updated on the next process
+    tx.send("done".to_string()).unwrap();
+    assert_eq!(program.process_incoming().expect("must process"), 0);
+    assert_eq!(program.app().status, "done");
+    assert_eq!(
+        receive(&mut client),
+        ServerMessage::Patches(vec![set_text(&[0], "done")])
+    );
+}
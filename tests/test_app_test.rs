@@ -0,0 +1,238 @@
+#![deny(warnings)]
+use futures::channel::oneshot;
+use sauron::{
+    dom::{events::RemoteEvent, TestApp, TestAppError},
+    html::{
+        attributes::id,
+        events::{on, on_click, on_input, InputEvent},
+        *,
+    },
+    vdom::{SelectorError, TreePath},
+    Application, Cmd, Node,
+};
+
+enum Msg {
+    Loaded(Vec<&'static str>),
+    Increment,
+    Double,
+    SetName(String),
+}
+
+#[derive(Default)]
+struct Todos {
+    items: Vec<&'static str>,
+    count: i32,
+    name: String,
+}
+
+impl Application for Todos {
+    type MSG = Msg;
+
+    fn init(&mut self) -> Cmd<Msg> {
+        Cmd::once(async { Msg::Loaded(vec!["one", "two"]) })
+    }
+
+    fn update(&mut self, msg: Msg) -> Cmd<Msg> {
+        match msg {
+            Msg::Loaded(items) => self.items = items,
+            Msg::Increment => self.count += 1,
+            Msg::Double => {
+                return Cmd::batch([
+                    Cmd::once(async { Msg::Increment }),
+                    Cmd::once(async { Msg::Increment }),
+                ])
+            }
+            Msg::SetName(name) => self.name = name,
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div(
+            [],
+            [
+                ul([], self.items.iter().map(|item| li([], [text(item)]))),
+                button(
+                    [id("inc"), on("click", |_| Msg::Increment)],
+                    [text(self.count)],
+                ),
+                button([id("double"), on("click", |_| Msg::Double)], []),
+                input(
+                    [
+                        id("name"),
+                        on("input", |event| {
+                            let remote = event.as_remote().expect("must be a remote event");
+                            Msg::SetName(remote.value.clone().unwrap_or_default())
+                        }),
+                    ],
+                    [],
+                ),
+                span([], [text(format!("hello {}", self.name))]),
+            ],
+        )
+    }
+}
+
+#[test]
+fn init_commands_are_run() {
+    let test_app = TestApp::new(Todos::default());
+    assert_eq!(test_app.app().items, ["one", "two"]);
+    assert_eq!(test_app.view().query_selector_all("li").unwrap().len(), 2);
+}
+
+#[test]
+fn click_and_input() {
+    let mut test_app = TestApp::new(Todos::default());
+    test_app.click("#inc").unwrap();
+    test_app.click("button#inc").unwrap();
+    test_app.input("#name", "sauron").unwrap();
+    assert_eq!(test_app.app().count, 2);
+    let html = test_app.render();
+    assert!(html.contains(r#">2</button>"#), "{html}");
+    assert!(html.contains("<span>hello sauron</span>"), "{html}");
+}
+
+#[test]
+fn commands_from_update_are_run() {
+    let mut test_app = TestApp::new(Todos::default());
+    test_app.click("#double").unwrap();
+    assert_eq!(test_app.app().count, 2);
+    test_app.dispatch(Msg::Double);
+    assert_eq!(test_app.app().count, 4);
+}
+
+#[test]
+fn trigger_at_path() {
+    let mut test_app = TestApp::new(Todos::default());
+    test_app
+        .trigger_at(&TreePath::new([1]), RemoteEvent::new("click"))
+        .unwrap();
+    assert_eq!(test_app.app().count, 1);
+}
+
+#[test]
+fn trigger_errors() {
+    let mut test_app = TestApp::new(Todos::default());
+    assert_eq!(
+        test_app.click("#missing"),
+        Err(TestAppError::NotFound("#missing".to_string()))
+    );
+    assert_eq!(
+        test_app.click("li"),
+        Err(TestAppError::NoListener {
+            event: "click".to_string(),
+            path: TreePath::new([0, 0]),
+        })
+    );
+    assert_eq!(
+        test_app.trigger_at(&TreePath::new([9]), RemoteEvent::new("click")),
+        Err(TestAppError::NoElement(TreePath::new([9])))
+    );
+    assert_eq!(
+        test_app.click(""),
+        Err(TestAppError::Selector(SelectorError::Empty))
+    );
+}
+
+struct Typed {
+    value: String,
+}
+
+impl Application for Typed {
+    type MSG = String;
+
+    fn update(&mut self, value: String) -> Cmd<String> {
+        self.value = value;
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<String> {
+        div(
+            [],
+            [
+                input(
+                    [id("typed"), on_input(|event: InputEvent| event.value())],
+                    [],
+                ),
+                button([id("clicked"), on_click(|_| "clicked".to_string())], []),
+                p([], [text(&self.value)]),
+            ],
+        )
+    }
+}
+
+#[test]
+fn typed_listeners() {
+    let mut test_app = TestApp::new(Typed {
+        value: String::new(),
+    });
+    test_app.input("#typed", "sauron").unwrap();
+    assert_eq!(test_app.app().value, "sauron");
+
+    // on_click needs a browser MouseEvent
+    assert_eq!(
+        test_app.click("#clicked"),
+        Err(TestAppError::UnsupportedListener {
+            event: "click".to_string(),
+            path: TreePath::new([1]),
+        })
+    );
+    assert_eq!(test_app.app().value, "sauron");
+}
+
+struct Panicking;
+
+impl Application for Panicking {
+    type MSG = ();
+
+    fn update(&mut self, _msg: ()) -> Cmd<()> {
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        button([id("panic"), on("click", |_| panic!("the handler failed"))], [])
+    }
+}
+
+#[test]
+#[should_panic(expected = "the handler failed")]
+fn panics_in_listeners_are_not_hidden() {
+    let mut test_app = TestApp::new(Panicking);
+    let _ = test_app.click("#panic");
+}
+
+struct Waiting {
+    rx: Option<oneshot::Receiver<String>>,
+    status: String,
+}
+
+impl Application for Waiting {
+    type MSG = String;
+
+    fn init(&mut self) -> Cmd<String> {
+        let rx = self.rx.take().expect("must have a receiver");
+        Cmd::once(async move { rx.await.unwrap_or_default() })
+    }
+
+    fn update(&mut self, status: String) -> Cmd<String> {
+        self.status = status;
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<String> {
+        p([], [text(&self.status)])
+    }
+}
+
+#[test]
+fn pending_commands_are_resumed_when_settled() {
+    let (tx, rx) = oneshot::channel();
+    let mut test_app = TestApp::new(Waiting {
+        rx: Some(rx),
+        status: "waiting".to_string(),
+    });
+    assert_eq!(test_app.render(), "<p>waiting</p>");
+    tx.send("done".to_string()).unwrap();
+    test_app.settle();
+    assert_eq!(test_app.render(), "<p>done</p>");
+}
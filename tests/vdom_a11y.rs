@@ -0,0 +1,165 @@
+#![deny(warnings)]
+use sauron::{
+    html::{attributes::*, events::*, *},
+    svg::{g, svg},
+    vdom::{
+        a11y::{a11y_ignore, A11yChecker, A11yViolation, Rule},
+        attr,
+    },
+    Node, TreePath,
+};
+
+fn rules_at(violations: Vec<A11yViolation>) -> Vec<(TreePath, Rule)> {
+    violations
+        .into_iter()
+        .map(|violation| (violation.path, violation.rule))
+        .collect()
+}
+
+#[test]
+fn accessible_view() {
+    let view: Node<()> = div(
+        [],
+        [
+            img([src("logo.png"), alt("logo")], []),
+            img([src("divider.png"), alt("")], []),
+            button([on_click(|_| ())], [text("save")]),
+            a([href("/home")], [img([src("home.png"), alt("home")], [])]),
+            label([r#for("name")], [text("name")]),
+            input([r#type("text"), id("name")], []),
+            label([], [text("age"), input([r#type("number")], [])]),
+            input([r#type("hidden"), value("1")], []),
+            textarea([attr("aria-label", "comment")], []),
+            div(
+                [
+                    attr("role", "button"),
+                    tabindex(0),
+                    on_click(|_| ()),
+                    on_keydown(|_| ()),
+                ],
+                [text("open")],
+            ),
+        ],
+    );
+    assert_eq!(view.check_a11y(), []);
+}
+
+#[test]
+fn images_without_alt() {
+    let view: Node<()> = div(
+        [],
+        [
+            img([src("logo.png")], []),
+            input(
+                [r#type("image"), src("go.png"), attr("aria-label", "go")],
+                [],
+            ),
+        ],
+    );
+    assert_eq!(
+        rules_at(view.check_a11y()),
+        [
+            (TreePath::new([0]), Rule::ImgAlt),
+            (TreePath::new([1]), Rule::ImgAlt)
+        ]
+    );
+}
+
+#[test]
+fn clickable_div() {
+    let view: Node<()> = div([], [div([on_click(|_| ())], [text("open")])]);
+    assert_eq!(
+        rules_at(view.check_a11y()),
+        [
+            (TreePath::new([0]), Rule::ClickRole),
+            (TreePath::new([0]), Rule::ClickFocus),
+            (TreePath::new([0]), Rule::ClickKeyboard),
+        ]
+    );
+}
+
+#[test]
+fn inputs_without_labels() {
+    let view: Node<()> = div(
+        [],
+        [
+            label([r#for("other")], [text("other")]),
+            input([r#type("text"), id("name")], []),
+            select([], [option([], [text("one")])]),
+            input([r#type("submit"), value("send")], []),
+        ],
+    );
+    assert_eq!(
+        rules_at(view.check_a11y()),
+        [
+            (TreePath::new([1]), Rule::InputLabel),
+            (TreePath::new([2]), Rule::InputLabel)
+        ]
+    );
+}
+
+#[test]
+fn buttons_and_links_without_text() {
+    let view: Node<()> = div(
+        [],
+        [
+            button([], [span([], [text("  ")])]),
+            a([href("/home")], [img([src("home.png"), alt("")], [])]),
+            a([], []),
+            button([attr("title", "close")], [text("×")]),
+        ],
+    );
+    assert_eq!(
+        rules_at(view.check_a11y()),
+        [
+            (TreePath::new([0]), Rule::ButtonName),
+            (TreePath::new([1]), Rule::LinkName)
+        ]
+    );
+}
+
+#[test]
+fn disabled_and_ignored_rules() {
+    let view: Node<()> = div(
+        [],
+        [
+            div([on_click(|_| ()), a11y_ignore([Rule::ClickKeyboard])], []),
+            img([a11y_ignore([])], []),
+            img([], []),
+        ],
+    );
+    let checker = A11yChecker::new().disable(Rule::ClickRole);
+    assert_eq!(
+        rules_at(checker.check(&view)),
+        [
+            (TreePath::new([0]), Rule::ClickFocus),
+            (TreePath::new([2]), Rule::ImgAlt)
+        ]
+    );
+    let checker = A11yChecker::only([Rule::ImgAlt]);
+    assert_eq!(
+        rules_at(checker.check(&view)),
+        [(TreePath::new([2]), Rule::ImgAlt)]
+    );
+}
+
+#[test]
+fn custom_and_svg_elements_are_not_checked() {
+    let view: Node<()> = div(
+        [],
+        [
+            html_element(None, "my-button", [on_click(|_| ())], [], false),
+            svg([], [g([on_click(|_| ())], [])]),
+        ],
+    );
+    assert_eq!(view.check_a11y(), []);
+}
+
+#[test]
+fn violation_message() {
+    let view: Node<()> = div([], [img([], [])]);
+    assert_eq!(
+        view.check_a11y()[0].to_string(),
+        "img-alt: <img> at path: [0], the image has no alt text"
+    );
+}
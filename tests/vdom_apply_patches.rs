@@ -0,0 +1,165 @@
+#![deny(warnings)]
+use sauron::{
+    html::{
+        attributes::{class, id, key, styles, value},
+        *,
+    },
+    vdom::{apply_patches, diff, ApplyPatchError, Patch, TreePath},
+    Node,
+};
+
+/// apply the diff of `old` and `new` into `old`, which then must be the same as `new`
+fn assert_patched(old: &Node<()>, new: &Node<()>) {
+    let patches = diff(old, new);
+    let mut patched = old.clone();
+    apply_patches(&mut patched, &patches).expect("must apply patches");
+    assert_eq!(&patched, new, "patches: {patches:#?}");
+}
+
+fn keyed_list(keys: &[usize]) -> Node<()> {
+    main(
+        [class("reordered")],
+        [ul(
+            [class("todo")],
+            keys.iter()
+                .map(|k| li([key(*k)], [text(format!("item{k}"))])),
+        )],
+    )
+}
+
+#[test]
+fn text_and_attributes() {
+    let old = div(
+        [class("a"), id("x")],
+        [text("hello"), input([value(1)], [])],
+    );
+    let new = div(
+        [class("b"), id("x")],
+        [text("world"), input([value(2)], [])],
+    );
+    assert_patched(&old, &new);
+}
+
+#[test]
+fn removed_attributes() {
+    let old = div([class("a"), id("x")], []);
+    let new = div([class("a")], []);
+    assert_patched(&old, &new);
+}
+
+#[test]
+fn style_properties() {
+    let old = div(
+        [styles([("display", "block"), ("position", "absolute")])],
+        [],
+    );
+    let new = div([styles([("display", "none")])], []);
+    assert_patched(&old, &new);
+    assert_patched(
+        &new,
+        &div([styles([("display", "none"), ("color", "red")])], []),
+    );
+}
+
+#[test]
+fn class_names() {
+    let old: Node<()> = div([class("a b"), id("x")], []);
+    let new = div([class("b c"), id("x")], []);
+    let patches = diff(&old, &new);
+    let mut patched = old.clone();
+    apply_patches(&mut patched, &patches).expect("must apply patches");
+    assert_eq!(patched.render_to_string(), new.render_to_string());
+    assert_patched(&div([class("a")], []), &div([class("a b")], []));
+}
+
+#[test]
+fn text_and_comment_content() {
+    let old: Node<()> = div([], [text("hello"), comment("old"), text("!")]);
+    let new: Node<()> = div([], [text("world"), comment("new"), text("!")]);
+    assert_eq!(
+        diff(&old, &new),
+        vec![
+            Patch::set_text(TreePath::new([0]), "world"),
+            Patch::set_text(TreePath::new([1]), "new"),
+        ]
+    );
+    assert_patched(&old, &new);
+}
+
+#[test]
+fn set_text_of_an_element() {
+    let mut node: Node<()> = div([], [span([], [])]);
+    let patches = vec![Patch::set_text(TreePath::new([0]), "text")];
+    assert_eq!(
+        apply_patches(&mut node, &patches),
+        Err(ApplyPatchError::NotText(TreePath::new([0])))
+    );
+}
+
+#[test]
+fn appended_removed_and_cleared_children() {
+    let old = ul([], [li([], [text(1)])]);
+    let more = ul(
+        [],
+        [li([], [text(1)]), li([], [text(2)]), li([], [text(3)])],
+    );
+    assert_patched(&old, &more);
+    assert_patched(&more, &old);
+    assert_patched(&more, &ul([], []));
+}
+
+#[test]
+fn replaced_with_different_tag() {
+    assert_patched(&div([], [span([], [])]), &div([], [p([], [])]));
+    assert_patched(&div([], []), &span([], [text("root")]));
+}
+
+#[test]
+fn keyed_reordering() {
+    let cases: &[(&[usize], &[usize])] = &[
+        (&[1, 2, 3], &[3, 2, 1]),
+        (&[1, 2, 3], &[2, 3]),
+        (&[1, 2, 3], &[1, 4, 2, 3]),
+        (&[1, 2, 3, 4, 5], &[5, 1, 2, 3, 4]),
+        (&[1, 2, 3], &[]),
+        (&[], &[1, 2]),
+        (&[1, 2, 3, 4, 5, 6], &[6, 5, 4, 3, 2, 1]),
+        (&[1, 2, 3, 4, 5], &[2, 1, 4, 3, 6, 5]),
+    ];
+    for (old, new) in cases {
+        assert_patched(&keyed_list(old), &keyed_list(new));
+    }
+}
+
+#[test]
+fn fragment_root() {
+    let old: Node<()> = fragment((1..4).map(|k| div([key(k)], [text(k)])));
+    let new: Node<()> = fragment((0..4).map(|k| div([key(k)], [text(k)])));
+    assert_patched(&old, &new);
+}
+
+#[test]
+fn patch_to_a_missing_node() {
+    let mut node: Node<()> = div([], []);
+    let patches = vec![Patch::remove_node(None, TreePath::new([3]))];
+    assert_eq!(
+        apply_patches(&mut node, &patches),
+        Err(ApplyPatchError::NodeNotFound(TreePath::new([3])))
+    );
+}
+
+#[test]
+fn patch_with_a_different_tag() {
+    let mut node: Node<()> = div([], [span([], [])]);
+    let patches = vec![Patch::remove_node(Some(&"p"), TreePath::new([0]))];
+    assert_eq!(
+        node.apply_patches(&patches),
+        Err(ApplyPatchError::TagMismatch {
+            path: TreePath::new([0]),
+            expected: "p".to_string(),
+            found: Some("span".to_string()),
+        })
+    );
+    // the node is unchanged when there is an error
+    assert_eq!(node, div([], [span([], [])]));
+}
@@ -0,0 +1,97 @@
+#![deny(warnings)]
+use sauron::{
+    assert_node_eq,
+    html::{
+        attributes::{class, id, key, styles, title},
+        events::on_click,
+        *,
+    },
+    vdom::{compare_nodes, AttributeMismatch, CompareOptions},
+    Node, TreePath,
+};
+
+#[test]
+fn equal_nodes_with_reordered_attributes() {
+    let left: Node<()> = div([id("x"), class("a")], [text("hello")]);
+    let right: Node<()> = div([class("a"), id("x")], [text("hello")]);
+    assert_node_eq!(left, right);
+}
+
+#[test]
+fn first_differing_path() {
+    let left: Node<()> = ul([], [li([], [text("one")]), li([], [text("two")])]);
+    let right: Node<()> = ul([], [li([], [text("one")]), li([], [text("deux")])]);
+    let mismatch = compare_nodes(&left, &right, CompareOptions::new()).unwrap_err();
+    assert_eq!(mismatch.path, TreePath::new([1, 0]));
+    assert_eq!(mismatch.reason, "different nodes");
+    assert_eq!(mismatch.left, "two");
+    assert_eq!(mismatch.right, "deux");
+}
+
+#[test]
+fn different_attributes_by_name() {
+    let left: Node<()> = div([id("x"), class("a"), styles([("color", "red")])], []);
+    let right: Node<()> = div([class("b"), id("x"), title("t")], []);
+    let mismatch = compare_nodes(&left, &right, CompareOptions::new()).unwrap_err();
+    assert_eq!(mismatch.path, TreePath::root());
+    assert_eq!(
+        mismatch.attributes,
+        vec![
+            AttributeMismatch {
+                name: "class",
+                left: Some(r#""a""#.to_string()),
+                right: Some(r#""b""#.to_string()),
+            },
+            AttributeMismatch {
+                name: "style",
+                left: Some(r#""color:red;""#.to_string()),
+                right: None,
+            },
+            AttributeMismatch {
+                name: "title",
+                left: None,
+                right: Some(r#""t""#.to_string()),
+            },
+        ]
+    );
+}
+
+#[test]
+fn different_number_of_children() {
+    let left: Node<()> = ul([], [li([], [])]);
+    let right: Node<()> = ul([], [li([], []), li([], [])]);
+    let mismatch = compare_nodes(&left, &right, CompareOptions::new()).unwrap_err();
+    assert_eq!(mismatch.path, TreePath::root());
+    assert_eq!(mismatch.reason, "different number of children: 1 != 2");
+}
+
+#[test]
+fn ignore_listeners_and_special_attributes() {
+    let left: Node<()> = ul([], [li([key(1), on_click(|_| ())], [text("one")])]);
+    let right: Node<()> = ul([], [li([], [text("one")])]);
+    let mismatch = compare_nodes(&left, &right, CompareOptions::new()).unwrap_err();
+    assert_eq!(
+        mismatch
+            .attributes
+            .iter()
+            .map(|attr| attr.name)
+            .collect::<Vec<_>>(),
+        ["key", "click"]
+    );
+    assert!(compare_nodes(&left, &right, CompareOptions::new().ignore_listeners()).is_err());
+    assert_node_eq!(
+        left,
+        right,
+        CompareOptions::new()
+            .ignore_listeners()
+            .ignore_special_attributes()
+    );
+}
+
+#[test]
+#[should_panic(expected = "nodes differ at path: [0], different tags: \"span\" != \"p\"")]
+fn assert_node_eq_panics_with_the_path() {
+    let left: Node<()> = div([], [span([], [])]);
+    let right: Node<()> = div([], [p([], [])]);
+    assert_node_eq!(left, right);
+}
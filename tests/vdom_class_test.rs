@@ -20,11 +20,10 @@ fn class_changed() {
 
     assert_eq!(
         diff,
-        vec![Patch::add_attributes(
-            &"main",
-            TreePath::new(vec![]),
-            vec![&attr("class", "class2")]
-        )]
+        vec![
+            Patch::add_classes(&"main", TreePath::new(vec![]), ["class2"]),
+            Patch::remove_classes(&"main", TreePath::new(vec![]), ["class1"]),
+        ]
     );
 }
 
@@ -54,11 +53,10 @@ fn parent_of_matching_keyed_are_ignored() {
 
     assert_eq!(
         patches,
-        vec![Patch::add_attributes(
-            &"ul",
-            TreePath::new(vec![]),
-            vec![&attr("class", "changed")]
-        )],
-        "Should add the new attributes"
+        vec![
+            Patch::add_classes(&"ul", TreePath::new(vec![]), ["changed"]),
+            Patch::remove_classes(&"ul", TreePath::new(vec![]), ["original"]),
+        ],
+        "Should add the new class and remove the old one"
     );
 }
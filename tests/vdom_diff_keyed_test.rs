@@ -147,7 +147,7 @@ fn there_are_2_exact_same_keys_in_the_old() {
     assert_eq!(
         diff,
         vec![
-            Patch::replace_node(None, TreePath::new(vec![0, 0]), vec![&leaf("1")]),
+            Patch::set_text(TreePath::new(vec![0, 0]), "1"),
             Patch::remove_node(Some(&"div"), TreePath::new(vec![1]))
         ]
     );
@@ -182,7 +182,7 @@ fn there_are_2_exact_same_keys_in_the_new() {
     assert_eq!(
         diff,
         vec![
-            Patch::replace_node(None, TreePath::new([0, 0]), vec![&leaf("1")]),
+            Patch::set_text(TreePath::new([0, 0]), "1"),
             Patch::insert_after_node(
                 Some(&"div"),
                 TreePath::new([0]),
@@ -222,8 +222,8 @@ fn there_are_2_exact_same_keys_in_both_old_and_new() {
     assert_eq!(
         diff,
         vec![
-            Patch::replace_node(None, TreePath::new(vec![0, 0]), vec![&leaf("1")]),
-            Patch::replace_node(None, TreePath::new(vec![1, 0]), vec![&leaf("3")]),
+            Patch::set_text(TreePath::new(vec![0, 0]), "1"),
+            Patch::set_text(TreePath::new(vec![1, 0]), "3"),
             Patch::insert_before_node(
                 Some(&"div"),
                 TreePath::new(vec![1]),
@@ -580,16 +580,8 @@ fn deep_nested_keyed_with_non_keyed_children() {
                 TreePath::new(vec![0, 2]),
                 vec![&attr("class", "some-class")]
             ),
-            Patch::replace_node(
-                None,
-                TreePath::new(vec![0, 2, 0, 0]),
-                vec![&leaf("paragraph1, with added content")]
-            ),
-            Patch::replace_node(
-                None,
-                TreePath::new(vec![0, 2, 1, 0]),
-                vec![&leaf("Click here to continue")]
-            ),
+            Patch::set_text(TreePath::new(vec![0, 2, 0, 0]), "paragraph1, with added content"),
+            Patch::set_text(TreePath::new(vec![0, 2, 1, 0]), "Click here to continue"),
             Patch::remove_node(Some(&"div"), TreePath::new(vec![0, 0]),),
             Patch::remove_node(Some(&"div"), TreePath::new(vec![0, 1]),),
         ]
@@ -636,11 +628,7 @@ fn text_changed_in_keyed_elements() {
     assert_eq!(
         patch,
         vec![
-            Patch::replace_node(
-                None,
-                TreePath::new(vec![0, 2, 0]),
-                vec![&leaf("item3 with changes")]
-            ),
+            Patch::set_text(TreePath::new(vec![0, 2, 0]), "item3 with changes"),
             Patch::remove_node(Some(&"article"), TreePath::new(vec![0, 0])),
         ]
     );
@@ -691,13 +679,9 @@ fn text_changed_in_mixed_keyed_and_non_keyed_elements() {
     assert_eq!(
         patch,
         vec![
-            Patch::replace_node(
-                None,
-                TreePath::new(vec![0, 2, 0]),
-                vec![&leaf("item3 with changes")]
-            ),
+            Patch::set_text(TreePath::new(vec![0, 2, 0]), "item3 with changes"),
             Patch::remove_node(Some(&"article"), TreePath::new(vec![0, 0]),),
-            Patch::replace_node(None, TreePath::new(vec![1, 0]), vec![&leaf("2 items left")]),
+            Patch::set_text(TreePath::new(vec![1, 0]), "2 items left"),
         ]
     );
 }
@@ -750,13 +734,9 @@ fn test12() {
     assert_eq!(
         patch,
         vec![
-            Patch::replace_node(
-                None,
-                TreePath::new(vec![1, 2, 0]),
-                vec![&leaf("item3 with changes")]
-            ),
+            Patch::set_text(TreePath::new(vec![1, 2, 0]), "item3 with changes"),
             Patch::remove_node(Some(&"article"), TreePath::new(vec![1, 0]),),
-            Patch::replace_node(None, TreePath::new(vec![2, 0]), vec![&leaf("2 items left")]),
+            Patch::set_text(TreePath::new(vec![2, 0]), "2 items left"),
         ]
     );
 }
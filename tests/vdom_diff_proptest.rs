@@ -0,0 +1,321 @@
+#![deny(warnings)]
+//! Property tests of the diff, where applying `diff(old, new)` to `old` must yield `new`.
+//!
+//! The trees are generated as a small model which is converted into `Node`,
+//! so the shrunk failing cases are easy to read.
+use proptest::prelude::*;
+use sauron::vdom::{apply_patches, attr, diff, element, fragment, key, node_list, Attribute, Leaf};
+use sauron::{html::text, Node};
+
+const TAGS: &[&str] = &["div", "span", "ul", "li"];
+
+#[derive(Debug, Clone)]
+enum Tree {
+    Text(String),
+    Element {
+        tag: &'static str,
+        class: Option<u8>,
+        key: Option<u8>,
+        children: Vec<Tree>,
+    },
+    /// unrolled into the children of the parent element
+    NodeList(Vec<Tree>),
+}
+
+/// the root, fragments are only supported as the root node
+#[derive(Debug, Clone)]
+enum Root {
+    Tree(Tree),
+    Fragment(Vec<Tree>),
+}
+
+fn tree() -> impl Strategy<Value = Tree> {
+    let leaf = prop_oneof![
+        "[ab]{0,2}".prop_map(Tree::Text),
+        (
+            prop::sample::select(TAGS),
+            any::<Option<u8>>(),
+            any::<Option<u8>>()
+        )
+            .prop_map(|(tag, class, key)| Tree::Element {
+                tag,
+                class: class.map(|c| c % 3),
+                key: key.map(|k| k % 8),
+                children: vec![],
+            }),
+    ];
+    leaf.prop_recursive(3, 32, 6, |inner| {
+        prop_oneof![
+            4 => (
+                prop::sample::select(TAGS),
+                any::<Option<u8>>(),
+                any::<Option<u8>>(),
+                prop::collection::vec(inner.clone(), 0..6)
+            )
+                .prop_map(|(tag, class, key, children)| Tree::Element {
+                    tag,
+                    class: class.map(|c| c % 3),
+                    key: key.map(|k| k % 8),
+                    children,
+                }),
+            1 => prop::collection::vec(inner, 0..3).prop_map(Tree::NodeList),
+        ]
+    })
+}
+
+fn root() -> impl Strategy<Value = Root> {
+    prop_oneof![
+        3 => tree()
+            .prop_filter("node lists are unrolled into the parent", |tree| {
+                !matches!(tree, Tree::NodeList(_))
+            })
+            .prop_map(Root::Tree),
+        1 => prop::collection::vec(tree(), 1..6).prop_map(Root::Fragment),
+    ]
+}
+
+/// A change to a tree, the node is selected by its index in the depth first traversal
+#[derive(Debug, Clone)]
+enum Mutation {
+    SetText(usize, String),
+    SetClass(usize, Option<u8>),
+    SetKey(usize, Option<u8>),
+    Retag(usize, &'static str),
+    InsertChild(usize, usize, Tree),
+    RemoveChild(usize, usize),
+    MoveChild(usize, usize, usize),
+}
+
+fn mutation() -> impl Strategy<Value = Mutation> {
+    prop_oneof![
+        (any::<usize>(), "[abc]{0,2}").prop_map(|(n, s)| Mutation::SetText(n, s)),
+        (any::<usize>(), any::<Option<u8>>())
+            .prop_map(|(n, c)| Mutation::SetClass(n, c.map(|c| c % 3))),
+        (any::<usize>(), any::<Option<u8>>())
+            .prop_map(|(n, k)| Mutation::SetKey(n, k.map(|k| k % 8))),
+        (any::<usize>(), prop::sample::select(TAGS)).prop_map(|(n, t)| Mutation::Retag(n, t)),
+        (any::<usize>(), any::<usize>(), tree())
+            .prop_map(|(n, i, t)| Mutation::InsertChild(n, i, t)),
+        (any::<usize>(), any::<usize>()).prop_map(|(n, i)| Mutation::RemoveChild(n, i)),
+        (any::<usize>(), any::<usize>(), any::<usize>())
+            .prop_map(|(n, from, to)| Mutation::MoveChild(n, from, to)),
+    ]
+}
+
+impl Tree {
+    fn count(&self) -> usize {
+        match self {
+            Tree::Text(_) => 1,
+            Tree::Element { children, .. } => 1 + children.iter().map(Tree::count).sum::<usize>(),
+            Tree::NodeList(nodes) => 1 + nodes.iter().map(Tree::count).sum::<usize>(),
+        }
+    }
+
+    /// return the nth node in the depth first traversal
+    fn nth(&mut self, n: usize) -> Option<&mut Tree> {
+        let mut remaining = n;
+        self.nth_inner(&mut remaining)
+    }
+
+    fn nth_inner(&mut self, remaining: &mut usize) -> Option<&mut Tree> {
+        if *remaining == 0 {
+            return Some(self);
+        }
+        *remaining -= 1;
+        match self {
+            Tree::Text(_) => None,
+            Tree::Element { children, .. } | Tree::NodeList(children) => children
+                .iter_mut()
+                .find_map(|child| child.nth_inner(remaining)),
+        }
+    }
+
+    fn mutate(&mut self, mutation: &Mutation) {
+        let count = self.count();
+        let select = |n: &usize| n % count;
+        match mutation {
+            Mutation::SetText(n, s) => {
+                if let Some(Tree::Text(txt)) = self.nth(select(n)) {
+                    *txt = s.clone();
+                }
+            }
+            Mutation::SetClass(n, c) => {
+                if let Some(Tree::Element { class, .. }) = self.nth(select(n)) {
+                    *class = *c;
+                }
+            }
+            Mutation::SetKey(n, k) => {
+                if let Some(Tree::Element { key, .. }) = self.nth(select(n)) {
+                    *key = *k;
+                }
+            }
+            Mutation::Retag(n, t) => {
+                if let Some(Tree::Element { tag, .. }) = self.nth(select(n)) {
+                    *tag = t;
+                }
+            }
+            Mutation::InsertChild(n, i, t) => {
+                if let Some(Tree::Element { children, .. } | Tree::NodeList(children)) =
+                    self.nth(select(n))
+                {
+                    let i = i % (children.len() + 1);
+                    children.insert(i, t.clone());
+                }
+            }
+            Mutation::RemoveChild(n, i) => {
+                if let Some(Tree::Element { children, .. } | Tree::NodeList(children)) =
+                    self.nth(select(n))
+                {
+                    if !children.is_empty() {
+                        let i = i % children.len();
+                        children.remove(i);
+                    }
+                }
+            }
+            Mutation::MoveChild(n, from, to) => {
+                if let Some(Tree::Element { children, .. } | Tree::NodeList(children)) =
+                    self.nth(select(n))
+                {
+                    if !children.is_empty() {
+                        let moved = children.remove(from % children.len());
+                        children.insert(to % (children.len() + 1), moved);
+                    }
+                }
+            }
+        }
+    }
+
+    fn to_node(&self) -> Node<()> {
+        match self {
+            Tree::Text(txt) => text(txt),
+            Tree::Element {
+                tag,
+                class,
+                key: k,
+                children,
+            } => {
+                let attrs = class
+                    .map(|c| attr("class", format!("c{c}")))
+                    .into_iter()
+                    .chain(k.map(key::<_, ()>));
+                element(tag, attrs, to_nodes(children))
+            }
+            Tree::NodeList(nodes) => node_list(to_nodes(nodes)),
+        }
+    }
+}
+
+/// convert the trees into nodes, the duplicate keys of the siblings are dropped
+fn to_nodes(trees: &[Tree]) -> Vec<Node<()>> {
+    let mut keys = vec![];
+    trees
+        .iter()
+        .map(|tree| {
+            let mut tree = tree.clone();
+            if let Tree::Element { key, .. } = &mut tree {
+                if let Some(k) = *key {
+                    if keys.contains(&k) {
+                        *key = None;
+                    } else {
+                        keys.push(k);
+                    }
+                }
+            }
+            tree.to_node()
+        })
+        .collect()
+}
+
+impl Root {
+    fn mutate(&mut self, mutation: &Mutation) {
+        match self {
+            Root::Tree(tree) => tree.mutate(mutation),
+            Root::Fragment(trees) => {
+                // the fragment is mutated as the children of a wrapper element
+                let mut wrapper = Tree::Element {
+                    tag: "div",
+                    class: None,
+                    key: None,
+                    children: std::mem::take(trees),
+                };
+                wrapper.mutate(mutation);
+                match wrapper {
+                    Tree::Element { children, .. } if !children.is_empty() => *trees = children,
+                    Tree::Element { .. } => *trees = vec![Tree::Text(String::new())],
+                    _ => unreachable!(),
+                }
+            }
+        }
+    }
+
+    fn to_node(&self) -> Node<()> {
+        match self {
+            Root::Tree(tree) => tree.to_node(),
+            Root::Fragment(trees) => fragment(to_nodes(trees)),
+        }
+    }
+}
+
+/// the number of nodes including the fragment children
+fn count_nodes(node: &Node<()>) -> usize {
+    1 + match node {
+        Node::Element(elm) => elm.children().iter().map(count_nodes).sum(),
+        Node::Leaf(Leaf::Fragment(nodes) | Leaf::NodeList(nodes)) => {
+            nodes.iter().map(count_nodes).sum()
+        }
+        Node::Leaf(_) => 0,
+    }
+}
+
+/// the attributes are sorted by name, since the order of the attributes doesn't matter in the DOM
+fn normalize(node: &Node<()>) -> Node<()> {
+    match node {
+        Node::Element(elm) => {
+            let mut attrs: Vec<Attribute<()>> = elm.attributes().to_vec();
+            attrs.sort_by_key(|a| a.name);
+            element(elm.tag(), attrs, elm.children().iter().map(normalize))
+        }
+        Node::Leaf(Leaf::Fragment(nodes)) => fragment(nodes.iter().map(normalize)),
+        Node::Leaf(_) => node.clone(),
+    }
+}
+
+fn old_and_new() -> impl Strategy<Value = (Root, Root)> {
+    (root(), prop::collection::vec(mutation(), 0..6)).prop_map(|(old, mutations)| {
+        let mut new = old.clone();
+        for mutation in mutations.iter() {
+            new.mutate(mutation);
+        }
+        (old, new)
+    })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(512))]
+
+    #[test]
+    fn applying_the_diff_yields_the_new_node((old, new) in old_and_new()) {
+        let old = old.to_node();
+        let new = new.to_node();
+        let patches = diff(&old, &new);
+        let mut patched = old.clone();
+        apply_patches(&mut patched, &patches)
+            .map_err(|e| TestCaseError::fail(format!("{e}, patches: {patches:#?}")))?;
+        prop_assert_eq!(normalize(&patched), normalize(&new), "patches: {:#?}", patches);
+    }
+
+    #[test]
+    fn patch_count_is_bounded((old, new) in old_and_new()) {
+        let old = old.to_node();
+        let new = new.to_node();
+        let patches = diff(&old, &new);
+        // an element can have both the added and the removed class names patched
+        prop_assert!(patches.len() <= 2 * (count_nodes(&old) + count_nodes(&new)));
+    }
+
+    #[test]
+    fn no_patches_for_the_same_node(old in root()) {
+        let old = old.to_node();
+        prop_assert!(diff(&old, &old.clone()).is_empty());
+    }
+}
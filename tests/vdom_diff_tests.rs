@@ -136,11 +136,10 @@ fn test_class_changed() {
     let diff = diff(&old, &new);
     assert_eq!(
         diff,
-        vec![Patch::add_attributes(
-            &"div",
-            TreePath::new(vec![]),
-            vec![&attr("class", "some-class2")]
-        )]
+        vec![
+            Patch::add_classes(&"div", TreePath::new(vec![]), ["some-class2"]),
+            Patch::remove_classes(&"div", TreePath::new(vec![]), ["some-class"]),
+        ]
     )
 }
 
@@ -162,11 +161,7 @@ fn leaf_node_changed() {
     dbg!(&diff);
     assert_eq!(
         diff,
-        vec![Patch::replace_node(
-            None,
-            TreePath::new(vec![0]),
-            vec![&leaf("text2")]
-        )]
+        vec![Patch::set_text(TreePath::new(vec![0]), "text2")]
     )
 }
 
@@ -0,0 +1,102 @@
+#![deny(warnings)]
+use sauron::{
+    html::{
+        attributes::{class, id, key, styles},
+        *,
+    },
+    vdom::{explain_patches, PatchExplanation},
+    *,
+};
+
+#[test]
+fn css_path_of_the_target() {
+    let old: Node<()> = div(
+        [class("app")],
+        [ul(
+            [id("todos")],
+            [
+                li([], [text("one")]),
+                li([], [text("two")]),
+                li([], [text("three")]),
+            ],
+        )],
+    );
+    let new: Node<()> = div(
+        [class("app")],
+        [ul(
+            [id("todos")],
+            [
+                li([], [text("one")]),
+                li([], [text("two")]),
+                li([class("done")], [text("3")]),
+            ],
+        )],
+    );
+    let report = explain_patches(&old, &diff(&old, &new));
+    assert_eq!(
+        report.patches,
+        [
+            PatchExplanation {
+                target: "div.app > ul#todos > li:nth-child(3)".to_string(),
+                description: "add attributes: class".to_string(),
+            },
+            PatchExplanation {
+                target: "div.app > ul#todos > li:nth-child(3) > #text".to_string(),
+                description: "set the text to \"3\"".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn counts_per_type_and_nodes() {
+    let old: Node<()> = ul(
+        [],
+        [
+            li([key(1)], [text("one")]),
+            li([key(2)], [text("two")]),
+            li([key(3)], [text("three")]),
+        ],
+    );
+    let new: Node<()> = ul(
+        [],
+        [
+            li([key(1)], [text("one")]),
+            li([key(3)], [text("three")]),
+            li([key(4)], [span([], [text("four")])]),
+        ],
+    );
+    let report = explain_patches(&old, &diff(&old, &new));
+    assert_eq!(report.total(), 2);
+    assert_eq!(report.per_type.get("RemoveNode"), Some(&1));
+    assert_eq!(report.per_type.get("InsertAfterNode"), Some(&1));
+    assert_eq!(report.nodes_removed, 2);
+    assert_eq!(report.nodes_created, 3);
+    assert_eq!(report.nodes_moved, 0);
+}
+
+#[test]
+fn attributes_touched_per_tag() {
+    let old: Node<()> = div(
+        [],
+        [
+            button([id("a"), styles([("color", "red")])], []),
+            button([id("b")], []),
+        ],
+    );
+    let new: Node<()> = div(
+        [],
+        [
+            button([id("x"), styles([("color", "blue")])], []),
+            button([id("y")], []),
+        ],
+    );
+    let report = explain_patches(&old, &diff(&old, &new));
+    assert_eq!(report.attributes_per_tag.get("button"), Some(&3));
+    let rendered = report.to_string();
+    assert!(rendered.starts_with("3 patches\n"), "{rendered}");
+    assert!(
+        rendered.contains("div > button#a:nth-child(1): set styles: color:blue"),
+        "{rendered}"
+    );
+}
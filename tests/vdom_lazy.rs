@@ -0,0 +1,165 @@
+#![deny(warnings)]
+use sauron::{
+    html::{attributes::class, *},
+    vdom::{apply_patches, Leaf},
+    *,
+};
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// a table of rows, where each row is a lazy node, counting the calls to the row view
+fn rows_table(rows: &[(usize, &'static str)], calls: &Rc<Cell<usize>>) -> Node<()> {
+    let rows = rows.iter().map(|row| {
+        let calls = Rc::clone(calls);
+        lazy(*row, move |(id, label)| {
+            calls.set(calls.get() + 1);
+            tr([class("row")], [td([], [text(id)]), td([], [text(label)])])
+        })
+    });
+    table_tag(rows)
+}
+
+/// like `rows_table`, but each lazy row is keyed with its id
+fn keyed_rows_table(rows: &[(usize, &'static str)], calls: &Rc<Cell<usize>>) -> Node<()> {
+    let rows = rows.iter().map(|row| {
+        let calls = Rc::clone(calls);
+        lazy_keyed(row.0, *row, move |(id, label)| {
+            calls.set(calls.get() + 1);
+            tr([class("row")], [td([], [text(id)]), td([], [text(label)])])
+        })
+    });
+    table_tag(rows)
+}
+
+fn table_tag(rows: impl IntoIterator<Item = Node<()>>) -> Node<()> {
+    element("table", [], [tbody([], rows)])
+}
+
+fn lazy_node(node: &Node<()>) -> &vdom::Lazy<()> {
+    match node {
+        Node::Leaf(Leaf::Lazy(lazy)) => lazy,
+        _ => panic!("expecting a lazy node"),
+    }
+}
+
+#[test]
+fn unchanged_args_are_not_built_nor_diffed() {
+    let calls = Rc::new(Cell::new(0));
+    let old = rows_table(&[(1, "one"), (2, "two")], &calls);
+    let rendered = old.render_to_string();
+    assert_eq!(calls.get(), 2);
+
+    let new = rows_table(&[(1, "one"), (2, "two")], &calls);
+    assert!(diff(&old, &new).is_empty());
+    assert_eq!(calls.get(), 2);
+    // the view of the previous render is reused
+    assert_eq!(new.render_to_string(), rendered);
+    assert_eq!(calls.get(), 2);
+}
+
+#[test]
+fn changed_args_diff_the_views() {
+    let calls = Rc::new(Cell::new(0));
+    let old = rows_table(&[(1, "one"), (2, "two")], &calls);
+    old.render_to_string();
+    let new = rows_table(&[(1, "one"), (2, "deux")], &calls);
+    assert_eq!(
+        diff(&old, &new),
+        vec![Patch::set_text(TreePath::new([0, 1, 1, 0]), "deux")]
+    );
+    // only the changed row is built, the first row reuses the previous view
+    assert_eq!(calls.get(), 3);
+    let rows = new.children()[0].children();
+    assert!(lazy_node(&rows[0]).is_built());
+}
+
+#[test]
+fn reused_view_is_diffed_in_the_next_render() {
+    let calls = Rc::new(Cell::new(0));
+    let first = rows_table(&[(1, "one")], &calls);
+    let second = rows_table(&[(1, "one")], &calls);
+    let third = rows_table(&[(1, "uno")], &calls);
+    assert!(diff(&first, &second).is_empty());
+    assert_eq!(
+        diff(&second, &third),
+        vec![Patch::set_text(TreePath::new([0, 0, 1, 0]), "uno")]
+    );
+    assert_eq!(calls.get(), 2);
+}
+
+#[test]
+fn lazy_and_plain_nodes() {
+    let calls = Rc::new(Cell::new(0));
+    let plain: Node<()> = table_tag([tr(
+        [class("row")],
+        [td([], [text(1)]), td([], [text("one")])],
+    )]);
+    let lazy = rows_table(&[(1, "one")], &calls);
+    assert!(diff(&plain, &lazy).is_empty());
+    assert!(diff(&lazy, &plain).is_empty());
+    assert_eq!(plain.render_to_string(), lazy.render_to_string());
+}
+
+#[test]
+fn apply_patches_to_lazy_nodes() {
+    let calls = Rc::new(Cell::new(0));
+    let old = rows_table(&[(1, "one"), (2, "two")], &calls);
+    let new = rows_table(&[(2, "two"), (3, "three")], &calls);
+    let mut patched = old.clone();
+    apply_patches(&mut patched, &diff(&old, &new)).expect("must apply");
+    assert_eq!(patched.render_to_string(), new.render_to_string());
+}
+
+#[test]
+fn lazy_with_mapped_msg() {
+    let view = |n: &usize| {
+        let n = *n;
+        button([on_click(move |_| n)], [text(n)])
+    };
+    let old = lazy(1, view).map_msg(|n| n * 2);
+    let new = lazy(1, view).map_msg(|n| n * 2);
+    assert_eq!(
+        old.render_to_string(),
+        view(&1).map_msg(|n| n * 2).render_to_string()
+    );
+    assert!(diff(&old, &new).is_empty());
+}
+
+#[test]
+fn keyed_insert_only_builds_the_new_row() {
+    let calls = Rc::new(Cell::new(0));
+    let old = keyed_rows_table(&[(2, "two"), (3, "three")], &calls);
+    old.render_to_string();
+    assert_eq!(calls.get(), 2);
+
+    let new = keyed_rows_table(&[(1, "one"), (2, "two"), (3, "three")], &calls);
+    let patches = diff(&old, &new);
+    // the rows 2 and 3 are matched by their keys and reuse their views
+    assert_eq!(calls.get(), 2);
+    assert_eq!(patches.len(), 1);
+    // only the inserted row is built
+    let rendered = new.render_to_string();
+    assert_eq!(calls.get(), 3);
+    let mut patched = old.clone();
+    apply_patches(&mut patched, &patches).expect("must apply");
+    assert_eq!(patched.render_to_string(), rendered);
+}
+
+#[test]
+fn unkeyed_insert_builds_the_rows_at_changed_index() {
+    let calls = Rc::new(Cell::new(0));
+    let old = rows_table(&[(2, "two"), (3, "three")], &calls);
+    old.render_to_string();
+    let new = rows_table(&[(1, "one"), (2, "two"), (3, "three")], &calls);
+    diff(&old, &new);
+    // the rows are diffed by index, so the first two rows have different args and are built again
+    assert_eq!(calls.get(), 4);
+}
+
+#[test]
+fn lazy_key_is_the_key_attribute() {
+    let keyed: Node<()> = lazy_keyed("row-1", 1, |n: &i32| li([], [text(n)]));
+    assert_eq!(keyed.first_value(&"key"), Some(&Value::from("row-1")));
+    let unkeyed: Node<()> = lazy(1, |n: &i32| li([], [text(n)]));
+    assert_eq!(unkeyed.first_value(&"key"), None);
+}
@@ -0,0 +1,110 @@
+#![deny(warnings)]
+use sauron::{
+    dom::{events::RemoteEvent, Event, MemoryDom, RenderBackend, Renderer},
+    html::{
+        attributes::{class, id},
+        events::{on, on_click, on_wheel},
+        *,
+    },
+    vdom::{diff, ListenerOptions},
+    Attribute, Node,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[test]
+fn set_listener_options() {
+    let wheel: Attribute<()> = on_wheel(|_| ()).passive().capture();
+    assert_eq!(
+        wheel.listener_options(),
+        Some(ListenerOptions {
+            passive: true,
+            capture: true,
+            ..Default::default()
+        })
+    );
+    let click: Attribute<()> = on_click(|_| ()).prevent_default().stop_propagation().once();
+    assert_eq!(
+        click.listener_options(),
+        Some(ListenerOptions {
+            once: true,
+            prevent_default: true,
+            stop_propagation: true,
+            ..Default::default()
+        })
+    );
+    assert_eq!(
+        on_click(|_| ()).listener_options(),
+        Some(ListenerOptions::default())
+    );
+    // options are only carried by event listeners
+    let plain: Attribute<()> = class("item").passive();
+    assert_eq!(plain.listener_options(), None);
+}
+
+#[test]
+fn options_are_part_of_the_listener_identity() {
+    let view = |options: ListenerOptions| -> Node<()> {
+        div([on_click(|_| ()).with_listener_options(options)], [])
+    };
+    let passive = ListenerOptions {
+        passive: true,
+        ..Default::default()
+    };
+    let old = view(ListenerOptions::default());
+    assert!(diff(&old, &view(ListenerOptions::default())).is_empty());
+    let new = view(passive);
+    assert_eq!(diff(&old, &new).len(), 1);
+    assert!(diff(&new, &view(passive)).is_empty());
+}
+
+#[test]
+fn memory_dom_honors_once() {
+    let msgs = Rc::new(RefCell::new(vec![]));
+    let dispatched = Rc::clone(&msgs);
+    let body = MemoryDom::create_element("body", None);
+    let view: Node<i32> = div(
+        [],
+        [
+            button([id("once"), on("click", |_| 1).once()], []),
+            button([id("always"), on("click", |_| 2).capture()], []),
+        ],
+    );
+    let renderer =
+        Renderer::<MemoryDom, _>::mount(&body, view, move |msg| dispatched.borrow_mut().push(msg));
+    let click = || Event::RemoteEvent(RemoteEvent::new("click"));
+    let buttons = renderer.root_node().children();
+    assert_eq!(
+        buttons[1].listener_options("click"),
+        Some(ListenerOptions {
+            capture: true,
+            ..Default::default()
+        })
+    );
+    assert!(buttons[0].dispatch_event("click", click()));
+    assert!(!buttons[0].dispatch_event("click", click()));
+    assert_eq!(buttons[0].listener_options("click"), None);
+    assert!(buttons[1].dispatch_event("click", click()));
+    assert!(buttons[1].dispatch_event("click", click()));
+    assert_eq!(*msgs.borrow(), [1, 2, 2]);
+}
+
+#[test]
+fn prevent_default_wins_over_passive() {
+    let prevent = ListenerOptions {
+        prevent_default: true,
+        ..Default::default()
+    };
+    let wheel: Attribute<()> = on_wheel(|_| ()).passive().prevent_default();
+    assert_eq!(wheel.listener_options(), Some(prevent));
+    let wheel: Attribute<()> = on_wheel(|_| ()).prevent_default().passive();
+    assert_eq!(wheel.listener_options(), Some(prevent));
+
+    let passive = ListenerOptions {
+        passive: true,
+        ..Default::default()
+    };
+    assert_eq!(passive.merge(prevent), prevent);
+    let wheel: Attribute<()> = on_wheel(|_| ()).with_listener_options(passive.merge(passive));
+    assert_eq!(wheel.listener_options(), Some(passive));
+}
@@ -207,11 +207,7 @@ fn text_changed() {
     dbg!(&diff);
     assert_eq!(
         diff,
-        vec![Patch::replace_node(
-            None,
-            TreePath::new(vec![0, 6, 0]),
-            vec![&leaf("line7_changed")]
-        )]
+        vec![Patch::set_text(TreePath::new(vec![0, 6, 0]), "line7_changed")]
     );
 }
 
@@ -262,11 +258,7 @@ fn text_changed_non_keyed() {
 
     assert_eq!(
         diff,
-        vec![Patch::replace_node(
-            None,
-            TreePath::new(vec![0, 6, 0]),
-            vec![&leaf("line7_changed")]
-        )]
+        vec![Patch::set_text(TreePath::new(vec![0, 6, 0]), "line7_changed")]
     );
 }
 
@@ -355,9 +347,9 @@ fn insert_one_line_at_start() {
     assert_eq!(
         diff,
         vec![
-            Patch::replace_node(None, TreePath::new(vec![0, 2, 0, 0]), vec![&leaf("4")]),
-            Patch::replace_node(None, TreePath::new(vec![0, 1, 0, 0]), vec![&leaf("3")]),
-            Patch::replace_node(None, TreePath::new(vec![0, 0, 0, 0]), vec![&leaf("2")]),
+            Patch::set_text(TreePath::new(vec![0, 2, 0, 0]), "4"),
+            Patch::set_text(TreePath::new(vec![0, 1, 0, 0]), "3"),
+            Patch::set_text(TreePath::new(vec![0, 0, 0, 0]), "2"),
             Patch::insert_before_node(
                 Some(&"div"),
                 TreePath::new(vec![0, 0]),
@@ -468,9 +460,9 @@ fn insert_two_lines_at_start() {
     assert_eq!(
         diff,
         vec![
-            Patch::replace_node(None, TreePath::new(vec![0, 2, 0, 0]), vec![&leaf("5")]),
-            Patch::replace_node(None, TreePath::new(vec![0, 1, 0, 0]), vec![&leaf("4")]),
-            Patch::replace_node(None, TreePath::new(vec![0, 0, 0, 0]), vec![&leaf("3")]),
+            Patch::set_text(TreePath::new(vec![0, 2, 0, 0]), "5"),
+            Patch::set_text(TreePath::new(vec![0, 1, 0, 0]), "4"),
+            Patch::set_text(TreePath::new(vec![0, 0, 0, 0]), "3"),
             Patch::insert_before_node(
                 Some(&"div"),
                 TreePath::new(vec![0, 0]),
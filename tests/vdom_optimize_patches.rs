@@ -0,0 +1,197 @@
+#![deny(warnings)]
+use sauron::{
+    assert_node_eq,
+    html::{
+        attributes::{class, id, key, styles},
+        *,
+    },
+    vdom::{apply_patches, diff, optimize_patches, Attribute, Patch, PatchType, TreePath},
+    Node,
+};
+
+/// apply both the original and the optimized patches into `old`, which must give the same result
+fn assert_equivalent<'a>(old: &Node<()>, patches: Vec<Patch<'a, ()>>) -> Vec<Patch<'a, ()>> {
+    let optimized = optimize_patches(patches.clone());
+    let mut original_patched = old.clone();
+    apply_patches(&mut original_patched, &patches).expect("must apply the original patches");
+    let mut optimized_patched = old.clone();
+    apply_patches(&mut optimized_patched, &optimized).expect("must apply the optimized patches");
+    assert_node_eq!(optimized_patched, original_patched);
+    optimized
+}
+
+fn keyed_list(keys: &[usize]) -> Node<()> {
+    ul(
+        [class("todo")],
+        keys.iter()
+            .map(|k| li([key(*k)], [text(format!("item{k}"))])),
+    )
+}
+
+#[test]
+fn merge_attributes_on_the_same_element() {
+    let old: Node<()> = div([], [span([class("a")], []), span([], [])]);
+    let attrs = [id("x"), class("b"), id("y")];
+    let styles_attr: Attribute<()> = styles([("color", "red")]);
+    let patches = vec![
+        Patch::add_attributes(&"span", TreePath::new([0]), [&attrs[0]]),
+        Patch::add_attributes(&"span", TreePath::new([1]), [&styles_attr]),
+        Patch::add_attributes(&"span", TreePath::new([0]), [&attrs[1], &attrs[2]]),
+    ];
+    let optimized = assert_equivalent(&old, patches);
+    assert_eq!(
+        optimized,
+        [
+            Patch::add_attributes(&"span", TreePath::new([0]), [&attrs[1], &attrs[2]]),
+            Patch::add_attributes(&"span", TreePath::new([1]), [&styles_attr]),
+        ]
+    );
+}
+
+#[test]
+fn cancel_added_then_removed() {
+    let old: Node<()> = div([class("a")], []);
+    let patches = vec![
+        Patch::add_classes(&"div", TreePath::root(), ["b", "c"]),
+        Patch::remove_classes(&"div", TreePath::root(), ["b"]),
+    ];
+    let optimized = assert_equivalent(&old, patches);
+    assert_eq!(
+        optimized,
+        [
+            Patch::add_classes(&"div", TreePath::root(), ["c"]),
+            Patch::remove_classes(&"div", TreePath::root(), ["b"]),
+        ]
+    );
+}
+
+#[test]
+fn insert_then_remove_is_replace() {
+    let old: Node<()> = div([], [span([], []), p([], [])]);
+    let replacement: Node<()> = em([], []);
+    let patches = vec![
+        Patch::insert_before_node(Some(&"span"), TreePath::new([0]), [&replacement]),
+        Patch::remove_node(Some(&"span"), TreePath::new([0])),
+    ];
+    let optimized = assert_equivalent(&old, patches);
+    assert_eq!(
+        optimized,
+        [Patch::replace_node(
+            Some(&"span"),
+            TreePath::new([0]),
+            [&replacement]
+        )]
+    );
+}
+
+#[test]
+fn remove_then_insert_beside_the_sibling_is_replace() {
+    let old: Node<()> = div([], [span([], []), p([], []), b([], [])]);
+    let replacement: Node<()> = em([], []);
+    let patches = vec![
+        Patch::remove_node(Some(&"p"), TreePath::new([1])),
+        Patch::insert_after_node(Some(&"span"), TreePath::new([0]), vec![&replacement]),
+    ];
+    let optimized = assert_equivalent(&old, patches);
+    assert_eq!(
+        optimized,
+        [Patch::replace_node(
+            Some(&"p"),
+            TreePath::new([1]),
+            [&replacement]
+        )]
+    );
+}
+
+#[test]
+fn keep_remove_and_insert_when_the_sibling_is_patched() {
+    let old: Node<()> = div([], [span([], []), p([], []), b([], [])]);
+    let first: Node<()> = em([], []);
+    let second: Node<()> = i([], []);
+    let patches = vec![
+        Patch::insert_before_node(Some(&"b"), TreePath::new([2]), [&first]),
+        Patch::remove_node(Some(&"p"), TreePath::new([1])),
+        Patch::insert_before_node(Some(&"b"), TreePath::new([2]), [&second]),
+    ];
+    let optimized = assert_equivalent(&old, patches);
+    assert_eq!(optimized.len(), 3);
+}
+
+#[test]
+fn merge_consecutive_inserts() {
+    let old: Node<()> = div([], [span([], [])]);
+    let (first, second): (Node<()>, Node<()>) = (em([], []), i([], []));
+    let patches = vec![
+        Patch::insert_after_node(Some(&"span"), TreePath::new([0]), vec![&first]),
+        Patch::insert_after_node(Some(&"span"), TreePath::new([0]), vec![&second]),
+    ];
+    let optimized = assert_equivalent(&old, patches);
+    assert_eq!(
+        optimized,
+        [Patch::insert_after_node(
+            Some(&"span"),
+            TreePath::new([0]),
+            vec![&second, &first]
+        )]
+    );
+}
+
+#[test]
+fn remove_moves_to_the_same_place() {
+    let old: Node<()> = div([], [span([], []), p([], []), b([], []), i([], [])]);
+    let patches = vec![
+        Patch::move_before_node(
+            Some(&"b"),
+            TreePath::new([2]),
+            [TreePath::new([0]), TreePath::new([1])],
+        ),
+        Patch::remove_node(Some(&"i"), TreePath::new([3])),
+    ];
+    let optimized = assert_equivalent(&old, patches);
+    assert_eq!(
+        optimized,
+        [Patch::remove_node(Some(&"i"), TreePath::new([3]))]
+    );
+}
+
+#[test]
+fn optimized_diffs_are_equivalent() {
+    let views: Vec<Node<()>> = vec![
+        keyed_list(&[1, 2, 3, 4, 5]),
+        keyed_list(&[5, 4, 3, 2, 1]),
+        keyed_list(&[1, 3, 5, 7]),
+        keyed_list(&[2, 1, 3, 4, 6, 5]),
+        keyed_list(&[]),
+        div(
+            [class("a b"), styles([("color", "red")])],
+            [text("hello"), span([id("x")], [text("world")])],
+        ),
+        div(
+            [class("b c"), styles([("width", "1px")])],
+            [span([], [text("world")]), text("hello")],
+        ),
+    ];
+    for old in views.iter() {
+        for new in views.iter() {
+            let patches = diff(old, new);
+            let optimized = assert_equivalent(old, patches.clone());
+            assert!(optimized.len() <= patches.len());
+        }
+    }
+}
+
+#[test]
+fn fewer_patches_for_changed_attributes() {
+    let old: Node<()> = div([class("a")], []);
+    let new: Node<()> = div([class("b"), id("x"), styles([("color", "red")])], []);
+    let patches = diff(&old, &new);
+    let optimized = assert_equivalent(&old, patches.clone());
+    let add_attributes = |patches: &[Patch<()>]| {
+        patches
+            .iter()
+            .filter(|p| matches!(p.patch_type, PatchType::AddAttributes { .. }))
+            .count()
+    };
+    assert!(add_attributes(&optimized) <= 1, "{optimized:#?}");
+    assert!(add_attributes(&patches) >= add_attributes(&optimized));
+}
@@ -0,0 +1,186 @@
+#![deny(warnings)]
+use sauron::{
+    html::attributes::{attr, class, id, key},
+    vdom::{fragment, node_list, Selector, SelectorError},
+    *,
+};
+
+fn todo_view() -> Node<()> {
+    main(
+        [id("app")],
+        [
+            h1([], [text("todos")]),
+            ul(
+                [class("todo-list")],
+                [
+                    li([key(1), class("done")], [text("one"), span([], [])]),
+                    text("not an element"),
+                    li([key(2), attr("data-kind", "work task")], [text("two")]),
+                    li(
+                        [key(3), class("done highlight")],
+                        [a([attr("href", "https://example.com/docs")], [])],
+                    ),
+                ],
+            ),
+            footer([], [span([class("count")], [text(1)])]),
+        ],
+    )
+}
+
+fn paths(view: &Node<()>, selector: &str) -> Vec<TreePath> {
+    view.query_selector_all(selector)
+        .expect("must be a valid selector")
+        .into_iter()
+        .map(|(_, path)| path)
+        .collect()
+}
+
+#[test]
+fn tag_id_and_class() {
+    let view = todo_view();
+    assert_eq!(paths(&view, "#app"), vec![TreePath::new([])]);
+    assert_eq!(paths(&view, "h1"), vec![TreePath::new([0])]);
+    assert_eq!(
+        paths(&view, ".done"),
+        vec![TreePath::new([1, 0]), TreePath::new([1, 3])]
+    );
+    assert_eq!(
+        paths(&view, "li.done.highlight"),
+        vec![TreePath::new([1, 3])]
+    );
+    assert_eq!(paths(&view, "*").len(), 10);
+    assert!(paths(&view, "table").is_empty());
+}
+
+#[test]
+fn attribute_selectors() {
+    let view = todo_view();
+    assert_eq!(paths(&view, "[data-kind]"), vec![TreePath::new([1, 2])]);
+    assert_eq!(
+        paths(&view, "[data-kind='work task']"),
+        vec![TreePath::new([1, 2])]
+    );
+    assert_eq!(
+        paths(&view, "[data-kind~=work]"),
+        vec![TreePath::new([1, 2])]
+    );
+    assert!(paths(&view, "[data-kind=work]").is_empty());
+    assert_eq!(
+        paths(&view, r#"a[href^="https://"][href$=docs]"#),
+        vec![TreePath::new([1, 3, 0])]
+    );
+    assert_eq!(
+        paths(&view, "[href*=example]"),
+        vec![TreePath::new([1, 3, 0])]
+    );
+    assert_eq!(paths(&view, "li[key='2']"), vec![TreePath::new([1, 2])]);
+}
+
+#[test]
+fn combinators() {
+    let view = todo_view();
+    assert_eq!(
+        paths(&view, "main span"),
+        vec![TreePath::new([1, 0, 1]), TreePath::new([2, 0])]
+    );
+    assert_eq!(paths(&view, "footer > span"), vec![TreePath::new([2, 0])]);
+    assert!(paths(&view, "main > span").is_empty());
+    assert_eq!(
+        paths(&view, "#app > ul li > a"),
+        vec![TreePath::new([1, 3, 0])]
+    );
+    assert_eq!(
+        paths(&view, "h1, .count"),
+        vec![TreePath::new([0]), TreePath::new([2, 0])]
+    );
+}
+
+#[test]
+fn nth_child_counts_only_elements() {
+    let view = todo_view();
+    assert_eq!(paths(&view, "li:nth-child(2)"), vec![TreePath::new([1, 2])]);
+    assert_eq!(
+        paths(&view, "li:nth-child(odd)"),
+        vec![TreePath::new([1, 0]), TreePath::new([1, 3])]
+    );
+    assert_eq!(
+        paths(&view, "ul > :nth-child(even)"),
+        vec![TreePath::new([1, 2])]
+    );
+    assert_eq!(
+        paths(&view, "li:nth-child(-n+2)"),
+        vec![TreePath::new([1, 0]), TreePath::new([1, 2])]
+    );
+    assert_eq!(
+        paths(&view, "main > :nth-child(2n + 1)"),
+        vec![TreePath::new([0]), TreePath::new([2])]
+    );
+}
+
+#[test]
+fn nth_child_counts_the_elements_of_node_lists_and_fragments() {
+    let view: Node<()> = ul(
+        [],
+        [
+            li([], [text(1)]),
+            node_list([li([], [text(2)]), li([], [text(3)])]),
+            fragment([li([], [text(4)])]),
+        ],
+    );
+    assert_eq!(paths(&view, "li:nth-child(1)"), vec![TreePath::new([0])]);
+    assert_eq!(paths(&view, "li:nth-child(3)"), vec![TreePath::new([2])]);
+    assert_eq!(paths(&view, "li:nth-child(4)"), vec![TreePath::new([3, 0])]);
+    assert_eq!(
+        paths(&view, "li:nth-child(even)"),
+        vec![TreePath::new([1]), TreePath::new([3, 0])]
+    );
+}
+
+#[test]
+fn first_match_and_its_node() {
+    let view = todo_view();
+    let (node, path) = view
+        .query_selector("li.done")
+        .expect("must be valid")
+        .expect("must be found");
+    assert_eq!(path, TreePath::new([1, 0]));
+    assert_eq!(path.find_node_by_path(&view), Some(node));
+    assert_eq!(view.query_selector("video"), Ok(None));
+}
+
+#[test]
+fn nodes_in_fragments_and_components() {
+    let view: Node<()> = fragment([
+        div([class("a")], []),
+        lazy(2, |n| p([class("a")], [text(n)])),
+    ]);
+    assert_eq!(
+        paths(&view, ".a"),
+        vec![TreePath::new([0]), TreePath::new([1])]
+    );
+    let (node, _) = view.query_selector("p").unwrap().unwrap();
+    assert_eq!(node, &p([class("a")], [text(2)]));
+}
+
+#[test]
+fn invalid_selectors() {
+    assert_eq!(Selector::parse(""), Err(SelectorError::Empty));
+    assert_eq!(Selector::parse("li,"), Err(SelectorError::Empty));
+    assert_eq!(
+        Selector::parse("li!"),
+        Err(SelectorError::Unexpected {
+            found: '!',
+            position: 2
+        })
+    );
+    assert_eq!(Selector::parse("[href"), Err(SelectorError::UnexpectedEnd));
+    assert_eq!(
+        Selector::parse("li:hover"),
+        Err(SelectorError::UnsupportedPseudoClass("hover".to_string()))
+    );
+    assert_eq!(
+        Selector::parse("li:nth-child(x)"),
+        Err(SelectorError::InvalidNth("x".to_string()))
+    );
+    assert!(todo_view().query_selector_all("ul >").is_err());
+}
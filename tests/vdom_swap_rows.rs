@@ -36,7 +36,7 @@ fn swap_999() {
     assert_eq!(
         diff,
         vec![
-            Patch::move_before_node(Some(&"li"), TreePath::new([1]), [TreePath::new([998])]),
+            Patch::move_before_node(Some(&"li"), TreePath::new([2]), [TreePath::new([998])]),
             Patch::move_after_node(Some(&"li"), TreePath::new([997]), [TreePath::new([1])]),
         ]
     );
@@ -75,10 +75,12 @@ fn swap_rows_non_keyed() {
     assert_eq!(
         diff,
         vec![
-            Patch::add_attributes(&"div", TreePath::new([1]), vec![&attr("class", "4")],),
-            Patch::replace_node(None, TreePath::new([1, 0]), vec![&leaf("line4")]),
-            Patch::add_attributes(&"div", TreePath::new([3],), [&attr("class", "2")],),
-            Patch::replace_node(None, TreePath::new([3, 0],), [&leaf("line2")],)
+            Patch::add_classes(&"div", TreePath::new([1]), ["4"]),
+            Patch::remove_classes(&"div", TreePath::new([1]), ["2"]),
+            Patch::set_text(TreePath::new([1, 0]), "line4"),
+            Patch::add_classes(&"div", TreePath::new([3]), ["2"]),
+            Patch::remove_classes(&"div", TreePath::new([3]), ["4"]),
+            Patch::set_text(TreePath::new([3, 0]), "line2")
         ]
     );
 }
@@ -125,7 +127,7 @@ fn move_key_2_to_after_node_index_6() {
         diff,
         vec![Patch::move_after_node(
             Some(&"div",),
-            TreePath::new([5]),
+            TreePath::new([6]),
             [TreePath::new([1])]
         ),]
     );
@@ -220,7 +222,7 @@ fn swap_rows_keyed() {
     assert_eq!(
         diff,
         vec![
-            Patch::move_before_node(Some(&"div"), TreePath::new([1]), [TreePath::new([6])]),
+            Patch::move_before_node(Some(&"div"), TreePath::new([2]), [TreePath::new([6])]),
             Patch::move_after_node(Some(&"div",), TreePath::new([5]), [TreePath::new([1])]),
         ]
     );
@@ -261,7 +263,7 @@ fn swap_rows_keyed_6_items() {
     assert_eq!(
         diff,
         vec![
-            Patch::move_before_node(Some(&"div",), TreePath::new([1]), [TreePath::new([4])]),
+            Patch::move_before_node(Some(&"div",), TreePath::new([2]), [TreePath::new([4])]),
             Patch::move_after_node(Some(&"div"), TreePath::new([3]), [TreePath::new([1])]),
         ]
     );
@@ -47,15 +47,6 @@ fn key_lis_1_to_9() {
     assert_eq!(
         diff,
         vec![
-            Patch::insert_after_node(
-                Some(&"div"),
-                TreePath::new(vec![8]),
-                vec![
-                    &element("div", vec![attr("key", "XXX4")], vec![leaf("lineXXX")]),
-                    &element("div", vec![attr("key", "XXX5")], vec![leaf("lineXXX")]),
-                    &element("div", vec![attr("key", "XXX6")], vec![leaf("lineXXX")]),
-                ]
-            ),
             Patch::insert_before_node(
                 Some(&"div"),
                 TreePath::new(vec![0]),
@@ -64,6 +55,15 @@ fn key_lis_1_to_9() {
                     &element("div", vec![attr("key", "XXX2")], vec![leaf("lineXXX")]),
                     &element("div", vec![attr("key", "XXX3")], vec![leaf("lineXXX")]),
                 ]
+            ),
+            Patch::insert_after_node(
+                Some(&"div"),
+                TreePath::new(vec![8]),
+                vec![
+                    &element("div", vec![attr("key", "XXX4")], vec![leaf("lineXXX")]),
+                    &element("div", vec![attr("key", "XXX5")], vec![leaf("lineXXX")]),
+                    &element("div", vec![attr("key", "XXX6")], vec![leaf("lineXXX")]),
+                ]
             )
         ]
     );
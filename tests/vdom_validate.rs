@@ -0,0 +1,159 @@
+#![deny(warnings)]
+use sauron::{
+    html::{attributes::key, *},
+    svg::{g, svg},
+    vdom::{InvalidReason, Leaf, ValidationError},
+    Node, TreePath,
+};
+use std::{cell::Cell, rc::Rc};
+
+#[test]
+fn valid_view() {
+    let view: Node<()> = div(
+        [],
+        [
+            p([], [text("hello"), a([], [text("link")])]),
+            table([], [tbody([], [tr([], [td([], [p([], [text("cell")])])])])]),
+            ul([], [li([key(1)], []), li([key(2)], [])]),
+        ],
+    );
+    assert_eq!(view.validate(), []);
+}
+
+#[test]
+fn duplicate_keys() {
+    let view: Node<()> = div(
+        [],
+        [
+            ul([], [li([key(1)], []), li([key(2)], []), li([key(1)], [])]),
+            fragment([span([key("a")], []), span([key("a")], [])]),
+        ],
+    );
+    assert_eq!(
+        view.validate(),
+        [
+            ValidationError {
+                path: TreePath::new([0, 2]),
+                reason: InvalidReason::DuplicateKey {
+                    key: "1".to_string(),
+                    first: TreePath::new([0, 0]),
+                },
+            },
+            ValidationError {
+                path: TreePath::new([1, 1]),
+                reason: InvalidReason::DuplicateKey {
+                    key: "a".to_string(),
+                    first: TreePath::new([1, 0]),
+                },
+            },
+        ]
+    );
+}
+
+#[test]
+fn children_in_self_closing() {
+    let view: Node<()> = div([], [input([], [text("not rendered")])]);
+    assert_eq!(
+        view.validate(),
+        [ValidationError {
+            path: TreePath::new([0]),
+            reason: InvalidReason::ChildrenInSelfClosing { tag: "input" },
+        }]
+    );
+}
+
+#[test]
+fn invalid_nesting() {
+    let view: Node<()> = div(
+        [],
+        [
+            p([], [span([], [p([], [])])]),
+            table([], [tr([], [td([], [])])]),
+            a([], [b([], [a([], [])])]),
+        ],
+    );
+    let reasons: Vec<(TreePath, InvalidReason)> = view
+        .validate()
+        .into_iter()
+        .map(|error| (error.path, error.reason))
+        .collect();
+    assert_eq!(
+        reasons,
+        [
+            (
+                TreePath::new([0, 0, 0]),
+                InvalidReason::InvalidNesting {
+                    tag: "p",
+                    parent: "p"
+                }
+            ),
+            (
+                TreePath::new([1, 0]),
+                InvalidReason::InvalidNesting {
+                    tag: "tr",
+                    parent: "table"
+                }
+            ),
+            (
+                TreePath::new([2, 0, 0]),
+                InvalidReason::InvalidNesting {
+                    tag: "a",
+                    parent: "a"
+                }
+            ),
+        ]
+    );
+}
+
+#[test]
+fn nesting_is_not_checked_in_svg() {
+    let view: Node<()> = p([], [svg([], [g([], [])])]);
+    assert_eq!(view.validate(), []);
+}
+
+#[test]
+fn error_message() {
+    let view: Node<()> = table([], [tr([], [])]);
+    let errors = view.validate();
+    assert_eq!(
+        errors[0].to_string(),
+        "invalid node at path: [0], <tr> can not be inside <table>"
+    );
+}
+
+#[test]
+fn lazy_views_are_not_built() {
+    let calls = Rc::new(Cell::new(0));
+    let view: Node<()> = table(
+        [],
+        [lazy(1, {
+            let calls = Rc::clone(&calls);
+            move |_: &i32| {
+                calls.set(calls.get() + 1);
+                tr([], [])
+            }
+        })],
+    );
+    assert_eq!(view.validate(), []);
+    assert_eq!(calls.get(), 0);
+
+    // the lazy view is checked once it is built
+    let Node::Element(table) = &view else {
+        unreachable!("must be an element")
+    };
+    let Node::Leaf(Leaf::Lazy(row)) = &table.children()[0] else {
+        unreachable!("must be a lazy node")
+    };
+    row.view();
+    assert_eq!(calls.get(), 1);
+    assert_eq!(
+        view.validate(),
+        [ValidationError {
+            path: TreePath::new([0]),
+            reason: InvalidReason::InvalidNesting {
+                tag: "tr",
+                parent: "table"
+            },
+        }]
+    );
+}
@@ -0,0 +1,170 @@
+#![deny(warnings)]
+use sauron::{
+    html::{
+        attributes::{class, href, id},
+        events::on_click,
+        *,
+    },
+    vdom::{visit::walk_element, Attribute, Element, Fold, Visitor},
+    Node, TreePath,
+};
+
+/// the hrefs of the links and their paths
+#[derive(Default)]
+struct Links(Vec<(String, TreePath)>);
+
+impl<MSG> Visitor<MSG> for Links {
+    fn visit_attribute(&mut self, attr: &Attribute<MSG>, path: &TreePath) {
+        if *attr.name() == "href" {
+            for value in attr.value().iter().filter_map(|v| v.get_simple()) {
+                self.0.push((value.to_string(), path.clone()));
+            }
+        }
+    }
+}
+
+fn links<MSG>(node: &Node<MSG>) -> Vec<(String, TreePath)> {
+    let mut links = Links::default();
+    links.visit_node(node, &TreePath::root());
+    links.0
+}
+
+#[test]
+fn collect_links_with_paths() {
+    let view: Node<()> = div(
+        [],
+        [
+            a([href("/home")], [text("home")]),
+            fragment([text("-"), a([href("/about")], [text("about")])]),
+            // the node list is unrolled into the children of the div
+            node_list([a([href("/blog")], [text("blog")])]),
+        ],
+    );
+    assert_eq!(
+        links(&view),
+        [
+            ("/home".to_string(), TreePath::new([0])),
+            ("/about".to_string(), TreePath::new([1, 1])),
+            ("/blog".to_string(), TreePath::new([2])),
+        ]
+    );
+}
+
+#[test]
+fn visit_into_lazy_views() {
+    let view: Node<()> = ul(
+        [],
+        [lazy("/lazy", |url: &&str| {
+            li([], [a([href(*url)], [text("lazy")])])
+        })],
+    );
+    assert_eq!(links(&view), [("/lazy".to_string(), TreePath::new([0, 0]))]);
+}
+
+#[test]
+fn override_to_skip_subtrees() {
+    /// collect the links, except those inside a `nav`
+    #[derive(Default)]
+    struct ContentLinks(Links);
+
+    impl<MSG> Visitor<MSG> for ContentLinks {
+        fn visit_element(&mut self, element: &Element<MSG>, path: &TreePath) {
+            if *element.tag() != "nav" {
+                walk_element(self, element, path);
+            }
+        }
+
+        fn visit_attribute(&mut self, attr: &Attribute<MSG>, path: &TreePath) {
+            self.0.visit_attribute(attr, path);
+        }
+    }
+
+    let view: Node<()> = div(
+        [],
+        [
+            nav([], [a([href("/home")], [text("home")])]),
+            a([href("/post")], [text("post")]),
+        ],
+    );
+    let mut content_links = ContentLinks::default();
+    content_links.visit_node(&view, &TreePath::root());
+    assert_eq!(
+        content_links.0 .0,
+        [("/post".to_string(), TreePath::new([1]))]
+    );
+}
+
+#[test]
+fn rewrite_urls() {
+    struct Rebase(&'static str);
+
+    impl<MSG> Fold<MSG> for Rebase {
+        fn fold_attribute(
+            &mut self,
+            attr: Attribute<MSG>,
+            _path: &TreePath,
+        ) -> Option<Attribute<MSG>> {
+            if *attr.name() != "href" {
+                return Some(attr);
+            }
+            let url = attr
+                .value()
+                .iter()
+                .find_map(|v| v.get_simple())?
+                .to_string();
+            Some(href(format!("{}{url}", self.0)))
+        }
+    }
+
+    let view: Node<()> = div(
+        [id("main")],
+        [
+            a([href("/home")], [text("home")]),
+            lazy((), |_| a([href("/about")], [text("about")])),
+        ],
+    );
+    let view = Rebase("/app").fold_node(view, &TreePath::root());
+    assert_eq!(
+        view.render_to_string(),
+        r#"<div id="main"><a href="/app/home">home</a><a href="/app/about">about</a></div>"#
+    );
+}
+
+#[test]
+fn strip_listeners() {
+    struct StripListeners;
+
+    impl<MSG> Fold<MSG> for StripListeners {
+        fn fold_attribute(
+            &mut self,
+            mut attr: Attribute<MSG>,
+            _path: &TreePath,
+        ) -> Option<Attribute<MSG>> {
+            attr.value.retain(|v| !v.is_event_listener());
+            (!attr.value.is_empty()).then_some(attr)
+        }
+    }
+
+    let view: Node<()> = div(
+        [],
+        [fragment([button(
+            [class("ok"), on_click(|_| ())],
+            [text("ok")],
+        )])],
+    );
+    let expected: Node<()> = div([], [fragment([button([class("ok")], [text("ok")])])]);
+    assert_eq!(StripListeners.fold_node(view, &TreePath::root()), expected);
+}
+
+#[test]
+fn node_count_does_not_go_into_leaves() {
+    let view: Node<()> = div(
+        [],
+        [
+            span([], [text("one")]),
+            fragment([text("two"), text("three")]),
+        ],
+    );
+    assert_eq!(view.node_count(), 4);
+    assert_eq!(view.descendant_node_count(), 3);
+}
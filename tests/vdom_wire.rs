@@ -0,0 +1,178 @@
+#![deny(warnings)]
+use sauron::{
+    html::{
+        attributes::{class, key, styles, value},
+        events::on_click,
+        *,
+    },
+    vdom::{
+        diff,
+        wire::{
+            self, HandlerId, HandlerRegistry, WireAttribute, WireAttributeValue, WireElement,
+            WireError, WireNode, WirePatch,
+        },
+        EventCallback, ListenerOptions,
+    },
+    Node,
+};
+
+fn list(items: &[&'static str]) -> Node<i32> {
+    ul(
+        [class("list"), styles([("color", "red")])],
+        items.iter().enumerate().map(|(i, item)| {
+            li(
+                [key(*item), on_click(move |_| i as i32)],
+                [text(item), input([value(i)], [])],
+            )
+        }),
+    )
+}
+
+fn ship(patches: &[WirePatch]) -> (Vec<WirePatch>, Vec<WirePatch>) {
+    let json = wire::to_json(patches).expect("must encode json");
+    let binary = wire::to_binary(patches).expect("must encode binary");
+    assert!(binary.len() < json.len());
+    (
+        wire::from_json(&json).expect("must decode json"),
+        wire::from_binary(&binary).expect("must decode binary"),
+    )
+}
+
+#[test]
+fn patches_survive_the_round_trip() {
+    let old = list(&["a", "b", "c"]);
+    let new = list(&["c", "a", "d"]);
+    let patches = diff(&old, &new);
+    assert!(!patches.is_empty());
+
+    let mut registry = HandlerRegistry::new();
+    let wire_patches: Vec<WirePatch> = patches.iter().map(|p| p.to_wire(&mut registry)).collect();
+
+    let (from_json, from_binary) = ship(&wire_patches);
+    assert_eq!(from_json, wire_patches);
+    assert_eq!(from_binary, wire_patches);
+
+    let owned: Vec<_> = from_binary
+        .into_iter()
+        .map(|p| {
+            p.into_patch(&|id| registry.get(id).expect("must be registered").clone())
+                .expect("must convert")
+        })
+        .collect();
+    let applied: Vec<_> = owned.iter().map(|p| p.as_patch()).collect();
+    assert_eq!(applied, patches);
+}
+
+#[test]
+fn listeners_become_handler_ids() {
+    let view = list(&["a", "b"]);
+    let mut registry = HandlerRegistry::new();
+    let wire_node = view.to_wire(&mut registry);
+    assert_eq!(registry.len(), 2);
+
+    let WireNode::Element(ul) = &wire_node else {
+        panic!("expecting an element");
+    };
+    let handler_ids: Vec<_> = ul
+        .children
+        .iter()
+        .flat_map(|li| match li {
+            WireNode::Element(li) => li.attrs.clone(),
+            _ => vec![],
+        })
+        .flat_map(|attr| attr.value)
+        .filter_map(|v| match v {
+            WireAttributeValue::EventListener(id, _options) => Some(id),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(handler_ids, vec![HandlerId(0), HandlerId(1)]);
+
+    assert!(registry.get(HandlerId(1)).is_some());
+    assert!(registry.get(HandlerId(2)).is_none());
+}
+
+#[test]
+fn the_same_listener_is_registered_once() {
+    let listener: EventCallback<i32> = EventCallback::from(|_| 1);
+    let mut registry = HandlerRegistry::new();
+    let id = registry.register(&listener);
+    assert_eq!(registry.register(&listener.clone()), id);
+    assert_eq!(registry.register(&EventCallback::from(|_| 2)), HandlerId(1));
+    assert_eq!(registry.len(), 2);
+
+    // the listener is kept until each of its registrations is released
+    registry.release(id);
+    assert!(registry.get(id).is_some());
+    registry.release(id);
+    assert!(registry.get(id).is_none());
+    assert_eq!(registry.register(&listener), HandlerId(2));
+}
+
+#[test]
+fn nodes_survive_the_round_trip() {
+    let view = list(&["a", "b"]);
+    let mut registry = HandlerRegistry::new();
+    let json = wire::to_json(&view.to_wire(&mut registry)).unwrap();
+    let decoded: WireNode = wire::from_json(&json).unwrap();
+    let node: Node<i32> = decoded
+        .into_node(&|id| registry.get(id).unwrap().clone())
+        .unwrap();
+    assert_eq!(node, view);
+    assert_eq!(node.render_to_string(), view.render_to_string());
+}
+
+#[test]
+fn listener_options_survive_the_round_trip() {
+    let view: Node<i32> = div([on_click(|_| 1).passive().once()], []);
+    let mut registry = HandlerRegistry::new();
+    let wire_node = view.to_wire(&mut registry);
+    let json = wire::to_json(&wire_node).unwrap();
+    let decoded: WireNode = wire::from_json(&json).unwrap();
+    assert_eq!(decoded, wire_node);
+    // the options are restored from the wire, even if the registered handler has none
+    let node: Node<i32> = decoded
+        .into_node(&|id| {
+            registry
+                .get(id)
+                .unwrap()
+                .clone()
+                .with_options(ListenerOptions::default())
+        })
+        .unwrap();
+    assert_eq!(node, view);
+}
+
+fn custom_element(tag: &str, attr: &str) -> WireNode {
+    WireNode::Element(WireElement {
+        namespace: None,
+        tag: tag.to_string(),
+        attrs: vec![WireAttribute {
+            namespace: None,
+            name: attr.to_string(),
+            value: vec![WireAttributeValue::Empty],
+        }],
+        children: vec![],
+        self_closing: false,
+    })
+}
+
+#[test]
+fn unknown_names_are_limited() {
+    let into_node = |node: WireNode| node.into_node::<()>(&|_| unreachable!("has no listener"));
+    // known names are always converted
+    for _ in 0..2000 {
+        into_node(custom_element("div", "class")).expect("must convert");
+    }
+    // the same unknown names are kept once
+    for _ in 0..2000 {
+        into_node(custom_element("my-widget", "data-row")).expect("must convert");
+    }
+    let err = (0..2000)
+        .map(|i| into_node(custom_element("div", &format!("data-{i}"))))
+        .find_map(Result::err)
+        .expect("must run out of names");
+    assert!(matches!(err, WireError::TooManyNames(_)));
+    // the names which were kept still convert
+    into_node(custom_element("my-widget", "data-row")).expect("must convert");
+}